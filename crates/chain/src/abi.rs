@@ -15,11 +15,19 @@ use eosio_scale_info::{
 pub struct ActionInfo {
     pub name: String,
     pub info: Type,
+    /// rust type of the action return value, `None` if the action returns `()`
+    pub result_type: Option<String>,
 }
 
 pub struct TableInfo {
     pub name: String,
     pub info: Type,
+    /// names of the secondary index fields
+    pub key_names: Vec<String>,
+    /// index types of the secondary index fields: `i64`, `i128`, `i256`, `float64` or `float128`
+    pub key_types: Vec<String>,
+    /// version of a `#[chain(table = "name", version = N)]` table, whose rows are prefixed with it
+    pub version: Option<u8>,
 }
 
 pub struct ABIInfo {
@@ -129,6 +137,7 @@ fn native_type_to_abi_type(tp: &str) -> &str {
         "TimePoint" => "time_point",
         "TimePointSec" => "time_point_sec",
         "BlockTimeStampType" => "block_timestamp_type",
+        "Microseconds" => "int64",
         "Name" => "name",
         "&[u8]" => "bytes",
         "String" => "string",
@@ -146,12 +155,150 @@ fn native_type_to_abi_type(tp: &str) -> &str {
     }
 }
 
+/// Splits `s` at the commas which are not nested in `<>`, `()` or `[]`.
+fn split_type_args(s: &str) -> Vec<&str> {
+    let mut args = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = s[start..].trim();
+    if !last.is_empty() {
+        args.push(last);
+    }
+    return args;
+}
+
+/// Structure of a rust type name as far as the ABI is concerned.
+enum RustType<'a> {
+    /// `Option<T>`, `Vec<T>`, `BinaryExtension<T>`, `Box<T>` or `BTreeSet<T>`
+    Wrapper(&'a str, &'a str),
+    /// `BTreeMap<K, V>`
+    Map(&'a str, &'a str),
+    /// `[T; N]`
+    Array(&'a str, &'a str),
+    /// `(A, B, ...)`
+    Tuple(Vec<&'a str>),
+    Plain(&'a str),
+}
+
+fn parse_rust_type(rust_type: &str) -> RustType {
+    let rust_type = rust_type.trim();
+    if rust_type.starts_with('(') && rust_type.ends_with(')') {
+        return RustType::Tuple(split_type_args(&rust_type[1..rust_type.len() - 1]));
+    }
+    if rust_type.starts_with('[') && rust_type.ends_with(']') {
+        if let Some((ty, len)) = rust_type[1..rust_type.len() - 1].rsplit_once(';') {
+            return RustType::Array(ty.trim(), len.trim());
+        }
+    }
+    for wrapper in ["Option", "Vec", "BinaryExtension", "Box", "BTreeSet", "BTreeMap"] {
+        if rust_type.starts_with(wrapper) && rust_type[wrapper.len()..].starts_with('<') && rust_type.ends_with('>') {
+            let inner = &rust_type[wrapper.len() + 1..rust_type.len() - 1];
+            if wrapper == "BTreeMap" {
+                let args = split_type_args(inner);
+                if args.len() == 2 {
+                    return RustType::Map(args[0], args[1]);
+                }
+                break;
+            }
+            return RustType::Wrapper(wrapper, inner);
+        }
+    }
+    return RustType::Plain(rust_type);
+}
+
+/// Converts an ABI type into a part of a struct name, e.g. `uint64[]` to `uint64_array`.
+fn abi_type_to_struct_name_part(abi_type: &str) -> String {
+    return abi_type.replace("[]", "_array").replace('[', "_array").replace(']', "").replace('?', "_opt").replace('$', "_ext");
+}
+
+/// Returns the ABI type of `rust_type`, pushes the `pair_*` and `tuple_*` structs which describe
+/// the tuples in it into `structs`.
+fn rust_type_to_abi_type(rust_type: &str, structs: &mut Vec<ABIStruct>) -> String {
+    match parse_rust_type(rust_type) {
+        RustType::Wrapper(wrapper, inner) => {
+            let inner_abi_type = rust_type_to_abi_type(inner, structs);
+            match wrapper {
+                "Option" => return inner_abi_type + "?",
+                "BinaryExtension" => return inner_abi_type + "$",
+                "Box" => return inner_abi_type,
+                _ => {
+                    if inner_abi_type == "uint8" && wrapper == "Vec" {
+                        return String::from("bytes");
+                    }
+                    return inner_abi_type + "[]";
+                }
+            }
+        }
+        RustType::Map(key, value) => {
+            return tuple_to_abi_type(&[key, value], structs) + "[]";
+        }
+        RustType::Array(ty, len) => {
+            return format!("{}[{}]", rust_type_to_abi_type(ty, structs), len);
+        }
+        RustType::Tuple(types) => {
+            return tuple_to_abi_type(&types, structs);
+        }
+        RustType::Plain(ty) => {
+            return String::from(native_type_to_abi_type(ty));
+        }
+    }
+}
+
+/// Describes a tuple with a `pair_A_B` struct with `first` and `second` fields if it has two elements,
+/// otherwise with a `tuple_A_B_C` struct with `field_0`, `field_1`... fields.
+fn tuple_to_abi_type(types: &[&str], structs: &mut Vec<ABIStruct>) -> String {
+    let abi_types: Vec<String> = types.iter().map(|ty| rust_type_to_abi_type(ty, structs)).collect();
+    let name_parts: Vec<String> = abi_types.iter().map(|ty| abi_type_to_struct_name_part(ty)).collect();
+    let (name, fields) = if abi_types.len() == 2 {
+        (format!("pair_{}", name_parts.join("_")), vec![String::from("first"), String::from("second")])
+    } else {
+        (format!("tuple_{}", name_parts.join("_")), (0..abi_types.len()).map(|i| format!("field_{}", i)).collect())
+    };
+    if !structs.iter().any(|s| s.name == name) {
+        structs.push(ABIStruct {
+            name: name.clone(),
+            base: String::from(""),
+            fields: fields.into_iter().zip(abi_types).map(|(name, ty)| ABIType{name, ty}).collect(),
+        });
+    }
+    return name;
+}
+
+/// Returns the struct and intrinsic type names used by `rust_type`.
+fn get_inner_rust_types(rust_type: &str) -> Vec<&str> {
+    match parse_rust_type(rust_type) {
+        RustType::Wrapper(_, inner) | RustType::Array(inner, _) => {
+            return get_inner_rust_types(inner);
+        }
+        RustType::Map(key, value) => {
+            return [get_inner_rust_types(key), get_inner_rust_types(value)].concat();
+        }
+        RustType::Tuple(types) => {
+            return types.into_iter().flat_map(get_inner_rust_types).collect();
+        }
+        RustType::Plain(ty) => {
+            return vec![ty];
+        }
+    }
+}
+
 fn is_intrinsic_abi_type(name: &str) -> bool {
     match name {
         "bool" | "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" | "f32" | "f64" | "i128" | "u128" |
         "String" |
         "Varint32" | "VarUint32" | "Float128" | "TimePoint" | "TimePointSec" |
-        "BlockTimeStampType" | "Name" | "Checksum160" | "Checksum256" | "Uint256" |
+        "BlockTimeStampType" | "Microseconds" | "Name" | "Checksum160" | "Checksum256" | "Uint256" |
         "Checksum512" | "PublicKey" | "Signature" | "Symbol" | "SymbolCode" | "Asset" |
         "ExtendedAsset"  => {
             return true;
@@ -175,6 +322,11 @@ fn get_last_path_name(path: &Path) -> String {
 }
 
 pub fn verify_abi_structs(main_contract_structs: &Vec<Type>) -> Vec<Type> {
+    return verify_abi_types(main_contract_structs, &Vec::new());
+}
+
+/// Same as `verify_abi_structs`, also collects the structs referenced by the action return types
+fn verify_abi_types(main_contract_structs: &Vec<Type>, actions: &Vec<ActionInfo>) -> Vec<Type> {
     //
     let mut main_contract_structs_map: HashMap<String, &Type> = HashMap::new();
     //<name, full_name>
@@ -246,17 +398,8 @@ pub fn verify_abi_structs(main_contract_structs: &Vec<Type>) -> Vec<Type> {
                 x.fields().iter().for_each(|field|{
                     let field_name = *field.name().unwrap();
                     let rust_type = *field.type_name().unwrap();
-                    if let Some(_) = rust_type.find("Option<") {
-                        let inner_rust_type = &rust_type["Option<".len()..rust_type.len() -1];
-                        check_rust_type(struct_name, field_name, inner_rust_type);
-                    } else if let Some(_) = rust_type.find("Vec<") {
-                        let inner_rust_type = &rust_type["Vec<".len()..rust_type.len() -1];
+                    for inner_rust_type in get_inner_rust_types(rust_type) {
                         check_rust_type(struct_name, field_name, inner_rust_type);
-                    } else if let Some(_) = rust_type.find("BinaryExtension<") {
-                        let inner_rust_type = &rust_type["BinaryExtension<".len()..rust_type.len() -1];
-                        check_rust_type(struct_name, field_name, inner_rust_type);
-                    } else {
-                        check_rust_type(struct_name, field_name, rust_type);
                     }
                 });
             }
@@ -264,7 +407,9 @@ pub fn verify_abi_structs(main_contract_structs: &Vec<Type>) -> Vec<Type> {
                 x.variants().iter().for_each(|v|{
                     let name = *v.name();
                     let rust_type = v.fields()[0].type_name().unwrap();
-                    check_rust_type(struct_name, name, *rust_type);
+                    for inner_rust_type in get_inner_rust_types(rust_type) {
+                        check_rust_type(struct_name, name, inner_rust_type);
+                    }
                 });
             }
             _ => {
@@ -273,6 +418,14 @@ pub fn verify_abi_structs(main_contract_structs: &Vec<Type>) -> Vec<Type> {
         }
     });
 
+    actions.iter().for_each(|action|{
+        if let Some(result_type) = &action.result_type {
+            for inner_rust_type in get_inner_rust_types(result_type) {
+                check_rust_type(&action.name, "return", inner_rust_type);
+            }
+        }
+    });
+
     let mut other_structs: Vec<Type> = Vec::new();
     for (_, ty) in other_structs_map {
         other_structs.push(ty.clone());
@@ -295,9 +448,11 @@ pub fn parse_abi_info(info: &mut ABIInfo) -> String {
     };
 
 
-    let other_structs = verify_abi_structs(&info.structs);
+    let other_structs = verify_abi_types(&info.structs, &info.actions);
     info.structs.extend(other_structs);
 
+    // `pair_*` and `tuple_*` structs of the tuples used by fields and return values
+    let mut tuple_structs: Vec<ABIStruct> = Vec::new();
     info.structs.iter().for_each(|item|{
         match item.type_def() {
             ::eosio_scale_info::TypeDef::Composite(x) => {
@@ -312,26 +467,11 @@ pub fn parse_abi_info(info: &mut ABIInfo) -> String {
                     fields: Vec::new(),
                 };
                 x.fields().iter().for_each(|field|{
-                    let ty: String;
                     let rust_type = *field.type_name().unwrap();
-                    if let Some(_) = rust_type.find("Option<") {
-                        ty = String::from(native_type_to_abi_type(&rust_type["Option<".len()..rust_type.len() -1])) + "?";
-                    } else if let Some(_) = rust_type.find("BinaryExtension<") {
-                        ty = String::from(native_type_to_abi_type(&rust_type["BinaryExtension<".len()..rust_type.len() -1])) + "$";
-                    } else if let Some(_) = rust_type.find("Vec<") {
-                        let inner_rust_type = &rust_type["Vec<".len()..rust_type.len() -1];
-                        if inner_rust_type == "u8" {
-                            ty = String::from("bytes");
-                        } else {
-                            ty = String::from(native_type_to_abi_type(inner_rust_type)) + "[]";
-                        }
-                    } else {
-                        ty = String::from(native_type_to_abi_type(rust_type));
-                    }
                     s.fields.push(
                         ABIType{
                             name: String::from(*field.name().unwrap()),
-                            ty,
+                            ty: rust_type_to_abi_type(rust_type, &mut tuple_structs),
                         }
                     )
                 });
@@ -345,7 +485,7 @@ pub fn parse_abi_info(info: &mut ABIInfo) -> String {
                 };
                 x.variants().iter().for_each(|v|{
                     let rust_type = v.fields()[0].type_name().unwrap();
-                    abi_variant.types.push(native_type_to_abi_type(rust_type).into());
+                    abi_variant.types.push(rust_type_to_abi_type(rust_type, &mut tuple_structs));
                 });
                 abi.variants.push(abi_variant);    
             }
@@ -358,13 +498,28 @@ pub fn parse_abi_info(info: &mut ABIInfo) -> String {
 
     info.tables.iter().for_each(|table|{
         if let ::eosio_scale_info::TypeDef::Composite(_) = table.info.type_def() {
-            let name = table.info.path().segments().last().unwrap();
+            let mut name = String::from(*table.info.path().segments().last().unwrap());
+            if table.version.is_some() {
+                // describe the version prefix of the rows with a wrapper struct
+                let row_name = name + "_row";
+                if !abi.structs.iter().any(|s| s.name == row_name) {
+                    abi.structs.push(ABIStruct {
+                        name: row_name.clone(),
+                        base: String::from(""),
+                        fields: vec![
+                            ABIType{name: String::from("version"), ty: String::from("uint8")},
+                            ABIType{name: String::from("row"), ty: String::from(*table.info.path().segments().last().unwrap())},
+                        ],
+                    });
+                }
+                name = row_name;
+            }
             abi.tables.push(ABITable {
                 name: table.name.clone(),
-                ty: String::from(*name),
+                ty: name,
                 index_type: String::from("i64"),
-                key_names: Vec::new(),
-                key_types: Vec::new(),
+                key_names: table.key_names.clone(),
+                key_types: table.key_types.clone(),
             });
         }
     });
@@ -378,8 +533,21 @@ pub fn parse_abi_info(info: &mut ABIInfo) -> String {
                 ricardian_contract: String::from(""),
             });
         }
+
+        if let Some(result_type) = &action.result_type {
+            abi.action_results.push(ABIActionResult {
+                name: action.name.clone(),
+                result_type: rust_type_to_abi_type(result_type, &mut tuple_structs),
+            });
+        }
     });
 
+    for s in tuple_structs {
+        if !abi.structs.iter().any(|x| x.name == s.name) {
+            abi.structs.push(s);
+        }
+    }
+
     let cmp = |x: &str, y: &str| -> std::cmp::Ordering {
         if x == y {
             return std::cmp::Ordering::Equal;
@@ -402,6 +570,10 @@ pub fn parse_abi_info(info: &mut ABIInfo) -> String {
         cmp(&x.name, &y.name)
     });
 
+    abi.action_results.sort_by(|x, y| -> std::cmp::Ordering {
+        cmp(&x.name, &y.name)
+    });
+
     abi.variants.sort_by(|x, y| -> std::cmp::Ordering {
         cmp(&x.name, &y.name)
     });
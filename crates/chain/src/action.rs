@@ -206,3 +206,12 @@ pub fn get_code_hash(account: Name) -> Checksum256 {
     dec.unpack(&mut result);
     return result.code_hash;
 }
+
+/// Decodes the return value of the first action in a `ChainTester::push_action` result.
+#[cfg(feature = "std")]
+pub fn decode_action_return_value<T: Packer + Default>(tx: &serde_json::Value) -> Option<T> {
+    let data = chaintester::get_action_return_value(tx)?;
+    let mut ret = T::default();
+    ret.unpack(&data);
+    Some(ret)
+}
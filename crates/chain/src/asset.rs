@@ -25,6 +25,12 @@ use crate::vmapi::eosio::{
     check,
 };
 
+use crate::utils::{
+    ParseError,
+    ParseErrorKind,
+    check_parse,
+};
+
 const MAX_AMOUNT: i64 = (1 << 62) - 1;
 const MAX_PRECISION: u8  = 18;
 
@@ -67,20 +73,33 @@ pub struct SymbolCode {
     pub value: u64,
 }
 
+fn parse_symbol_code(sym: &str) -> Result<u64, ParseError> {
+    let raw = sym.as_bytes();
+    if !(raw.len() < 7 && raw.len() > 0) {
+        return Err(ParseError::new(ParseErrorKind::BadLength, "bad symbol name"));
+    }
+
+    let mut value: u64 = 0;
+    for i in (0..raw.len()).rev() {
+        let c = raw[i];
+        if !(c >= 'A' as u8 && c <= 'Z' as u8) {
+            return Err(ParseError::new(ParseErrorKind::BadCharacter, "invald symbol character"));
+        }
+        value <<= 8;
+        value |= c as u64;
+    }
+    Ok(value)
+}
+
 impl SymbolCode {
     ///
     pub fn new(sym: &str) -> Self {
-        let raw = sym.as_bytes();
-        check(raw.len() < 7 && raw.len() > 0, "bad symbol name");
+        check_parse(Self::try_new(sym))
+    }
 
-        let mut value: u64 = 0;
-        for i in (0..raw.len()).rev() {
-            let c = raw[i];
-            check(c >= 'A' as u8 && c <= 'Z' as u8, "invald symbol character");
-            value <<= 8;
-            value |= c as u64;
-        }
-        Self{value}
+    /// Like `new`, but returns an error instead of aborting the action.
+    pub fn try_new(sym: &str) -> Result<Self, ParseError> {
+        Ok(Self{value: parse_symbol_code(sym)?})
     }
 
     ///
@@ -141,20 +160,26 @@ pub struct Symbol {
 impl Symbol {
     ///
     pub fn new(name: &str, precision: u8) -> Self {
-        let raw = name.as_bytes();
-        check(raw.len() < 7 && raw.len() > 0, "bad symbol name");
+        let value = check_parse(parse_symbol_code(name));
+        Self{value: value << 8 | precision as u64}
+    }
 
-        let mut value: u64 = 0;
-        for i in (0..raw.len()).rev() {
-            let c = raw[i];
-            check(c >= 'A' as u8 && c <= 'Z' as u8, "invald symbol character");
-            value <<= 8;
-            value |= c as u64;
+    /// Like `new`, but returns an error instead of aborting the action,
+    /// unlike `new` it also rejects a precision greater than 18.
+    pub fn try_new(name: &str, precision: u8) -> Result<Self, ParseError> {
+        if precision > MAX_PRECISION {
+            return Err(ParseError::new(ParseErrorKind::BadPrecision, "bad symbol precision"));
         }
+        Ok(Self{value: parse_symbol_code(name)? << 8 | precision as u64})
+    }
 
-        value <<= 8;
-        value |= precision as u64;
-        Self{value}
+    /// Parses the `precision,CODE` form returned by `to_string`, e.g. `4,EOS`.
+    pub fn try_from_str(s: &str) -> Result<Self, ParseError> {
+        let (precision, code) = s.split_once(',')
+            .ok_or(ParseError::new(ParseErrorKind::BadCharacter, "bad symbol string"))?;
+        let precision: u8 = precision.parse()
+            .map_err(|_| ParseError::new(ParseErrorKind::BadPrecision, "bad symbol precision"))?;
+        Self::try_new(code, precision)
     }
 
     ///
@@ -220,91 +245,266 @@ enum AssetStringParseStatus {
     FoundSpace,
 }
 
-fn is_amount_within_range(amount: i64) -> bool {
-    return -MAX_AMOUNT <= amount && amount <= MAX_AMOUNT;
+struct ParsedAsset {
+    amount: i128,
+    precision: u8,
+    // symbol code, 0 if the string has no symbol
+    symbol: u64,
 }
 
-impl Asset {
-    ///
-    pub fn new(amount: i64, symbol: Symbol) -> Self {
-        check(is_amount_within_range(amount), "magnitude of asset amount must be less than 2^62");
-        check(symbol.is_valid(), "invalid symbol name");
-        Self{amount, symbol}
+fn parse_asset_string(s: &str, max_amount: i128) -> Result<ParsedAsset, ParseError> {
+    use ParseErrorKind::*;
+    if s.len() == 0 {
+        return Err(ParseError::new(BadLength, "Asset.from_string: empty string"));
     }
+    let mut status = AssetStringParseStatus::Initial;
+    let mut raw = s.as_bytes();
 
-    ///
-    pub fn from_string(s: &str) -> Self {
-        check(s.len() > 0, "Asset.from_string: empty string");
-        let mut status = AssetStringParseStatus::Initial;
-        let mut raw = s.as_bytes();
-
-        let mut minus: bool = false;
-        let mut amount: i64 = 0;
-        let mut symbol: u64 = 0;
-        let mut precision: u8 = 0;
-        let mut raw_symbol: Vec<u8> = Vec::with_capacity(7);
-
-        if raw[0] == '-' as u8 {
-            minus = true;
-            raw = &raw[1..];
-        }
+    let mut minus: bool = false;
+    let mut amount: i128 = 0;
+    let mut precision: u8 = 0;
+    let mut raw_symbol: Vec<u8> = Vec::with_capacity(7);
+
+    if raw[0] == '-' as u8 {
+        minus = true;
+        raw = &raw[1..];
+    }
 
-        for &c in raw {
-            if c == '.' as u8 {
-                check(status == AssetStringParseStatus::Initial, "Asset.from_string: invalid dot character");
-                status = AssetStringParseStatus::FoundDot;
-                continue;
-            } else if c == ' ' as u8 {
-                check(status == AssetStringParseStatus::Initial || status == AssetStringParseStatus::FoundDot, "Asset.from_string: invalid space character");
-                // if status == AssetStringParseStatus::FoundDot {
-                //     check(precision > 0, "Asset.from_string: invalid precision");
-                // }
-                status = AssetStringParseStatus::FoundSpace;
-                continue;
+    for &c in raw {
+        if c == '.' as u8 {
+            if status != AssetStringParseStatus::Initial {
+                return Err(ParseError::new(BadCharacter, "Asset.from_string: invalid dot character"));
             }
+            status = AssetStringParseStatus::FoundDot;
+            continue;
+        } else if c == ' ' as u8 {
+            if status == AssetStringParseStatus::FoundSpace {
+                return Err(ParseError::new(BadCharacter, "Asset.from_string: invalid space character"));
+            }
+            status = AssetStringParseStatus::FoundSpace;
+            continue;
+        }
 
-            match status {
-                AssetStringParseStatus::Initial => {
-                    check(c >= '0' as u8 && c <= '9' as u8, "Asset.from_string: bad amount");
-                    amount *= 10;
-                    amount += (c - '0' as u8) as i64;
-                    check(is_amount_within_range(amount), "bad amount");
+        match status {
+            AssetStringParseStatus::Initial | AssetStringParseStatus::FoundDot => {
+                if !(c >= '0' as u8 && c <= '9' as u8) {
+                    return Err(ParseError::new(BadCharacter, "Asset.from_string: bad amount"));
                 }
-                AssetStringParseStatus::FoundDot => {
-                    check(c >= '0' as u8 && c <= '9' as u8, "Asset.from_string: bad amount");
-                    amount *= 10;
-                    amount += (c - '0' as u8) as i64;
+                amount = match amount.checked_mul(10).and_then(|a| a.checked_add((c - '0' as u8) as i128)) {
+                    Some(a) => a,
+                    None => {
+                        return Err(ParseError::new(BadAmount, "bad amount"));
+                    }
+                };
+                if status == AssetStringParseStatus::FoundDot {
                     precision += 1;
-                    check(precision <= MAX_PRECISION, "Asset.from_string: bad precision");
-                    check(is_amount_within_range(amount), "bad amount");
+                    if precision > MAX_PRECISION {
+                        return Err(ParseError::new(BadPrecision, "Asset.from_string: bad precision"));
+                    }
+                }
+                if amount > max_amount {
+                    return Err(ParseError::new(BadAmount, "bad amount"));
+                }
+            }
+            AssetStringParseStatus::FoundSpace => {
+                if !(c >= 'A' as u8 && c <= 'Z' as u8) {
+                    return Err(ParseError::new(BadCharacter, "Asset.from_string: bad symbol"));
                 }
-                AssetStringParseStatus::FoundSpace => {
-                    check(c >= 'A' as u8 && c <= 'Z' as u8, "Asset.from_string: bad symbol");
-                    raw_symbol.push(c);
-                    check(raw_symbol.len() < 7, "Asset.from_string: bad symbol");
+                raw_symbol.push(c);
+                if raw_symbol.len() >= 7 {
+                    return Err(ParseError::new(BadLength, "Asset.from_string: bad symbol"));
                 }
             }
         }
+    }
+
+    if status == AssetStringParseStatus::FoundSpace && raw_symbol.len() == 0 {
+        return Err(ParseError::new(BadLength, "Asset.from_string: bad symbol"));
+    }
+
+    if minus {
+        amount = -amount;
+    }
+
+    let mut symbol: u64 = 0;
+    for &c in raw_symbol.iter().rev() {
+        symbol <<= 8;
+        symbol |= c as u64;
+    }
+
+    Ok(ParsedAsset{amount, precision, symbol})
+}
+
+/// How digits are dropped when an amount is converted to a lower precision.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Rounding {
+    /// Round toward negative infinity.
+    Floor,
+    /// Round toward positive infinity.
+    Ceil,
+    /// Drop the extra digits.
+    TowardZero,
+    /// Round away from zero if any dropped digit is not zero.
+    AwayFromZero,
+    /// Round to the nearest value, ties away from zero.
+    HalfUp,
+    /// Round to the nearest value, ties to the even neighbour.
+    HalfEven,
+}
+
+fn pow10(n: u8) -> i128 {
+    10i128.pow(n as u32)
+}
+
+// `divisor` must be positive.
+fn div_round(value: i128, divisor: i128, rounding: Rounding) -> i128 {
+    let q = value / divisor;
+    let r = value % divisor;
+    if r == 0 {
+        return q;
+    }
 
-        check(raw_symbol.len() != 0, "Asset.from_string: bad symbol");
+    let away = if value < 0 { q - 1 } else { q + 1 };
+    match rounding {
+        Rounding::Floor => if value < 0 { away } else { q },
+        Rounding::Ceil => if value > 0 { away } else { q },
+        Rounding::TowardZero => q,
+        Rounding::AwayFromZero => away,
+        Rounding::HalfUp => if 2 * r.abs() >= divisor { away } else { q },
+        Rounding::HalfEven => match (2 * r.abs()).cmp(&divisor) {
+            core::cmp::Ordering::Greater => away,
+            core::cmp::Ordering::Less => q,
+            core::cmp::Ordering::Equal => if q % 2 == 0 { q } else { away },
+        },
+    }
+}
+
+/// A fixed-point decimal number, `value` scaled by `10^precision`.
+#[derive(Copy, Clone, Default, Eq, PartialEq)]
+pub struct Decimal {
+    value: i128,
+    precision: u8,
+}
+
+impl Decimal {
+    ///
+    pub fn new(value: i128, precision: u8) -> Self {
+        check(precision <= MAX_PRECISION, "Decimal: bad precision");
+        Self{value, precision}
+    }
 
-        if minus {
-            amount = -amount;
+    ///
+    pub fn value(&self) -> i128 {
+        self.value
+    }
+
+    ///
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Parses a plain decimal number such as `"-12.345"`.
+    pub fn from_str(s: &str) -> Self {
+        check_parse(Self::try_from_str(s))
+    }
+
+    /// Like `from_str`, but returns an error instead of aborting the action.
+    pub fn try_from_str(s: &str) -> Result<Self, ParseError> {
+        let parsed = parse_asset_string(s, i128::MAX / 10)?;
+        if parsed.symbol != 0 {
+            return Err(ParseError::new(ParseErrorKind::BadCharacter, "Decimal.from_str: unexpected symbol"));
         }
+        Ok(Self{value: parsed.amount, precision: parsed.precision})
+    }
 
-        raw_symbol.reverse();
-        for c in raw_symbol {
-            symbol <<= 8;
-            symbol |= c as u64;
+    ///
+    pub fn to_string(&self) -> String {
+        let divisor = pow10(self.precision) as u128;
+        let abs = self.value.unsigned_abs();
+        let mut ret = String::new();
+        if self.value < 0 {
+            ret.push('-');
         }
+        ret += &(abs / divisor).to_string();
+        if self.precision > 0 {
+            let frac = (abs % divisor).to_string();
+            ret.push('.');
+            for _ in frac.len()..self.precision as usize {
+                ret.push('0');
+            }
+            ret += &frac;
+        }
+        ret
+    }
 
-        symbol <<= 8;
-        symbol |= precision as u64;
+    /// Converts to `precision` digits after the decimal point.
+    pub fn rescale(&self, precision: u8, rounding: Rounding) -> Self {
+        check(precision <= MAX_PRECISION, "Decimal: bad precision");
+        let value = if precision >= self.precision {
+            let value = self.value.checked_mul(pow10(precision - self.precision));
+            check(value.is_some(), "Decimal.rescale: overflow");
+            value.unwrap()
+        } else {
+            div_round(self.value, pow10(self.precision - precision), rounding)
+        };
+        Self{value, precision}
+    }
 
-        Self{
-            amount: amount,
-            symbol: Symbol{value: symbol}
+    /// Converts to an asset of `symbol`, rounding to its precision with `rounding`.
+    pub fn to_asset(&self, symbol: Symbol, rounding: Rounding) -> Asset {
+        check(symbol.precision() <= MAX_PRECISION as usize, "Decimal.to_asset: bad precision");
+        let value = self.rescale(symbol.precision() as u8, rounding).value;
+        check(value >= -MAX_AMOUNT as i128 && value <= MAX_AMOUNT as i128, "magnitude of asset amount must be less than 2^62");
+        Asset::new(value as i64, symbol)
+    }
+}
+
+impl From<Asset> for Decimal {
+    fn from(asset: Asset) -> Self {
+        Self{value: asset.amount as i128, precision: asset.symbol.precision() as u8}
+    }
+}
+
+fn is_amount_within_range(amount: i64) -> bool {
+    return -MAX_AMOUNT <= amount && amount <= MAX_AMOUNT;
+}
+
+impl Asset {
+    ///
+    pub fn new(amount: i64, symbol: Symbol) -> Self {
+        check(is_amount_within_range(amount), "magnitude of asset amount must be less than 2^62");
+        check(symbol.is_valid(), "invalid symbol name");
+        Self{amount, symbol}
+    }
+
+    ///
+    pub fn from_string(s: &str) -> Self {
+        check_parse(Self::try_from_string(s))
+    }
+
+    /// Like `from_string`, but returns an error instead of aborting the action.
+    pub fn try_from_string(s: &str) -> Result<Self, ParseError> {
+        let parsed = parse_asset_string(s, MAX_AMOUNT as i128)?;
+        if parsed.symbol == 0 {
+            return Err(ParseError::new(ParseErrorKind::BadLength, "Asset.from_string: bad symbol"));
         }
+
+        Ok(Self{
+            amount: parsed.amount as i64,
+            symbol: Symbol{value: parsed.symbol << 8 | parsed.precision as u64}
+        })
+    }
+
+    /// Parses a plain decimal number such as `"1.23456"` into an asset of `symbol`,
+    /// digits beyond the precision of `symbol` are rounded with `rounding`.
+    pub fn from_decimal_str(s: &str, symbol: Symbol, rounding: Rounding) -> Self {
+        Decimal::from_str(s).to_asset(symbol, rounding)
+    }
+
+    /// Converts the asset to a symbol with the same code and `precision`, e.g. `1.0000 EOS` to `1.00000000 EOS`.
+    pub fn to_precision(&self, precision: u8, rounding: Rounding) -> Self {
+        let symbol = Symbol{value: self.symbol.code().value() << 8 | precision as u64};
+        Decimal::from(*self).to_asset(symbol, rounding)
     }
 
     ///
@@ -390,6 +590,146 @@ impl ops::SubAssign for Asset {
     }
 }
 
+impl ops::Neg for Asset {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            amount: -self.amount,
+            symbol: self.symbol
+        }
+    }
+}
+
+impl ops::Mul<i64> for Asset {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        let amount = self.amount as i128 * rhs as i128;
+        check(amount >= -MAX_AMOUNT as i128, "multiplication underflow");
+        check(amount <= MAX_AMOUNT as i128, "multiplication overflow");
+        Self {
+            amount: amount as i64,
+            symbol: self.symbol
+        }
+    }
+}
+
+impl ops::Mul<Asset> for i64 {
+    type Output = Asset;
+
+    fn mul(self, rhs: Asset) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl ops::MulAssign<i64> for Asset {
+    fn mul_assign(&mut self, rhs: i64) {
+        *self = *self * rhs;
+    }
+}
+
+impl ops::Div<i64> for Asset {
+    type Output = Self;
+
+    fn div(self, rhs: i64) -> Self::Output {
+        check(rhs != 0, "divide by zero");
+        Self {
+            amount: self.amount / rhs,
+            symbol: self.symbol
+        }
+    }
+}
+
+impl ops::DivAssign<i64> for Asset {
+    fn div_assign(&mut self, rhs: i64) {
+        *self = *self / rhs;
+    }
+}
+
+/// Ratio of two assets with the same symbol.
+impl ops::Div<Asset> for Asset {
+    type Output = i64;
+
+    fn div(self, rhs: Asset) -> Self::Output {
+        check(self.symbol == rhs.symbol, "div: bad symbol");
+        check(rhs.amount != 0, "divide by zero");
+        self.amount / rhs.amount
+    }
+}
+
+impl PartialOrd for Asset {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Aborts if the symbols are not the same.
+impl Ord for Asset {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        check(self.symbol == other.symbol, "comparison of assets with different symbols is not allowed");
+        self.amount.cmp(&other.amount)
+    }
+}
+
+impl Asset {
+    /// Returns `None` if the symbols are not the same or the result is out of range.
+    pub fn checked_add(&self, rhs: Asset) -> Option<Asset> {
+        if self.symbol != rhs.symbol {
+            return None;
+        }
+        Self::checked_new(self.amount as i128 + rhs.amount as i128, self.symbol)
+    }
+
+    /// Returns `None` if the symbols are not the same or the result is out of range.
+    pub fn checked_sub(&self, rhs: Asset) -> Option<Asset> {
+        if self.symbol != rhs.symbol {
+            return None;
+        }
+        Self::checked_new(self.amount as i128 - rhs.amount as i128, self.symbol)
+    }
+
+    /// Returns `None` if the result is out of range.
+    pub fn checked_mul(&self, rhs: i64) -> Option<Asset> {
+        Self::checked_new(self.amount as i128 * rhs as i128, self.symbol)
+    }
+
+    /// Returns `None` if `rhs` is zero.
+    pub fn checked_div(&self, rhs: i64) -> Option<Asset> {
+        if rhs == 0 {
+            return None;
+        }
+        Self::checked_new(self.amount as i128 / rhs as i128, self.symbol)
+    }
+
+    /// Computes `self * numerator / denominator` with a 128 bit intermediate, rounding toward zero.
+    /// Returns `None` if `denominator` is zero or the result is out of range.
+    pub fn checked_mul_div(&self, numerator: i64, denominator: i64) -> Option<Asset> {
+        if denominator == 0 {
+            return None;
+        }
+        Self::checked_new(self.amount as i128 * numerator as i128 / denominator as i128, self.symbol)
+    }
+
+    /// Ratio of two assets with the same symbol, returns `None` if the symbols are not the same or `rhs` is zero.
+    pub fn checked_div_asset(&self, rhs: Asset) -> Option<i64> {
+        if self.symbol != rhs.symbol || rhs.amount == 0 {
+            return None;
+        }
+        Some(self.amount / rhs.amount)
+    }
+
+    fn checked_new(amount: i128, symbol: Symbol) -> Option<Asset> {
+        if amount < -MAX_AMOUNT as i128 || amount > MAX_AMOUNT as i128 {
+            return None;
+        }
+        Some(Self {
+            amount: amount as i64,
+            symbol
+        })
+    }
+}
+
 impl Printable for Asset {
     fn print(&self) {
         prints(&self.to_string());
@@ -449,6 +789,120 @@ impl ExtendedAsset {
     pub fn contract(&self) -> Name {
         self.contract
     }
+
+    /// Returns `None` if the contracts or symbols are not the same or the result is out of range.
+    pub fn checked_add(&self, rhs: ExtendedAsset) -> Option<ExtendedAsset> {
+        if self.contract != rhs.contract {
+            return None;
+        }
+        self.quantity.checked_add(rhs.quantity).map(|quantity| Self::new(quantity, self.contract))
+    }
+
+    /// Returns `None` if the contracts or symbols are not the same or the result is out of range.
+    pub fn checked_sub(&self, rhs: ExtendedAsset) -> Option<ExtendedAsset> {
+        if self.contract != rhs.contract {
+            return None;
+        }
+        self.quantity.checked_sub(rhs.quantity).map(|quantity| Self::new(quantity, self.contract))
+    }
+
+    /// Returns `None` if the result is out of range.
+    pub fn checked_mul(&self, rhs: i64) -> Option<ExtendedAsset> {
+        self.quantity.checked_mul(rhs).map(|quantity| Self::new(quantity, self.contract))
+    }
+
+    /// Returns `None` if `rhs` is zero.
+    pub fn checked_div(&self, rhs: i64) -> Option<ExtendedAsset> {
+        self.quantity.checked_div(rhs).map(|quantity| Self::new(quantity, self.contract))
+    }
+}
+
+impl ops::Add for ExtendedAsset {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        check(self.contract == rhs.contract, "add: bad contract");
+        Self::new(self.quantity + rhs.quantity, self.contract)
+    }
+}
+
+impl ops::AddAssign for ExtendedAsset {
+    fn add_assign(&mut self, rhs: ExtendedAsset) {
+        *self = *self + rhs;
+    }
+}
+
+impl ops::Sub for ExtendedAsset {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        check(self.contract == rhs.contract, "sub: bad contract");
+        Self::new(self.quantity - rhs.quantity, self.contract)
+    }
+}
+
+impl ops::SubAssign for ExtendedAsset {
+    fn sub_assign(&mut self, rhs: ExtendedAsset) {
+        *self = *self - rhs;
+    }
+}
+
+impl ops::Neg for ExtendedAsset {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.quantity, self.contract)
+    }
+}
+
+impl ops::Mul<i64> for ExtendedAsset {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self::new(self.quantity * rhs, self.contract)
+    }
+}
+
+impl ops::MulAssign<i64> for ExtendedAsset {
+    fn mul_assign(&mut self, rhs: i64) {
+        *self = *self * rhs;
+    }
+}
+
+impl ops::Div<i64> for ExtendedAsset {
+    type Output = Self;
+
+    fn div(self, rhs: i64) -> Self::Output {
+        Self::new(self.quantity / rhs, self.contract)
+    }
+}
+
+impl ops::DivAssign<i64> for ExtendedAsset {
+    fn div_assign(&mut self, rhs: i64) {
+        *self = *self / rhs;
+    }
+}
+
+impl PartialOrd for ExtendedAsset {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Aborts if the contracts or symbols are not the same.
+impl Ord for ExtendedAsset {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        check(self.contract == other.contract, "comparison of extended assets with different contracts is not allowed");
+        self.quantity.cmp(&other.quantity)
+    }
+}
+
+impl Printable for ExtendedAsset {
+    fn print(&self) {
+        prints(&self.quantity.to_string());
+        prints("@");
+        self.contract.print();
+    }
 }
 
 impl Packer for ExtendedAsset {
@@ -477,3 +931,121 @@ impl Packer for ExtendedAsset {
         dec.get_pos()
     }
 }
+
+impl core::str::FromStr for SymbolCode {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_new(s)
+    }
+}
+
+impl TryFrom<&str> for SymbolCode {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_new(s)
+    }
+}
+
+impl core::str::FromStr for Symbol {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_str(s)
+    }
+}
+
+impl TryFrom<&str> for Symbol {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_from_str(s)
+    }
+}
+
+impl core::str::FromStr for Asset {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_string(s)
+    }
+}
+
+impl TryFrom<&str> for Asset {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_from_string(s)
+    }
+}
+
+impl core::str::FromStr for Decimal {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_str(s)
+    }
+}
+
+impl TryFrom<&str> for Decimal {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_from_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal() {
+        let eos = Symbol::new("EOS", 4);
+        let a = Asset::from_string("1.2345 EOS");
+        assert!(a.to_precision(8, Rounding::TowardZero) == Asset::from_string("1.23450000 EOS"));
+        assert!(a.to_precision(2, Rounding::Floor) == Asset::from_string("1.23 EOS"));
+        assert!(a.to_precision(2, Rounding::Ceil) == Asset::from_string("1.24 EOS"));
+        assert!((-a).to_precision(2, Rounding::Floor) == Asset::from_string("-1.24 EOS"));
+        assert!(Asset::from_decimal_str("0.00005", eos, Rounding::HalfUp) == Asset::new(1, eos));
+        assert!(Asset::from_decimal_str("0.00005", eos, Rounding::HalfEven) == Asset::new(0, eos));
+        assert!(Asset::from_decimal_str("0.00015", eos, Rounding::HalfEven) == Asset::new(2, eos));
+        assert!(Asset::from_decimal_str("-0.00015", eos, Rounding::TowardZero) == Asset::new(-1, eos));
+        assert!(Asset::from_decimal_str("3", eos, Rounding::AwayFromZero) == Asset::new(30000, eos));
+
+        let d = Decimal::from_str("-12.0345");
+        assert_eq!(d.value(), -120345);
+        assert_eq!(d.precision(), 4);
+        assert_eq!(d.to_string(), "-12.0345");
+        assert_eq!(Decimal::new(5, 3).to_string(), "0.005");
+        assert_eq!(Decimal::from(a).rescale(6, Rounding::Floor).to_string(), "1.234500");
+        assert!(d.to_asset(eos, Rounding::Floor) == Asset::new(-120345, eos));
+        assert_eq!(Decimal::try_from_str("170141183460469231731687303715884105728").err().unwrap().kind(), ParseErrorKind::BadAmount);
+        assert_eq!(Decimal::try_from_str("170141183460469231731687303715884105729").err().unwrap().kind(), ParseErrorKind::BadAmount);
+        assert_eq!(Decimal::try_from_str("99999999999999999999999999999999999999999").err().unwrap().kind(), ParseErrorKind::BadAmount);
+    }
+
+    #[test]
+    fn test_asset_arithmetic() {
+        let sym = Symbol::new("EOS", 4);
+        let a = Asset::new(10000, sym);
+        assert!(a * 3 == Asset::new(30000, sym));
+        assert!(3 * a == Asset::new(30000, sym));
+        assert!(a / 3 == Asset::new(3333, sym));
+        assert_eq!(Asset::new(30000, sym) / a, 3);
+        assert!(-a == Asset::new(-10000, sym));
+        assert!(a < a * 2);
+        assert!(a.checked_add(Asset::new(MAX_AMOUNT, sym)).is_none());
+        assert!(a.checked_sub(Asset::new(1, Symbol::new("BTC", 4))).is_none());
+        assert!(a.checked_mul(MAX_AMOUNT).is_none());
+        assert!(a.checked_div(0).is_none());
+        assert!(Asset::new(MAX_AMOUNT, sym).checked_mul_div(3, 4) == Some(Asset::new(MAX_AMOUNT / 4 * 3 + 2, sym)));
+
+        let contract = Name::new("eosio.token");
+        let ea = ExtendedAsset::new(a, contract);
+        assert!(ea + ea == ExtendedAsset::new(a * 2, contract));
+        assert!(ea.checked_add(ExtendedAsset::new(a, Name::new("fake.token"))).is_none());
+        assert!(ea < ea * 2);
+    }
+}
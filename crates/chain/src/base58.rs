@@ -0,0 +1,167 @@
+use crate::{
+    vec::Vec,
+    string::String,
+    check,
+};
+
+use crate::ripemd160::ripemd160;
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+fn digit_value(c: u8) -> Option<u8> {
+    ALPHABET.iter().position(|&a| a == c).map(|i| i as u8)
+}
+
+/// Encodes `data` as a base58 string using the bitcoin alphabet.
+pub fn encode(data: &[u8]) -> String {
+    let zeros = data.iter().take_while(|&&b| b == 0).count();
+    // little-endian base58 digits
+    let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+    for &byte in &data[zeros..] {
+        let mut carry = byte as u32;
+        for d in digits.iter_mut() {
+            carry += (*d as u32) << 8;
+            *d = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut ret = String::with_capacity(zeros + digits.len());
+    for _ in 0..zeros {
+        ret.push('1');
+    }
+    for &d in digits.iter().rev() {
+        ret.push(ALPHABET[d as usize] as char);
+    }
+    ret
+}
+
+/// Decodes a base58 string, returns `None` if it contains a character outside of the alphabet.
+pub fn try_decode(s: &str) -> Option<Vec<u8>> {
+    let raw = s.as_bytes();
+    let zeros = raw.iter().take_while(|&&c| c == b'1').count();
+    // little-endian bytes
+    let mut bytes: Vec<u8> = Vec::with_capacity(raw.len() * 733 / 1000 + 1);
+    for &c in &raw[zeros..] {
+        let mut carry = digit_value(c)? as u32;
+        for b in bytes.iter_mut() {
+            carry += (*b as u32) * 58;
+            *b = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut ret = Vec::with_capacity(zeros + bytes.len());
+    ret.resize(zeros, 0u8);
+    ret.extend(bytes.iter().rev());
+    Some(ret)
+}
+
+/// Decodes a base58 string, aborts the action on an invalid character.
+pub fn decode(s: &str) -> Vec<u8> {
+    match try_decode(s) {
+        Some(data) => data,
+        None => {
+            check(false, "base58: invalid character");
+            Vec::new()
+        }
+    }
+}
+
+fn checksum(data: &[u8], suffix: &str) -> [u8; 4] {
+    let mut buf = Vec::with_capacity(data.len() + suffix.len());
+    buf.extend_from_slice(data);
+    buf.extend_from_slice(suffix.as_bytes());
+    let digest = ripemd160(&buf);
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+/// Encodes `data` followed by the first 4 bytes of `ripemd160(data || suffix)`,
+/// the format of `PUB_K1_`/`SIG_K1_` style strings, where `suffix` is the key type ("K1", "R1", "WA").
+/// Legacy `EOS` public keys use an empty suffix.
+pub fn encode_with_checksum(data: &[u8], suffix: &str) -> String {
+    let mut buf = Vec::with_capacity(data.len() + 4);
+    buf.extend_from_slice(data);
+    buf.extend_from_slice(&checksum(data, suffix));
+    encode(&buf)
+}
+
+/// Reverse of [`encode_with_checksum`], aborts the action if the checksum does not match.
+pub fn decode_with_checksum(s: &str, suffix: &str) -> Vec<u8> {
+    let mut data = decode(s);
+    check(data.len() > 4, "base58: data too short");
+    let pos = data.len() - 4;
+    check(data[pos..] == checksum(&data[..pos], suffix), "base58: checksum mismatch");
+    data.truncate(pos);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base58() {
+        assert_eq!(encode(b""), "");
+        assert_eq!(encode(b"hello world"), "StV1DL6CwTryKyV");
+        assert_eq!(encode(&[0, 0, 0x28, 0x7f, 0xb4, 0xcd]), "11233QC4");
+        assert_eq!(decode("StV1DL6CwTryKyV"), b"hello world");
+        assert_eq!(decode("11233QC4"), [0, 0, 0x28, 0x7f, 0xb4, 0xcd]);
+        assert!(try_decode("0OIl").is_none());
+    }
+
+    #[test]
+    fn test_ripemd160() {
+        assert_eq!(crate::utils::decode_hex("9c1185a5c5e9fc54612808977ee8f548b2258d31"), ripemd160(b""));
+        assert_eq!(crate::utils::decode_hex("8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"), ripemd160(b"abc"));
+        assert_eq!(
+            crate::utils::decode_hex("12a053384a9c0c88e405a06c27dcf49ada62eb2b"),
+            ripemd160(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")
+        );
+    }
+
+    #[test]
+    fn test_key_strings() {
+        use crate::structs::{PublicKey, ECCPublicKey, Signature};
+        use crate::serializer::Encoder;
+
+        let key = ECCPublicKey::from_hex("02c0ded2bc1f1305fb0faac5e6c03ee3a1924234985427b6167ca569d13df435cf");
+        let legacy = "EOS6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5GDW5CV";
+        assert!(PublicKey::from_str(legacy) == PublicKey::K1(key));
+        assert!(PublicKey::from_str("PUB_K1_6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5BoDq63") == PublicKey::K1(key));
+        assert_eq!(PublicKey::K1(key).to_string(), legacy);
+        assert_eq!(ECCPublicKey::from_str(legacy).to_string(), legacy);
+
+        let r1 = "PUB_R1_6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5Bpuyty";
+        assert!(PublicKey::from_str(r1) == PublicKey::R1(key));
+        assert_eq!(PublicKey::from_str(r1).to_string(), r1);
+
+        let wa = "PUB_WA_6rmcnz9pYLh35CJMeFQka5NNEsK9K2h7LJ2mcuobzHjGgrMhY4pNrrjTg81ubPMfs";
+        match PublicKey::from_str(wa) {
+            PublicKey::WebAuth(x) => assert_eq!(x.rpid, "localhost"),
+            _ => panic!("not a WebAuthN key"),
+        }
+        assert_eq!(PublicKey::from_str(wa).to_string(), wa);
+
+        let hex = "20331f956b5b344e5d225c857cceac8183a90dd883201510e34b5cd60aac0d7da015f7da1a6a53ef5c6572050826b1d8a70e56abbe815a513d7c796314c59774a7";
+        let k1 = "SIG_K1_KbSF8BCNVA95KzR1qLmdn4VnxRoLVFQ1fZ8VV5gVdW1hLfGBdcwEc93hF7FBkWZip1tq2Ps27UZxceaR3hYwAjKL7j59q8";
+        assert!(Signature::from_str(k1) == Signature::from_hex(hex));
+        assert_eq!(Signature::from_hex(hex).to_string(), k1);
+
+        let r1 = "SIG_R1_KbSF8BCNVA95KzR1qLmdn4VnxRoLVFQ1fZ8VV5gVdW1hLfGBdcwEc93hF7FBkWZip1tq2Ps27UZxceaR3hYwAjKL7wkpby";
+        assert_eq!(Signature::from_str(r1).to_string(), r1);
+
+        let wa = "SIG_WA_47PB1qZicum6buZg7bnENmmM2c4wusB5rYnNNLWdLdv5QNbZGbXtYLAYJV9SQnWzHS3a7ogyfmmCREH7EcEEvkyebb13eFVfY83wpRqD";
+        let sig = Signature::from_str(wa);
+        assert_eq!(Encoder::pack(&sig).len(), 66 + 4 + 3);
+        assert_eq!(sig.to_string(), wa);
+    }
+}
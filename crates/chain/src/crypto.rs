@@ -1,4 +1,5 @@
 use crate::vmapi::crypto;
+use crate::vmapi::eosio_ex;
 
 use crate::structs::*;
 
@@ -8,6 +9,7 @@ use crate::serializer::{
 };
 
 use crate::vec;
+use crate::vec::Vec;
 
 ///
 pub fn assert_sha256(data: &[u8], hash: &Checksum256) {
@@ -73,3 +75,299 @@ pub fn assert_recover_key(digest: &Checksum256, sig: &Signature, pubkey: &Public
     let _pubkey = Encoder::pack(pubkey);
     crypto::assert_recover_key(digest, _sig.as_ptr(), _sig.len(), _pubkey.as_ptr(), _pubkey.len());
 }
+
+///
+pub fn sha3(data: &[u8]) -> Checksum256 {
+    let mut hash = Checksum256::default();
+    eosio_ex::sha3(data, &mut hash.data, false);
+    return hash;
+}
+
+/// Ethereum flavoured Keccak-256, which differs from `sha3` in the padding.
+pub fn keccak256(data: &[u8]) -> Checksum256 {
+    let mut hash = Checksum256::default();
+    eosio_ex::sha3(data, &mut hash.data, true);
+    return hash;
+}
+
+/// Recovers the 65 bytes uncompressed secp256k1 public key from a compact signature,
+/// which is a recovery byte (27..=34) followed by `r` and `s`.
+/// Returns `None` if the signature is invalid.
+pub fn k1_recover(sig: &[u8; 65], digest: &Checksum256) -> Option<[u8; 65]> {
+    let mut pubkey = [0u8; 65];
+    if eosio_ex::k1_recover(sig, &digest.data, &mut pubkey) != 0 {
+        return None;
+    }
+    return Some(pubkey);
+}
+
+/// BLAKE2b compression function F as defined in EIP-152.
+pub fn blake2_f(rounds: u32, state: &[u8; 64], msg: &[u8; 128], t0_offset: &[u8; 8], t1_offset: &[u8; 8], final_block: bool) -> Option<[u8; 64]> {
+    let mut result = [0u8; 64];
+    if eosio_ex::blake2_f(rounds, state, msg, t0_offset, t1_offset, final_block, &mut result) != 0 {
+        return None;
+    }
+    return Some(result);
+}
+
+/// Point of the alt_bn128 G1 group with big-endian coordinates, all zeros is the point at infinity.
+#[derive(Clone, Copy, Eq, PartialEq, Default, Debug)]
+pub struct G1Point {
+    ///
+    pub x: [u8; 32],
+    ///
+    pub y: [u8; 32],
+}
+
+impl G1Point {
+    ///
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut ret = [0u8; 64];
+        ret[..32].copy_from_slice(&self.x);
+        ret[32..].copy_from_slice(&self.y);
+        return ret;
+    }
+
+    ///
+    pub fn from_bytes(data: &[u8; 64]) -> Self {
+        let mut ret = Self::default();
+        ret.x.copy_from_slice(&data[..32]);
+        ret.y.copy_from_slice(&data[32..]);
+        return ret;
+    }
+}
+
+/// Point of the alt_bn128 G2 group. Coordinates are elements of Fq2 encoded as in EIP-197,
+/// the imaginary part first: `x = x[0] * i + x[1]`, and the same for `y`.
+#[derive(Clone, Copy, Eq, PartialEq, Default, Debug)]
+pub struct G2Point {
+    ///
+    pub x: [[u8; 32]; 2],
+    ///
+    pub y: [[u8; 32]; 2],
+}
+
+impl G2Point {
+    ///
+    pub fn to_bytes(&self) -> [u8; 128] {
+        let mut ret = [0u8; 128];
+        ret[..32].copy_from_slice(&self.x[0]);
+        ret[32..64].copy_from_slice(&self.x[1]);
+        ret[64..96].copy_from_slice(&self.y[0]);
+        ret[96..].copy_from_slice(&self.y[1]);
+        return ret;
+    }
+}
+
+///
+pub fn alt_bn128_add(op1: &G1Point, op2: &G1Point) -> Option<G1Point> {
+    let mut result = [0u8; 64];
+    if eosio_ex::alt_bn128_add(&op1.to_bytes(), &op2.to_bytes(), &mut result) != 0 {
+        return None;
+    }
+    return Some(G1Point::from_bytes(&result));
+}
+
+/// Multiplies `g1` by the big-endian `scalar`.
+pub fn alt_bn128_mul(g1: &G1Point, scalar: &[u8; 32]) -> Option<G1Point> {
+    let mut result = [0u8; 64];
+    if eosio_ex::alt_bn128_mul(&g1.to_bytes(), scalar, &mut result) != 0 {
+        return None;
+    }
+    return Some(G1Point::from_bytes(&result));
+}
+
+/// Checks whether the product of the pairings of `pairs` equals one.
+/// Returns `None` if any point is invalid.
+pub fn alt_bn128_pair(pairs: &[(G1Point, G2Point)]) -> Option<bool> {
+    let mut data: Vec<u8> = Vec::with_capacity(pairs.len() * 192);
+    for (g1, g2) in pairs {
+        data.extend_from_slice(&g1.to_bytes());
+        data.extend_from_slice(&g2.to_bytes());
+    }
+    match eosio_ex::alt_bn128_pair(&data) {
+        0 => Some(true),
+        1 => Some(false),
+        _ => None,
+    }
+}
+
+/// Big-endian unsigned integer of arbitrary length, used by `mod_exp`.
+#[derive(Clone, Eq, PartialEq, Default, Debug)]
+pub struct BigUint(pub Vec<u8>);
+
+/// Returns `base ^ exp % modulus`, left padded to the length of `modulus`.
+/// Returns `None` if `modulus` is empty.
+pub fn mod_exp(base: &BigUint, exp: &BigUint, modulus: &BigUint) -> Option<BigUint> {
+    let mut result = vec![0u8; modulus.0.len()];
+    if eosio_ex::mod_exp(&base.0, &exp.0, &modulus.0, &mut result) != 0 {
+        return None;
+    }
+    return Some(BigUint(result));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::decode_hex;
+
+    fn g1(hex: &str) -> G1Point {
+        return G1Point::from_bytes(&decode_hex(hex).try_into().unwrap());
+    }
+
+    fn g2_generator() -> G2Point {
+        let mut ret = G2Point::default();
+        ret.x[0].copy_from_slice(&decode_hex("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"));
+        ret.x[1].copy_from_slice(&decode_hex("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"));
+        ret.y[0].copy_from_slice(&decode_hex("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"));
+        ret.y[1].copy_from_slice(&decode_hex("12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"));
+        return ret;
+    }
+
+    #[test]
+    fn test_hashes() {
+        let long = vec![0x61u8; 1000];
+        assert_eq!(sha1(b"abc").data.to_vec(), decode_hex("a9993e364706816aba3e25717850c26c9cd0d89d"));
+        assert_eq!(sha1(&long).data.to_vec(), decode_hex("291e9a6c66994949b57ba5e650361e98fc36b1ba"));
+        assert_eq!(sha256(b"abc").data.to_vec(), decode_hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+        assert_eq!(sha256(&long).data.to_vec(), decode_hex("41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3"));
+        assert_eq!(sha512(b"abc").data.to_vec(), decode_hex("ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"));
+        assert_eq!(sha512(&long).data.to_vec(), decode_hex("67ba5535a46e3f86dbfbed8cbbaf0125c76ed549ff8b0b9e03e0c88cf90fa634fa7b12b47d77b694de488ace8d9a65967dc96df599727d3292a8d9d447709c97"));
+        assert_eq!(ripemd160(b"abc").data.to_vec(), decode_hex("8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"));
+
+        assert_sha1(b"abc", &sha1(b"abc"));
+        assert_sha256(&long, &sha256(&long));
+        assert_sha512(b"abc", &sha512(b"abc"));
+        assert_ripemd160(b"abc", &ripemd160(b"abc"));
+    }
+
+    #[test]
+    fn test_recover_key() {
+        let digest = sha256(b"hello");
+        let vectors = [
+            (
+                "0020af71f27c68e604c242e8677302eb378ab5be25b8d48ee372ec4db660450808637d5a5f4ec986991d880e44a2b637e6eb61ff44623949999e9f827df15dc4e2a4",
+                "000294f133b59c20c7e1f53f6a6695c22a6b2d3698778dae057a260fb3d22d78425e",
+            ),
+            (
+                "0120d07465b4e43e018147178c6a1a04936977284469ca3318b94ba143ee373cfbb3352d9aa3a6cdd4663cc16e0028dee2068ad579a04e17b743d1ca5654ed487d35",
+                "01037f7464e9a99ed8b50b67712ae9b76bf1d80ffe9430f2e94055d6dbb336f5c745",
+            ),
+        ];
+        for (sig, pubkey) in vectors {
+            let mut _sig = Signature::default();
+            _sig.unpack(&decode_hex(sig));
+            let mut _pubkey = PublicKey::default();
+            _pubkey.unpack(&decode_hex(pubkey));
+
+            let recovered = recover_key(&digest, &_sig);
+            assert_eq!(Encoder::pack(&recovered), decode_hex(pubkey));
+            assert_recover_key(&digest, &_sig, &_pubkey);
+        }
+    }
+
+    #[test]
+    fn test_keccak() {
+        assert_eq!(keccak256(b"").data, Checksum256::from_hex("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").data);
+        assert_eq!(keccak256(b"abc").data, Checksum256::from_hex("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45").data);
+        assert_eq!(sha3(b"").data, Checksum256::from_hex("a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a").data);
+        assert_eq!(sha3(b"abc").data, Checksum256::from_hex("3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532").data);
+        // more than one block
+        let data = vec![0x61u8; 200];
+        assert_ne!(keccak256(&data).data, keccak256(&data[..199]).data);
+    }
+
+    #[test]
+    fn test_blake2_f() {
+        // test vector 5 of EIP-152, which is BLAKE2b-512("abc")
+        let state: [u8; 64] = decode_hex("48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b").try_into().unwrap();
+        let mut msg = [0u8; 128];
+        msg[..3].copy_from_slice(b"abc");
+        let t0 = [3u8, 0, 0, 0, 0, 0, 0, 0];
+        let t1 = [0u8; 8];
+        let ret = blake2_f(12, &state, &msg, &t0, &t1, true).unwrap();
+        assert_eq!(ret.to_vec(), decode_hex("ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"));
+        assert_ne!(blake2_f(12, &state, &msg, &t0, &t1, false).unwrap(), ret);
+    }
+
+    #[test]
+    fn test_k1_recover() {
+        let digest = Checksum256::from_hex("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+        let sig: [u8; 65] = decode_hex("1ce625915f0c96c570ec5fe9855531f4613a2335b03cb34fc0e3ebc329ff955146bd78fb9c24c56b94db8c119165a90eb3313347d07af4e294db9616c428258492").try_into().unwrap();
+        let pubkey = k1_recover(&sig, &digest).unwrap();
+        assert_eq!(pubkey.to_vec(), decode_hex("044e3b81af9c2234cad09d679ce6035ed1392347ce64ce405f5dcd36228a25de6e47fd35c4215d1edf53e6f83de344615ce719bdb0fd878f6ed76f06dd277956de"));
+
+        let mut bad = sig;
+        bad[0] = 26;
+        assert_eq!(k1_recover(&bad, &digest), None);
+        let mut bad = sig;
+        bad[1..33].fill(0);
+        assert_eq!(k1_recover(&bad, &digest), None);
+
+        let secret: [u8; 32] = decode_hex("0102030405060708091011121314151617181920212223242526272829303132").try_into().unwrap();
+        let nonce: [u8; 32] = decode_hex("7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d1e0f9a8b7c6d5e4f3a2b1c0d9e8f7a6b").try_into().unwrap();
+        let sig = crate::crypto_primitives::secp256k1::sign(&secret, &nonce, &digest.data);
+        assert_eq!(k1_recover(&sig, &digest), Some(crate::crypto_primitives::secp256k1::public_key(&secret)));
+    }
+
+    #[test]
+    fn test_alt_bn128_add_mul() {
+        let g = g1("00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002");
+        let g2 = g1("030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd315ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4");
+        let g3 = g1("0769bf9ac56bea3ff40232bcb1b6bd159315d84715b8e679f2d355961915abf02ab799bee0489429554fdb7c8d086475319e63b40b9c5b57cdf1ff3dd9fe2261");
+        assert_eq!(alt_bn128_add(&g, &g), Some(g2));
+        assert_eq!(alt_bn128_add(&g, &g2), Some(g3));
+        assert_eq!(alt_bn128_add(&g, &G1Point::default()), Some(g));
+
+        let mut scalar = [0u8; 32];
+        scalar[28..].copy_from_slice(&123456789u32.to_be_bytes());
+        assert_eq!(alt_bn128_mul(&g, &scalar), Some(g1("142a7688cf05c29f7593351e1b86eb87e3ad5dcb1b0fc3d853e9852040c57019136b5d7e238ae6edc22d1fba5a2dcde8a7b0df53b0c4af7f600e6a0c4610c899")));
+        let mut scalar = [0u8; 32];
+        scalar[31] = 3;
+        assert_eq!(alt_bn128_mul(&g, &scalar), Some(g3));
+
+        let mut neg = g;
+        neg.y.copy_from_slice(&decode_hex("30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45"));
+        assert_eq!(alt_bn128_add(&g, &neg), Some(G1Point::default()));
+
+        let mut bad = g;
+        bad.y[31] = 3;
+        assert_eq!(alt_bn128_add(&g, &bad), None);
+        assert_eq!(alt_bn128_mul(&bad, &scalar), None);
+    }
+
+    #[test]
+    fn test_alt_bn128_pair() {
+        let g = g1("00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002");
+        let g2 = g1("030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd315ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4");
+        let mut neg = g;
+        neg.y.copy_from_slice(&decode_hex("30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45"));
+        let q = g2_generator();
+
+        assert_eq!(alt_bn128_pair(&[]), Some(true));
+        assert_eq!(alt_bn128_pair(&[(g, q)]), Some(false));
+        assert_eq!(alt_bn128_pair(&[(g2, q), (neg, q), (neg, q)]), Some(true));
+        assert_eq!(alt_bn128_pair(&[(g2, q), (neg, q)]), Some(false));
+
+        let mut bad = q;
+        bad.y[1][31] ^= 1;
+        assert_eq!(alt_bn128_pair(&[(g, bad)]), None);
+    }
+
+    #[test]
+    fn test_mod_exp() {
+        let ret = mod_exp(&BigUint(vec![3]), &BigUint(vec![5]), &BigUint(vec![0, 7])).unwrap();
+        assert_eq!(ret, BigUint(vec![0, 5]));
+
+        // Fermat's little theorem with the secp256k1 field modulus, from EIP-198
+        let base = BigUint(vec![3]);
+        let exp = BigUint(decode_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e"));
+        let modulus = BigUint(decode_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"));
+        let mut one = vec![0u8; 32];
+        one[31] = 1;
+        assert_eq!(mod_exp(&base, &exp, &modulus), Some(BigUint(one)));
+
+        assert_eq!(mod_exp(&base, &exp, &BigUint(vec![0, 0])), Some(BigUint(vec![0, 0])));
+        assert_eq!(mod_exp(&base, &exp, &BigUint(vec![])), None);
+    }
+}
@@ -0,0 +1,152 @@
+//! Pure Rust implementations of the crypto host functions, including the ones of the
+//! crypto_primitives protocol feature, used by the off-chain vm api so that unit tests
+//! get the same results as the chain without a tester server.
+//! They are checked against the published test vectors of NIST, RFC 6979 and the EIPs
+//! of the precompiles, since audited crates can not be used as dependencies here yet.
+
+mod bigint;
+mod field;
+mod ecdsa;
+pub mod sha1;
+pub mod sha2;
+pub mod keccak;
+pub mod blake2;
+pub mod secp256k1;
+pub mod secp256r1;
+pub mod bn128;
+
+pub use bigint::BigNat;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::decode_hex;
+
+    #[test]
+    fn test_nist_hash_vectors() {
+        // FIPS 180-2 and FIPS 202 examples
+        let two_blocks = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+        let two_blocks_512 = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+        let million = vec![b'a'; 1_000_000];
+
+        assert_eq!(sha1::sha1(b"abc").to_vec(), decode_hex("a9993e364706816aba3e25717850c26c9cd0d89d"));
+        assert_eq!(sha1::sha1(two_blocks).to_vec(), decode_hex("84983e441c3bd26ebaae4aa1f95129e5e54670f1"));
+        assert_eq!(sha1::sha1(&million).to_vec(), decode_hex("34aa973cd4c4daa4f61eeb2bdbad27316534016f"));
+
+        assert_eq!(sha2::sha256(b"").to_vec(), decode_hex("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"));
+        assert_eq!(sha2::sha256(two_blocks).to_vec(), decode_hex("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"));
+        assert_eq!(sha2::sha256(&million).to_vec(), decode_hex("cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"));
+
+        assert_eq!(sha2::sha512(b"").to_vec(), decode_hex("cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"));
+        assert_eq!(sha2::sha512(two_blocks_512).to_vec(), decode_hex("8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909"));
+        assert_eq!(sha2::sha512(&million).to_vec(), decode_hex("e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973ebde0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b"));
+
+        assert_eq!(keccak::sha3_256(two_blocks).to_vec(), decode_hex("41c0dba2a9d6240849100376a8235e2c82e1b9998a999e21db32dd97496d3376"));
+        assert_eq!(keccak::sha3_256(two_blocks_512).to_vec(), decode_hex("916f6061fe879741ca6469b43971dfdb28b1a32dc36cb3254e812be27aad1d18"));
+        assert_eq!(keccak::sha3_256(&million).to_vec(), decode_hex("5c8875ae474a3634ba4fd55ec85bffd661f32aca75c6d699d0cdcb6c115891c1"));
+    }
+
+    #[test]
+    fn test_eip152_vectors() {
+        let state: [u8; 64] = decode_hex("48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b").try_into().unwrap();
+        let mut msg = [0u8; 128];
+        msg[..3].copy_from_slice(b"abc");
+        let t0 = [3u8, 0, 0, 0, 0, 0, 0, 0];
+        let t1 = [0u8; 8];
+        // test vectors 4, 6 and 7, vector 5 is in the tests of `crypto`
+        assert_eq!(blake2::blake2_f(0, &state, &msg, &t0, &t1, true).to_vec(), decode_hex("08c9bcf367e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d282e6ad7f520e511f6c3e2b8c68059b9442be0454267ce079217e1319cde05b"));
+        assert_eq!(blake2::blake2_f(12, &state, &msg, &t0, &t1, false).to_vec(), decode_hex("75ab69d3190a562c51aef8d88f1c2775876944407270c42c9844252c26d2875298743e7f6d5ea2f2d3e8d226039cd31b4e426ac4f2d3d666a610c2116fde4735"));
+        assert_eq!(blake2::blake2_f(1, &state, &msg, &t0, &t1, true).to_vec(), decode_hex("b63a380cb2897d521994a85234ee2c181b5f844d2c624c002677e9703449d2fba551b3a8333bcdf5f2f7e08993d53923de3d64fcc68c034e717b9293fed7a421"));
+    }
+
+    #[test]
+    fn test_eip196_vectors() {
+        // chfast1 of the bn256Add and bn256ScalarMul tests of the precompiles
+        let a: [u8; 64] = decode_hex("18b18acfb4c2c30276db5411368e7185b311dd124691610c5d3b74034e093dc9063c909c4720840cb5134cb9f59fa749755796819658d32efc0d288198f37266").try_into().unwrap();
+        let b: [u8; 64] = decode_hex("07c2b7f58a84bd6145f00c9c2bc0bb1a187f20ff2c92963a88019e7c6a014eed06614e20c147e940f2d70da3f74c9a17df361706a4485c742bd6788478fa17d7").try_into().unwrap();
+        assert_eq!(bn128::add(&a, &b).unwrap().to_vec(), decode_hex("2243525c5efd4b9c3d3c45ac0ca3fe4dd85e830a4ce6b65fa1eeaee202839703301d1d33be6da8e509df21cc35964723180eed7532537db9ae5e7d48f195c915"));
+
+        let p: [u8; 64] = decode_hex("2bd3e6d0f3b142924f5ca7b49ce5b9d54c4703d7ae5648e61d02268b1a0a9fb721611ce0a6af85915e2f1d70300909ce2e49dfad4a4619c8390cae66cefdb204").try_into().unwrap();
+        let scalar: [u8; 32] = decode_hex("00000000000000000000000000000000000000000000000011138ce750fa15c2").try_into().unwrap();
+        assert_eq!(bn128::mul(&p, &scalar).unwrap().to_vec(), decode_hex("070a8d6a982153cae4be29d434e8faef8a47b274a053f5a4ee2a6c9c13c31e5c031b8ce914eba3a9ffb989f9cdd5b0f01943074bf4f0f315690ec3cec6981afc"));
+    }
+
+    #[test]
+    fn test_eip197_vectors() {
+        // jeff1-6, two_point_match_2, one_point and empty_data of the bn256Pairing tests of the precompiles
+        let g2 = "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c21800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+        let vectors = [
+            (format!("1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f593034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf704bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a416782bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c2032c61a830e3c17286de9462bf242fca2883585b93870a73853face6a6bf411{g2}"), true),
+            (format!("2eca0c7238bf16e83e7a1e6c5d49540685ff51380f309842a98561558019fc0203d3260361bb8451de5ff5ecd17f010ff22f5c31cdf184e9020b06fa5997db841213d2149b006137fcfb23036606f848d638d576a120ca981b5b1a5f9300b3ee2276cf730cf493cd95d64677bbb75fc42db72513a4c1e387b476d056f80aa75f21ee6226d31426322afcda621464d0611d226783262e21bb3bc86b537e986237096df1f82dff337dd5972e32a8ad43e28a78a96a823ef1cd4debe12b6552ea5f06967a1237ebfeca9aaae0d6d0bab8e28c198c5a339ef8a2407e31cdac516db922160fa257a5fd5b280642ff47b65eca77e626cb685c84fa6d3b6882a283ddd1{g2}"), true),
+            (format!("0f25929bcb43d5a57391564615c9e70a992b10eafa4db109709649cf48c50dd216da2f5cb6be7a0aa72c440c53c9bbdfec6c36c7d515536431b3a865468acbba2e89718ad33c8bed92e210e81d1853435399a271913a6520736a4729cf0d51eb01a9e2ffa2e92599b68e44de5bcf354fa2642bd4f26b259daa6f7ce3ed57aeb314a9a87b789a58af499b314e13c3d65bede56c07ea2d418d6874857b70763713178fb49a2d6cd347dc58973ff49613a20757d0fcc22079f9abd10c3baee245901b9e027bd5cfc2cb5db82d4dc9677ac795ec500ecd47deee3b5da006d6d049b811d7511c78158de484232fc68daf8a45cf217d1c2fae693ff5871e8752d73b21{g2}"), true),
+            ("2f2ea0b3da1e8ef11914acf8b2e1b32d99df51f5f4f206fc6b947eae860eddb6068134ddb33dc888ef446b648d72338684d678d2eb2371c61a50734d78da4b7225f83c8b6ab9de74e7da488ef02645c5a16a6652c3c71a15dc37fe3a5dcb7cb122acdedd6308e3bb230d226d16a105295f523a8a02bfc5e8bd2da135ac4c245d065bbad92e7c4e31bf3757f1fe7362a63fbfee50e7dc68da116e67d600d9bf6806d302580dc0661002994e7cd3a7f224e7ddc27802777486bf80f40e4ca3cfdb186bac5188a98c45e6016873d107f5cd131f3a3e339d0375e58bd6219347b008122ae2b09e539e152ec5364e7e2204b03d11d3caa038bfc7cd499f8176aacbee1f39e4e4afc4bc74790a4a028aff2c3d2538731fb755edefd8cb48d6ea589b5e283f150794b6736f670d6a1033f9b46c6f5204f50813eb85c8dc4b59db1c5d39140d97ee4d2b36d99bc49974d18ecca3e7ad51011956051b464d9e27d46cc25e0764bb98575bd466d32db7b15f582b2d5c452b36aa394b789366e5e3ca5aabd415794ab061441e51d01e94640b7e3084a07e02c78cf3103c542bc5b298669f211b88da1679b0b64a63b7e0e7bfe52aae524f73a55be7fe70c7e9bfc94b4cf0da1213d2149b006137fcfb23036606f848d638d576a120ca981b5b1a5f9300b3ee2276cf730cf493cd95d64677bbb75fc42db72513a4c1e387b476d056f80aa75f21ee6226d31426322afcda621464d0611d226783262e21bb3bc86b537e986237096df1f82dff337dd5972e32a8ad43e28a78a96a823ef1cd4debe12b6552ea5f".to_string(), true),
+            (format!("20a754d2071d4d53903e3b31a7e98ad6882d58aec240ef981fdf0a9d22c5926a29c853fcea789887315916bbeb89ca37edb355b4f980c9a12a94f30deeed30211213d2149b006137fcfb23036606f848d638d576a120ca981b5b1a5f9300b3ee2276cf730cf493cd95d64677bbb75fc42db72513a4c1e387b476d056f80aa75f21ee6226d31426322afcda621464d0611d226783262e21bb3bc86b537e986237096df1f82dff337dd5972e32a8ad43e28a78a96a823ef1cd4debe12b6552ea5f1abb4a25eb9379ae96c84fff9f0540abcfc0a0d11aeda02d4f37e4baf74cb0c11073b3ff2cdbb38755f8691ea59e9606696b3ff278acfc098fa8226470d03869217cee0a9ad79a4493b5253e2e4e3a39fc2df38419f230d341f60cb064a0ac290a3d76f140db8418ba512272381446eb73958670f00cf46f1d9e64cba057b53c26f64a8ec70387a13e41430ed3ee4a7db2059cc5fc13c067194bcc0cb49a98552fd72bd9edb657346127da132e5b82ab908f5816c826acb499e22f2412d1a2d70f25929bcb43d5a57391564615c9e70a992b10eafa4db109709649cf48c50dd2198a1f162a73261f112401aa2db79c7dab1533c9935c77290a6ce3b191f2318d{g2}"), true),
+            (format!("1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f593034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf704bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a416782bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c103188585e2364128fe25c70558f1560f4f9350baf3959e603cc91486e110936{g2}"), false),
+            (format!("00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002{g2}000000000000000000000000000000000000000000000000000000000000000130644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45{g2}"), true),
+            (format!("00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002{g2}"), false),
+            ("".to_string(), true),
+        ];
+        for (input, expected) in &vectors {
+            assert_eq!(bn128::pairing_check(&decode_hex(input)), Some(*expected));
+        }
+
+        let mut bad = decode_hex(&vectors[0].0);
+        bad[383] ^= 1;
+        assert_eq!(bn128::pairing_check(&bad), None);
+        assert_eq!(bn128::pairing_check(&bad[..191]), None);
+    }
+
+    #[test]
+    fn test_eip198_vectors() {
+        let modulus = BigNat::from_be_bytes(&decode_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"));
+        let exp = BigNat::from_be_bytes(&decode_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e"));
+        assert_eq!(BigNat::from_u64(3).pow_mod(&exp, &modulus), BigNat::from_u64(1));
+        assert_eq!(BigNat::zero().pow_mod(&exp, &modulus), BigNat::zero());
+    }
+
+    #[test]
+    fn test_p256_rfc6979_vector() {
+        // RFC 6979 A.2.5, P-256 with SHA-256 and the message "sample"
+        let digest = sha2::sha256(b"sample");
+        let mut sig = [27u8; 65];
+        sig[1..].copy_from_slice(&decode_hex("efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"));
+        assert_eq!(secp256r1::recover_compressed(&sig, &digest).unwrap().to_vec(), decode_hex("0360fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6"));
+        sig[0] = 28;
+        assert_ne!(secp256r1::recover_compressed(&sig, &digest).unwrap().to_vec(), decode_hex("0360fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6"));
+    }
+
+    #[test]
+    fn test_secp256k1_generator() {
+        let mut secret = [0u8; 32];
+        secret[31] = 1;
+        assert_eq!(secp256k1::public_key(&secret).to_vec(), decode_hex("0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"));
+    }
+
+    #[test]
+    fn test_bignat_div_rem() {
+        let mut seed = 0x9e3779b97f4a7c15u64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            return seed;
+        };
+        for i in 1..64 {
+            let a: Vec<u8> = (0..i * 3).map(|_| next() as u8).collect();
+            let b: Vec<u8> = (0..i).map(|_| next() as u8).collect();
+            let a = BigNat::from_be_bytes(&a);
+            let b = BigNat::from_be_bytes(&b);
+            if b.is_zero() {
+                continue;
+            }
+            let (q, r) = a.div_rem(&b);
+            assert!(r < b);
+            let back = q.mul(&b);
+            assert_eq!(back.to_be_bytes(i * 3), a.sub(&r).to_be_bytes(i * 3));
+        }
+
+        let m = BigNat::from_u64(1_000_000_007);
+        assert_eq!(BigNat::from_u64(3).pow_mod(&BigNat::from_u64(1_000_000_006), &m), BigNat::from_u64(1));
+        assert_eq!(BigNat::from_u64(3).pow_mod(&BigNat::from_u64(5), &BigNat::from_u64(7)), BigNat::from_u64(5));
+    }
+}
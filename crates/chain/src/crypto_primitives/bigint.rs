@@ -0,0 +1,241 @@
+use core::cmp::Ordering;
+
+use crate::vec::Vec;
+
+/// Arbitrary precision unsigned integer, stored as little-endian 32 bit limbs without leading zero limbs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BigNat {
+    limbs: Vec<u32>,
+}
+
+impl BigNat {
+    pub fn zero() -> Self {
+        return Self { limbs: Vec::new() };
+    }
+
+    pub fn from_u64(n: u64) -> Self {
+        let mut ret = Self { limbs: vec![n as u32, (n >> 32) as u32] };
+        ret.normalize();
+        return ret;
+    }
+
+    pub fn from_be_bytes(data: &[u8]) -> Self {
+        let mut limbs = Vec::with_capacity(data.len() / 4 + 1);
+        for chunk in data.rchunks(4) {
+            let mut limb = 0u32;
+            for b in chunk {
+                limb = (limb << 8) | *b as u32;
+            }
+            limbs.push(limb);
+        }
+        let mut ret = Self { limbs };
+        ret.normalize();
+        return ret;
+    }
+
+    /// Returns the big-endian representation, left padded with zeros to `len` bytes.
+    /// Returns `None` if the value does not fit.
+    pub fn to_be_bytes(&self, len: usize) -> Option<Vec<u8>> {
+        let mut ret = vec![0u8; len];
+        for (i, limb) in self.limbs.iter().enumerate() {
+            for j in 0..4 {
+                let b = (limb >> (8 * j)) as u8;
+                let pos = i * 4 + j;
+                if pos >= len {
+                    if b != 0 {
+                        return None;
+                    }
+                    continue;
+                }
+                ret[len - 1 - pos] = b;
+            }
+        }
+        return Some(ret);
+    }
+
+    pub fn is_zero(&self) -> bool {
+        return self.limbs.is_empty();
+    }
+
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() * 32 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    pub fn bit(&self, i: usize) -> bool {
+        let limb = i / 32;
+        if limb >= self.limbs.len() {
+            return false;
+        }
+        return (self.limbs[limb] >> (i % 32)) & 1 == 1;
+    }
+
+    fn normalize(&mut self) {
+        while let Some(&0) = self.limbs.last() {
+            self.limbs.pop();
+        }
+    }
+
+    pub fn mul(&self, other: &BigNat) -> BigNat {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, b) in other.limbs.iter().enumerate() {
+                let t = *a as u64 * *b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = t as u32;
+                carry = t >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        let mut ret = Self { limbs };
+        ret.normalize();
+        return ret;
+    }
+
+    /// Returns `self - other`, `self` must not be less than `other`.
+    pub fn sub(&self, other: &BigNat) -> BigNat {
+        assert!(*self >= *other, "BigNat: subtraction underflow");
+        let mut limbs = self.limbs.clone();
+        let mut borrow = 0i64;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let b = other.limbs.get(i).copied().unwrap_or(0) as i64;
+            let t = *limb as i64 - b - borrow;
+            *limb = t as u32;
+            borrow = if t < 0 { 1 } else { 0 };
+        }
+        let mut ret = Self { limbs };
+        ret.normalize();
+        return ret;
+    }
+
+    fn shl_limbs(limbs: &[u32], shift: u32, extra: usize) -> Vec<u32> {
+        let mut ret = vec![0u32; limbs.len() + extra];
+        for (i, limb) in limbs.iter().enumerate() {
+            ret[i] |= limb << shift;
+            if shift > 0 && i + 1 < ret.len() {
+                ret[i + 1] |= limb >> (32 - shift);
+            }
+        }
+        return ret;
+    }
+
+    /// Returns `(self / divisor, self % divisor)`, using Knuth's algorithm D.
+    pub fn div_rem(&self, divisor: &BigNat) -> (BigNat, BigNat) {
+        assert!(!divisor.is_zero(), "BigNat: division by zero");
+        if *self < *divisor {
+            return (Self::zero(), self.clone());
+        }
+
+        let n = divisor.limbs.len();
+        if n == 1 {
+            let d = divisor.limbs[0] as u64;
+            let mut q = vec![0u32; self.limbs.len()];
+            let mut rem = 0u64;
+            for i in (0..self.limbs.len()).rev() {
+                let cur = (rem << 32) | self.limbs[i] as u64;
+                q[i] = (cur / d) as u32;
+                rem = cur % d;
+            }
+            let mut q = Self { limbs: q };
+            q.normalize();
+            return (q, Self::from_u64(rem));
+        }
+
+        let m = self.limbs.len() - n;
+        let shift = divisor.limbs[n - 1].leading_zeros();
+        let vn = Self::shl_limbs(&divisor.limbs, shift, 0);
+        let mut un = Self::shl_limbs(&self.limbs, shift, 1);
+        let mut q = vec![0u32; m + 1];
+        let base = 1u64 << 32;
+
+        for j in (0..=m).rev() {
+            let num = ((un[j + n] as u64) << 32) | un[j + n - 1] as u64;
+            let mut qhat = num / vn[n - 1] as u64;
+            let mut rhat = num % vn[n - 1] as u64;
+            while qhat >= base || qhat * vn[n - 2] as u64 > ((rhat << 32) | un[j + n - 2] as u64) {
+                qhat -= 1;
+                rhat += vn[n - 1] as u64;
+                if rhat >= base {
+                    break;
+                }
+            }
+
+            let mut k = 0i64;
+            for i in 0..n {
+                let p = qhat * vn[i] as u64;
+                let t = un[i + j] as i64 - k - (p & 0xffff_ffff) as i64;
+                un[i + j] = t as u32;
+                k = (p >> 32) as i64 - (t >> 32);
+            }
+            let t = un[j + n] as i64 - k;
+            un[j + n] = t as u32;
+
+            if t < 0 {
+                qhat -= 1;
+                let mut carry = 0u64;
+                for i in 0..n {
+                    let t = un[i + j] as u64 + vn[i] as u64 + carry;
+                    un[i + j] = t as u32;
+                    carry = t >> 32;
+                }
+                un[j + n] = un[j + n].wrapping_add(carry as u32);
+            }
+            q[j] = qhat as u32;
+        }
+
+        let mut rem = vec![0u32; n];
+        for i in 0..n {
+            rem[i] = un[i] >> shift;
+            if shift > 0 {
+                rem[i] |= un[i + 1] << (32 - shift);
+            }
+        }
+        let mut q = Self { limbs: q };
+        q.normalize();
+        let mut rem = Self { limbs: rem };
+        rem.normalize();
+        return (q, rem);
+    }
+
+    pub fn rem(&self, modulus: &BigNat) -> BigNat {
+        return self.div_rem(modulus).1;
+    }
+
+    /// Returns `self ^ exp % modulus`, `modulus` must not be zero.
+    pub fn pow_mod(&self, exp: &BigNat, modulus: &BigNat) -> BigNat {
+        let base = self.rem(modulus);
+        let mut ret = Self::from_u64(1).rem(modulus);
+        for i in (0..exp.bits()).rev() {
+            ret = ret.mul(&ret).rem(modulus);
+            if exp.bit(i) {
+                ret = ret.mul(&base).rem(modulus);
+            }
+        }
+        return ret;
+    }
+}
+
+impl PartialOrd for BigNat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl Ord for BigNat {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.limbs.len() != other.limbs.len() {
+            return self.limbs.len().cmp(&other.limbs.len());
+        }
+        for i in (0..self.limbs.len()).rev() {
+            if self.limbs[i] != other.limbs[i] {
+                return self.limbs[i].cmp(&other.limbs[i]);
+            }
+        }
+        return Ordering::Equal;
+    }
+}
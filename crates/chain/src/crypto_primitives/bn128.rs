@@ -0,0 +1,439 @@
+//! alt_bn128 (BN254) curve operations with the encoding of EIP-196 and EIP-197.
+//! The pairing follows the straightforward construction over the degree 12 extension field.
+
+use core::ops::{
+    Add,
+    Mul,
+    Neg,
+    Sub,
+};
+
+use super::bigint::BigNat;
+use super::field::{
+    Curve,
+    Field,
+    FieldParams,
+    Fp,
+    Point,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BaseParams;
+
+impl FieldParams for BaseParams {
+    const MODULUS: [u64; 4] = [0x3c208c16d87cfd47, 0x97816a916871ca8d, 0xb85045b68181585d, 0x30644e72e131a029];
+}
+
+type Fq = Fp<BaseParams>;
+
+#[derive(Clone, Copy)]
+struct G1;
+
+impl Curve for G1 {
+    type Base = Fq;
+    fn a() -> Fq {
+        return Fq::zero();
+    }
+    fn b() -> Fq {
+        return Fq::from_u64(3);
+    }
+}
+
+/// The twisted curve `y^2 = x^3 + 3 / (9 + i)` which G2 lives on.
+#[derive(Clone, Copy)]
+struct G2;
+
+impl Curve for G2 {
+    type Base = Fq2;
+    fn a() -> Fq2 {
+        return Fq2::zero();
+    }
+    fn b() -> Fq2 {
+        let xi = Fq2 { c0: Fq::from_u64(9), c1: Fq::one() };
+        return Fq2::from_u64(3) * xi.inverse().unwrap();
+    }
+}
+
+/// G2 mapped onto the curve over Fq12, where the Miller loop runs.
+#[derive(Clone, Copy)]
+struct G12;
+
+impl Curve for G12 {
+    type Base = Fq12;
+    fn a() -> Fq12 {
+        return Fq12::zero();
+    }
+    fn b() -> Fq12 {
+        return Fq12::from_u64(3);
+    }
+}
+
+/// Order of G1 and G2, big-endian.
+const CURVE_ORDER: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+const ATE_LOOP_COUNT: u128 = 29793968203157093288;
+const LOG_ATE_LOOP_COUNT: u32 = 63;
+
+/// Element `c0 + c1 * i` of Fq2, with `i^2 = -1`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Fq2 {
+    c0: Fq,
+    c1: Fq,
+}
+
+impl Add for Fq2 {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        return Fq2 { c0: self.c0 + other.c0, c1: self.c1 + other.c1 };
+    }
+}
+
+impl Sub for Fq2 {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        return Fq2 { c0: self.c0 - other.c0, c1: self.c1 - other.c1 };
+    }
+}
+
+impl Mul for Fq2 {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        return Fq2 {
+            c0: self.c0 * other.c0 - self.c1 * other.c1,
+            c1: self.c0 * other.c1 + self.c1 * other.c0,
+        };
+    }
+}
+
+impl Neg for Fq2 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        return Fq2 { c0: -self.c0, c1: -self.c1 };
+    }
+}
+
+impl Field for Fq2 {
+    fn zero() -> Self {
+        return Fq2 { c0: Fq::zero(), c1: Fq::zero() };
+    }
+
+    fn one() -> Self {
+        return Fq2 { c0: Fq::one(), c1: Fq::zero() };
+    }
+
+    fn from_u64(n: u64) -> Self {
+        return Fq2 { c0: Fq::from_u64(n), c1: Fq::zero() };
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        let norm_inv = (self.c0.square() + self.c1.square()).inverse()?;
+        return Some(Fq2 { c0: self.c0 * norm_inv, c1: -(self.c1 * norm_inv) });
+    }
+}
+
+/// Element of Fq12 as a polynomial in `w` of degree less than 12, with `w^12 = 18 * w^6 - 82`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Fq12 {
+    c: [Fq; 12],
+}
+
+/// Coefficients of the reduction polynomial `w^12 - 18 * w^6 + 82`, lowest degree first.
+fn fq12_modulus() -> [Fq; 13] {
+    let mut ret = [Fq::zero(); 13];
+    ret[0] = Fq::from_u64(82);
+    ret[6] = -Fq::from_u64(18);
+    ret[12] = Fq::one();
+    return ret;
+}
+
+fn poly_degree(p: &[Fq]) -> usize {
+    let mut d = p.len() - 1;
+    while d > 0 && p[d].is_zero() {
+        d -= 1;
+    }
+    return d;
+}
+
+/// Returns the quotient of the polynomial division `a / b`.
+fn poly_div(a: &[Fq; 13], b: &[Fq; 13]) -> [Fq; 13] {
+    let dega = poly_degree(a);
+    let degb = poly_degree(b);
+    let mut rem = *a;
+    let mut ret = [Fq::zero(); 13];
+    if dega < degb {
+        return ret;
+    }
+    let lead_inv = b[degb].inverse().unwrap();
+    for i in (0..=dega - degb).rev() {
+        ret[i] = rem[degb + i] * lead_inv;
+        for c in 0..=degb {
+            rem[c + i] = rem[c + i] - ret[i] * b[c];
+        }
+    }
+    return ret;
+}
+
+impl Fq12 {
+    fn from_coeffs(coeffs: &[(usize, Fq)]) -> Self {
+        let mut ret = Self::zero();
+        for (i, v) in coeffs {
+            ret.c[*i] = *v;
+        }
+        return ret;
+    }
+}
+
+impl Add for Fq12 {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        let mut ret = self;
+        for i in 0..12 {
+            ret.c[i] = self.c[i] + other.c[i];
+        }
+        return ret;
+    }
+}
+
+impl Sub for Fq12 {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        let mut ret = self;
+        for i in 0..12 {
+            ret.c[i] = self.c[i] - other.c[i];
+        }
+        return ret;
+    }
+}
+
+impl Mul for Fq12 {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let mut t = [Fq::zero(); 23];
+        for i in 0..12 {
+            if self.c[i].is_zero() {
+                continue;
+            }
+            for j in 0..12 {
+                t[i + j] = t[i + j] + self.c[i] * other.c[j];
+            }
+        }
+        let c18 = Fq::from_u64(18);
+        let c82 = Fq::from_u64(82);
+        for i in (12..23).rev() {
+            let top = t[i];
+            if top.is_zero() {
+                continue;
+            }
+            t[i - 6] = t[i - 6] + top * c18;
+            t[i - 12] = t[i - 12] - top * c82;
+        }
+        let mut ret = Self::zero();
+        ret.c.copy_from_slice(&t[..12]);
+        return ret;
+    }
+}
+
+impl Neg for Fq12 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        return Self::zero() - self;
+    }
+}
+
+impl Field for Fq12 {
+    fn zero() -> Self {
+        return Fq12 { c: [Fq::zero(); 12] };
+    }
+
+    fn one() -> Self {
+        return Self::from_u64(1);
+    }
+
+    fn from_u64(n: u64) -> Self {
+        return Self::from_coeffs(&[(0, Fq::from_u64(n))]);
+    }
+
+    /// Extended Euclidean algorithm on polynomials.
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let mut lm = [Fq::zero(); 13];
+        lm[0] = Fq::one();
+        let mut hm = [Fq::zero(); 13];
+        let mut low = [Fq::zero(); 13];
+        low[..12].copy_from_slice(&self.c);
+        let mut high = fq12_modulus();
+
+        while poly_degree(&low) > 0 {
+            let r = poly_div(&high, &low);
+            let mut nm = hm;
+            let mut new = high;
+            for i in 0..13 {
+                for j in 0..13 - i {
+                    nm[i + j] = nm[i + j] - lm[i] * r[j];
+                    new[i + j] = new[i + j] - low[i] * r[j];
+                }
+            }
+            hm = lm;
+            high = low;
+            lm = nm;
+            low = new;
+        }
+
+        let low_inv = low[0].inverse()?;
+        let mut ret = Self::zero();
+        for (c, m) in ret.c.iter_mut().zip(lm.iter()) {
+            *c = *m * low_inv;
+        }
+        return Some(ret);
+    }
+}
+
+fn read_fq(data: &[u8]) -> Option<Fq> {
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(&data[..32]);
+    return Fq::from_be_bytes(&buf);
+}
+
+/// Decodes `x || y`, all zeros is the point at infinity.
+fn decode_g1(data: &[u8]) -> Option<Point<G1>> {
+    let x = read_fq(&data[..32])?;
+    let y = read_fq(&data[32..64])?;
+    if x.is_zero() && y.is_zero() {
+        return Some(Point::infinity());
+    }
+    if !Point::<G1>::is_on_curve(&x, &y) {
+        return None;
+    }
+    return Some(Point::from_affine(x, y));
+}
+
+fn encode_g1(p: &Point<G1>) -> [u8; 64] {
+    let mut ret = [0u8; 64];
+    if let Some((x, y)) = p.to_affine() {
+        ret[..32].copy_from_slice(&x.to_be_bytes());
+        ret[32..].copy_from_slice(&y.to_be_bytes());
+    }
+    return ret;
+}
+
+/// Decodes `x_c1 || x_c0 || y_c1 || y_c0`, checking that the point is in the subgroup of order r.
+fn decode_g2(data: &[u8]) -> Option<Point<G2>> {
+    let x = Fq2 { c1: read_fq(&data[..32])?, c0: read_fq(&data[32..64])? };
+    let y = Fq2 { c1: read_fq(&data[64..96])?, c0: read_fq(&data[96..128])? };
+    if x.is_zero() && y.is_zero() {
+        return Some(Point::infinity());
+    }
+    if !Point::<G2>::is_on_curve(&x, &y) {
+        return None;
+    }
+    let p = Point::from_affine(x, y);
+    if !p.mul_be(&CURVE_ORDER).is_infinity() {
+        return None;
+    }
+    return Some(p);
+}
+
+pub fn add(op1: &[u8; 64], op2: &[u8; 64]) -> Option<[u8; 64]> {
+    let a = decode_g1(op1)?;
+    let b = decode_g1(op2)?;
+    return Some(encode_g1(&a.add(&b)));
+}
+
+pub fn mul(g1: &[u8; 64], scalar: &[u8; 32]) -> Option<[u8; 64]> {
+    let p = decode_g1(g1)?;
+    return Some(encode_g1(&p.mul_be(scalar)));
+}
+
+/// Maps a G2 point onto the curve over Fq12.
+fn twist(x: &Fq2, y: &Fq2) -> Point<G12> {
+    let nine = Fq::from_u64(9);
+    let nx = Fq12::from_coeffs(&[(2, x.c0 - x.c1 * nine), (8, x.c1)]);
+    let ny = Fq12::from_coeffs(&[(3, y.c0 - y.c1 * nine), (9, y.c1)]);
+    return Point::from_affine(nx, ny);
+}
+
+fn cast_g1(x: &Fq, y: &Fq) -> Point<G12> {
+    return Point::from_affine(Fq12::from_coeffs(&[(0, *x)]), Fq12::from_coeffs(&[(0, *y)]));
+}
+
+/// Line through `p1` and `p2` evaluated at `t`, as a numerator and a denominator.
+fn linefunc(p1: &Point<G12>, p2: &Point<G12>, t: &Point<G12>) -> (Fq12, Fq12) {
+    let (x1, y1, z1) = (p1.x, p1.y, p1.z);
+    let (x2, y2, z2) = (p2.x, p2.y, p2.z);
+    let (xt, yt, zt) = (t.x, t.y, t.z);
+    let mut m_num = y2 * z1 - y1 * z2;
+    let mut m_den = x2 * z1 - x1 * z2;
+    if m_den.is_zero() {
+        if !m_num.is_zero() {
+            return (xt * z1 - x1 * zt, z1 * zt);
+        }
+        m_num = Fq12::from_u64(3) * x1.square();
+        m_den = Fq12::from_u64(2) * y1 * z1;
+    }
+    return (m_num * (xt * z1 - x1 * zt) - m_den * (yt * z1 - y1 * zt), m_den * zt * z1);
+}
+
+fn miller_loop(q: &Point<G12>, p: &Point<G12>) -> Fq12 {
+    let mut r = *q;
+    let mut f_num = Fq12::one();
+    let mut f_den = Fq12::one();
+    for i in (0..=LOG_ATE_LOOP_COUNT).rev() {
+        let (n, d) = linefunc(&r, &r, p);
+        f_num = f_num.square() * n;
+        f_den = f_den.square() * d;
+        r = r.double();
+        if ATE_LOOP_COUNT & (1u128 << i) != 0 {
+            let (n, d) = linefunc(&r, q, p);
+            f_num = f_num * n;
+            f_den = f_den * d;
+            r = r.add(q);
+        }
+    }
+
+    let modulus = Fq::modulus_be_bytes();
+    let q1: Point<G12> = Point { x: q.x.pow_be(&modulus), y: q.y.pow_be(&modulus), z: q.z.pow_be(&modulus) };
+    let nq2: Point<G12> = Point { x: q1.x.pow_be(&modulus), y: -q1.y.pow_be(&modulus), z: q1.z.pow_be(&modulus) };
+    let (n1, d1) = linefunc(&r, &q1, p);
+    r = r.add(&q1);
+    let (n2, d2) = linefunc(&r, &nq2, p);
+    return f_num * n1 * n2 * (f_den * d1 * d2).inverse().unwrap();
+}
+
+/// Returns `(p^12 - 1) / r`, big-endian.
+fn final_exponent() -> Vec<u8> {
+    let p = BigNat::from_be_bytes(&Fq::modulus_be_bytes());
+    let mut p12 = BigNat::from_u64(1);
+    for _ in 0..12 {
+        p12 = p12.mul(&p);
+    }
+    let exp = p12.sub(&BigNat::from_u64(1)).div_rem(&BigNat::from_be_bytes(&CURVE_ORDER)).0;
+    return exp.to_be_bytes(exp.bits().div_ceil(8)).unwrap();
+}
+
+/// Checks that the product of the pairings of all `G1 || G2` pairs in `pairs` is one.
+/// Returns `None` if the input length is not a multiple of 192 or a point is invalid.
+pub fn pairing_check(pairs: &[u8]) -> Option<bool> {
+    if !pairs.len().is_multiple_of(192) {
+        return None;
+    }
+    let mut points = Vec::new();
+    for pair in pairs.chunks(192) {
+        let p = decode_g1(&pair[..64])?;
+        let q = decode_g2(&pair[64..])?;
+        points.push((p, q));
+    }
+
+    let mut f = Fq12::one();
+    for (p, q) in points {
+        let (Some((px, py)), Some((qx, qy))) = (p.to_affine(), q.to_affine()) else {
+            continue;
+        };
+        f = f * miller_loop(&twist(&qx, &qy), &cast_g1(&px, &py));
+    }
+    return Some(f.pow_be(&final_exponent()) == Fq12::one());
+}
@@ -0,0 +1,87 @@
+use super::field::{
+    Curve,
+    Field,
+    FieldParams,
+    Fp,
+    Point,
+};
+
+/// Curve over a prime field with a prime order generator, as used by ECDSA.
+pub trait EcdsaCurve: Curve<Base = Fp<Self::BaseParams>> {
+    type BaseParams: FieldParams;
+    type ScalarParams: FieldParams;
+    fn generator() -> Point<Self>;
+}
+
+/// Serializes an affine point as a 33 bytes compressed public key.
+pub fn serialize_compressed<C: EcdsaCurve>(x: &C::Base, y: &C::Base) -> [u8; 33] {
+    let mut ret = [0u8; 33];
+    ret[0] = 0x02 | y.is_odd() as u8;
+    ret[1..].copy_from_slice(&x.to_be_bytes());
+    return ret;
+}
+
+/// Serializes an affine point as a 65 bytes uncompressed public key.
+pub fn serialize_uncompressed<C: EcdsaCurve>(x: &C::Base, y: &C::Base) -> [u8; 65] {
+    let mut ret = [0u8; 65];
+    ret[0] = 0x04;
+    ret[1..33].copy_from_slice(&x.to_be_bytes());
+    ret[33..].copy_from_slice(&y.to_be_bytes());
+    return ret;
+}
+
+/// Recovers the public key from `r || s` and the recovery id,
+/// whose first bit is the parity of `R.y` and whose second bit tells that `R.x` is `r + n`.
+pub fn recover<C: EcdsaCurve>(recid: u8, rs: &[u8], digest: &[u8; 32]) -> Option<(C::Base, C::Base)> {
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(&rs[..32]);
+    let r = Fp::<C::ScalarParams>::from_be_bytes(&buf)?;
+    buf.copy_from_slice(&rs[32..64]);
+    let s = Fp::<C::ScalarParams>::from_be_bytes(&buf)?;
+    if r.is_zero() || s.is_zero() {
+        return None;
+    }
+
+    let mut x_bytes = r.to_be_bytes();
+    if recid & 2 != 0 {
+        let n = Fp::<C::ScalarParams>::modulus_be_bytes();
+        let mut carry = 0u16;
+        for i in (0..32).rev() {
+            let t = x_bytes[i] as u16 + n[i] as u16 + carry;
+            x_bytes[i] = t as u8;
+            carry = t >> 8;
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    let x = C::Base::from_be_bytes(&x_bytes)?;
+    let mut y = Point::<C>::rhs(&x).sqrt()?;
+    if y.is_odd() != (recid & 1 == 1) {
+        y = -y;
+    }
+    let big_r = Point::<C>::from_affine(x, y);
+
+    // Q = r^-1 * (s * R - e * G)
+    let e = Fp::<C::ScalarParams>::from_be_bytes_reduced(digest);
+    let r_inv = r.inverse()?;
+    let u1 = -(e * r_inv);
+    let u2 = s * r_inv;
+    let q = C::generator().mul_be(&u1.to_be_bytes()).add(&big_r.mul_be(&u2.to_be_bytes()));
+    return q.to_affine();
+}
+
+/// Signs `digest` with the given nonce, returning the recovery id and `r || s`.
+#[cfg(test)]
+pub fn sign<C: EcdsaCurve>(secret: &[u8; 32], nonce: &[u8; 32], digest: &[u8; 32]) -> (u8, [u8; 64]) {
+    let d = Fp::<C::ScalarParams>::from_be_bytes(secret).unwrap();
+    let k = Fp::<C::ScalarParams>::from_be_bytes(nonce).unwrap();
+    let (rx, ry) = C::generator().mul_be(nonce).to_affine().unwrap();
+    let r = Fp::<C::ScalarParams>::from_be_bytes_reduced(&rx.to_be_bytes());
+    let e = Fp::<C::ScalarParams>::from_be_bytes_reduced(digest);
+    let s = k.inverse().unwrap() * (e + r * d);
+    let mut rs = [0u8; 64];
+    rs[..32].copy_from_slice(&r.to_be_bytes());
+    rs[32..].copy_from_slice(&s.to_be_bytes());
+    return (ry.is_odd() as u8, rs);
+}
@@ -0,0 +1,390 @@
+use core::marker::PhantomData;
+use core::ops::{
+    Add,
+    Mul,
+    Neg,
+    Sub,
+};
+
+/// Element of a finite field, used by the generic curve arithmetic below.
+pub trait Field: Copy + PartialEq + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn from_u64(n: u64) -> Self;
+    /// Returns `None` for zero.
+    fn inverse(&self) -> Option<Self>;
+
+    fn is_zero(&self) -> bool {
+        return *self == Self::zero();
+    }
+
+    fn square(&self) -> Self {
+        return *self * *self;
+    }
+
+    /// Raises `self` to the power of the big-endian integer `exp`.
+    fn pow_be(&self, exp: &[u8]) -> Self {
+        let mut ret = Self::one();
+        for b in exp {
+            for i in (0..8).rev() {
+                ret = ret.square();
+                if (b >> i) & 1 == 1 {
+                    ret = ret * *self;
+                }
+            }
+        }
+        return ret;
+    }
+}
+
+/// Modulus of a 256 bit prime field, as little-endian 64 bit limbs.
+pub trait FieldParams: Copy + PartialEq + Eq + core::fmt::Debug + 'static {
+    const MODULUS: [u64; 4];
+}
+
+const fn geq(a: &[u64; 4], b: &[u64; 4]) -> bool {
+    let mut i = 4;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    return true;
+}
+
+const fn add_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], bool) {
+    let mut ret = [0u64; 4];
+    let mut carry = 0u128;
+    let mut i = 0;
+    while i < 4 {
+        let t = a[i] as u128 + b[i] as u128 + carry;
+        ret[i] = t as u64;
+        carry = t >> 64;
+        i += 1;
+    }
+    return (ret, carry != 0);
+}
+
+const fn sub_limbs(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut ret = [0u64; 4];
+    let mut borrow = 0u64;
+    let mut i = 0;
+    while i < 4 {
+        let (t, b1) = a[i].overflowing_sub(b[i]);
+        let (t, b2) = t.overflowing_sub(borrow);
+        ret[i] = t;
+        borrow = (b1 || b2) as u64;
+        i += 1;
+    }
+    return ret;
+}
+
+const fn add_mod(a: &[u64; 4], b: &[u64; 4], m: &[u64; 4]) -> [u64; 4] {
+    let (ret, carry) = add_limbs(a, b);
+    if carry || geq(&ret, m) {
+        return sub_limbs(&ret, m);
+    }
+    return ret;
+}
+
+fn limbs_to_be_bytes(limbs: &[u64; 4]) -> [u8; 32] {
+    let mut ret = [0u8; 32];
+    for (i, limb) in limbs.iter().enumerate() {
+        ret[32 - 8 * (i + 1)..32 - 8 * i].copy_from_slice(&limb.to_be_bytes());
+    }
+    return ret;
+}
+
+/// Returns `-m^-1 mod 2^64`.
+const fn mont_inv(m0: u64) -> u64 {
+    let mut inv = 1u64;
+    let mut i = 0;
+    while i < 63 {
+        inv = inv.wrapping_mul(inv);
+        inv = inv.wrapping_mul(m0);
+        i += 1;
+    }
+    return inv.wrapping_neg();
+}
+
+/// Returns `2^512 mod m`.
+const fn mont_r2(m: &[u64; 4]) -> [u64; 4] {
+    let mut ret = [1u64, 0, 0, 0];
+    let mut i = 0;
+    while i < 512 {
+        ret = add_mod(&ret, &ret, m);
+        i += 1;
+    }
+    return ret;
+}
+
+/// Element of the prime field defined by `P`, kept in Montgomery form.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Fp<P: FieldParams> {
+    mont: [u64; 4],
+    _params: PhantomData<P>,
+}
+
+impl<P: FieldParams> Fp<P> {
+    const R2: [u64; 4] = mont_r2(&P::MODULUS);
+    const INV: u64 = mont_inv(P::MODULUS[0]);
+
+    fn new(mont: [u64; 4]) -> Self {
+        return Self { mont, _params: PhantomData };
+    }
+
+    fn mont_mul(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        let m = &P::MODULUS;
+        let mut t = [0u64; 6];
+        for bi in b {
+            let mut c = 0u128;
+            for j in 0..4 {
+                let x = t[j] as u128 + a[j] as u128 * *bi as u128 + c;
+                t[j] = x as u64;
+                c = x >> 64;
+            }
+            let x = t[4] as u128 + c;
+            t[4] = x as u64;
+            t[5] = (x >> 64) as u64;
+
+            let k = t[0].wrapping_mul(Self::INV);
+            let x = t[0] as u128 + k as u128 * m[0] as u128;
+            let mut c = x >> 64;
+            for j in 1..4 {
+                let x = t[j] as u128 + k as u128 * m[j] as u128 + c;
+                t[j - 1] = x as u64;
+                c = x >> 64;
+            }
+            let x = t[4] as u128 + c;
+            t[3] = x as u64;
+            t[4] = t[5] + (x >> 64) as u64;
+        }
+        let ret = [t[0], t[1], t[2], t[3]];
+        if t[4] != 0 || geq(&ret, m) {
+            return sub_limbs(&ret, m);
+        }
+        return ret;
+    }
+
+    /// Converts a canonical value, which must be less than the modulus.
+    pub fn from_limbs(limbs: [u64; 4]) -> Self {
+        return Self::new(Self::mont_mul(&limbs, &Self::R2));
+    }
+
+    pub fn to_limbs(self) -> [u64; 4] {
+        return Self::mont_mul(&self.mont, &[1, 0, 0, 0]);
+    }
+
+    fn limbs_from_be_bytes(data: &[u8; 32]) -> [u64; 4] {
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&data[32 - 8 * (i + 1)..32 - 8 * i]);
+            *limb = u64::from_be_bytes(buf);
+        }
+        return limbs;
+    }
+
+    /// Returns `None` if `data` is not less than the modulus.
+    pub fn from_be_bytes(data: &[u8; 32]) -> Option<Self> {
+        let limbs = Self::limbs_from_be_bytes(data);
+        if geq(&limbs, &P::MODULUS) {
+            return None;
+        }
+        return Some(Self::from_limbs(limbs));
+    }
+
+    /// Like `from_be_bytes`, but reduces `data` modulo the modulus.
+    pub fn from_be_bytes_reduced(data: &[u8; 32]) -> Self {
+        let mut limbs = Self::limbs_from_be_bytes(data);
+        while geq(&limbs, &P::MODULUS) {
+            limbs = sub_limbs(&limbs, &P::MODULUS);
+        }
+        return Self::from_limbs(limbs);
+    }
+
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        return limbs_to_be_bytes(&self.to_limbs());
+    }
+
+    pub fn modulus_be_bytes() -> [u8; 32] {
+        return limbs_to_be_bytes(&P::MODULUS);
+    }
+
+    pub fn is_odd(&self) -> bool {
+        return self.to_limbs()[0] & 1 == 1;
+    }
+
+    /// Square root for moduli congruent to 3 mod 4.
+    pub fn sqrt(&self) -> Option<Self> {
+        let (exp, _) = add_limbs(&P::MODULUS, &[1, 0, 0, 0]);
+        let exp = [
+            (exp[0] >> 2) | (exp[1] << 62),
+            (exp[1] >> 2) | (exp[2] << 62),
+            (exp[2] >> 2) | (exp[3] << 62),
+            exp[3] >> 2,
+        ];
+        let ret = self.pow_be(&limbs_to_be_bytes(&exp));
+        if ret.square() != *self {
+            return None;
+        }
+        return Some(ret);
+    }
+}
+
+impl<P: FieldParams> Add for Fp<P> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        return Self::new(add_mod(&self.mont, &other.mont, &P::MODULUS));
+    }
+}
+
+impl<P: FieldParams> Sub for Fp<P> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        if geq(&self.mont, &other.mont) {
+            return Self::new(sub_limbs(&self.mont, &other.mont));
+        }
+        let (t, _) = add_limbs(&self.mont, &P::MODULUS);
+        return Self::new(sub_limbs(&t, &other.mont));
+    }
+}
+
+impl<P: FieldParams> Mul for Fp<P> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        return Self::new(Self::mont_mul(&self.mont, &other.mont));
+    }
+}
+
+impl<P: FieldParams> Neg for Fp<P> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        return Self::zero() - self;
+    }
+}
+
+impl<P: FieldParams> Field for Fp<P> {
+    fn zero() -> Self {
+        return Self::new([0; 4]);
+    }
+
+    fn one() -> Self {
+        return Self::from_limbs([1, 0, 0, 0]);
+    }
+
+    fn from_u64(n: u64) -> Self {
+        return Self::from_limbs([n, 0, 0, 0]);
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let exp = sub_limbs(&P::MODULUS, &[2, 0, 0, 0]);
+        return Some(self.pow_be(&limbs_to_be_bytes(&exp)));
+    }
+}
+
+/// Short Weierstrass curve `y^2 = x^3 + a * x + b`.
+pub trait Curve: Copy {
+    type Base: Field;
+    fn a() -> Self::Base;
+    fn b() -> Self::Base;
+}
+
+/// Point of a curve in homogeneous projective coordinates.
+#[derive(Clone, Copy)]
+pub struct Point<C: Curve> {
+    pub x: C::Base,
+    pub y: C::Base,
+    pub z: C::Base,
+}
+
+impl<C: Curve> Point<C> {
+    pub fn infinity() -> Self {
+        return Self { x: C::Base::one(), y: C::Base::one(), z: C::Base::zero() };
+    }
+
+    pub fn from_affine(x: C::Base, y: C::Base) -> Self {
+        return Self { x, y, z: C::Base::one() };
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        return self.z.is_zero();
+    }
+
+    /// Returns `None` for the point at infinity.
+    pub fn to_affine(self) -> Option<(C::Base, C::Base)> {
+        let zinv = self.z.inverse()?;
+        return Some((self.x * zinv, self.y * zinv));
+    }
+
+    /// Returns `x^3 + a * x + b`, the square of `y` for points on the curve.
+    pub fn rhs(x: &C::Base) -> C::Base {
+        return x.square() * *x + C::a() * *x + C::b();
+    }
+
+    pub fn is_on_curve(x: &C::Base, y: &C::Base) -> bool {
+        return y.square() == Self::rhs(x);
+    }
+
+    pub fn double(&self) -> Self {
+        let w = C::a() * self.z.square() + C::Base::from_u64(3) * self.x.square();
+        let s = self.y * self.z;
+        let b = self.x * self.y * s;
+        let h = w.square() - C::Base::from_u64(8) * b;
+        let s2 = s.square();
+        return Self {
+            x: C::Base::from_u64(2) * h * s,
+            y: w * (C::Base::from_u64(4) * b - h) - C::Base::from_u64(8) * self.y.square() * s2,
+            z: C::Base::from_u64(8) * s * s2,
+        };
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        if self.is_infinity() {
+            return *other;
+        }
+        if other.is_infinity() {
+            return *self;
+        }
+        let u1 = other.y * self.z;
+        let u2 = self.y * other.z;
+        let v1 = other.x * self.z;
+        let v2 = self.x * other.z;
+        if v1 == v2 {
+            if u1 == u2 {
+                return self.double();
+            }
+            return Self::infinity();
+        }
+        let u = u1 - u2;
+        let v = v1 - v2;
+        let vv = v.square();
+        let vv_v2 = vv * v2;
+        let v3 = v * vv;
+        let w = self.z * other.z;
+        let a = u.square() * w - v3 - C::Base::from_u64(2) * vv_v2;
+        return Self {
+            x: v * a,
+            y: u * (vv_v2 - a) - v3 * u2,
+            z: v3 * w,
+        };
+    }
+
+    /// Multiplies the point by the big-endian integer `scalar`.
+    pub fn mul_be(&self, scalar: &[u8]) -> Self {
+        let mut ret = Self::infinity();
+        for b in scalar {
+            for i in (0..8).rev() {
+                ret = ret.double();
+                if (b >> i) & 1 == 1 {
+                    ret = ret.add(self);
+                }
+            }
+        }
+        return ret;
+    }
+}
@@ -0,0 +1,85 @@
+use super::ecdsa::{
+    self,
+    EcdsaCurve,
+};
+use super::field::{
+    Curve,
+    Field,
+    FieldParams,
+    Fp,
+    Point,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BaseParams;
+
+impl FieldParams for BaseParams {
+    const MODULUS: [u64; 4] = [0xfffffffefffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff];
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ScalarParams;
+
+impl FieldParams for ScalarParams {
+    const MODULUS: [u64; 4] = [0xbfd25e8cd0364141, 0xbaaedce6af48a03b, 0xfffffffffffffffe, 0xffffffffffffffff];
+}
+
+type Fq = Fp<BaseParams>;
+
+const GENERATOR_X: [u64; 4] = [0x59f2815b16f81798, 0x029bfcdb2dce28d9, 0x55a06295ce870b07, 0x79be667ef9dcbbac];
+const GENERATOR_Y: [u64; 4] = [0x9c47d08ffb10d4b8, 0xfd17b448a6855419, 0x5da4fbfc0e1108a8, 0x483ada7726a3c465];
+
+#[derive(Clone, Copy)]
+pub struct Secp256k1;
+
+impl Curve for Secp256k1 {
+    type Base = Fq;
+    fn a() -> Fq {
+        return Fq::zero();
+    }
+    fn b() -> Fq {
+        return Fq::from_u64(7);
+    }
+}
+
+impl EcdsaCurve for Secp256k1 {
+    type BaseParams = BaseParams;
+    type ScalarParams = ScalarParams;
+    fn generator() -> Point<Self> {
+        return Point::from_affine(Fq::from_limbs(GENERATOR_X), Fq::from_limbs(GENERATOR_Y));
+    }
+}
+
+/// Recovers the uncompressed public key from a compact signature, which is a recovery byte
+/// in the range 27..=34 followed by `r` and `s`, the same format as `k1_recover` takes.
+pub fn recover(sig: &[u8; 65], digest: &[u8; 32]) -> Option<[u8; 65]> {
+    if sig[0] < 27 || sig[0] >= 35 {
+        return None;
+    }
+    let (x, y) = ecdsa::recover::<Secp256k1>((sig[0] - 27) & 3, &sig[1..], digest)?;
+    return Some(ecdsa::serialize_uncompressed::<Secp256k1>(&x, &y));
+}
+
+/// Like `recover`, but returns the compressed public key.
+pub fn recover_compressed(sig: &[u8; 65], digest: &[u8; 32]) -> Option<[u8; 33]> {
+    if sig[0] < 27 || sig[0] >= 35 {
+        return None;
+    }
+    let (x, y) = ecdsa::recover::<Secp256k1>((sig[0] - 27) & 3, &sig[1..], digest)?;
+    return Some(ecdsa::serialize_compressed::<Secp256k1>(&x, &y));
+}
+
+#[cfg(test)]
+pub fn sign(secret: &[u8; 32], nonce: &[u8; 32], digest: &[u8; 32]) -> [u8; 65] {
+    let (recid, rs) = ecdsa::sign::<Secp256k1>(secret, nonce, digest);
+    let mut ret = [0u8; 65];
+    ret[0] = 27 + recid;
+    ret[1..].copy_from_slice(&rs);
+    return ret;
+}
+
+#[cfg(test)]
+pub fn public_key(secret: &[u8; 32]) -> [u8; 65] {
+    let (x, y) = Secp256k1::generator().mul_be(secret).to_affine().unwrap();
+    return ecdsa::serialize_uncompressed::<Secp256k1>(&x, &y);
+}
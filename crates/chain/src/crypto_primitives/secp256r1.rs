@@ -0,0 +1,63 @@
+use super::ecdsa::{
+    self,
+    EcdsaCurve,
+};
+use super::field::{
+    Curve,
+    Field,
+    FieldParams,
+    Fp,
+    Point,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BaseParams;
+
+impl FieldParams for BaseParams {
+    const MODULUS: [u64; 4] = [0xffffffffffffffff, 0x00000000ffffffff, 0x0000000000000000, 0xffffffff00000001];
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ScalarParams;
+
+impl FieldParams for ScalarParams {
+    const MODULUS: [u64; 4] = [0xf3b9cac2fc632551, 0xbce6faada7179e84, 0xffffffffffffffff, 0xffffffff00000000];
+}
+
+type Fq = Fp<BaseParams>;
+
+const CURVE_B: [u64; 4] = [0x3bce3c3e27d2604b, 0x651d06b0cc53b0f6, 0xb3ebbd55769886bc, 0x5ac635d8aa3a93e7];
+const GENERATOR_X: [u64; 4] = [0xf4a13945d898c296, 0x77037d812deb33a0, 0xf8bce6e563a440f2, 0x6b17d1f2e12c4247];
+const GENERATOR_Y: [u64; 4] = [0xcbb6406837bf51f5, 0x2bce33576b315ece, 0x8ee7eb4a7c0f9e16, 0x4fe342e2fe1a7f9b];
+
+/// NIST P-256.
+#[derive(Clone, Copy)]
+pub struct Secp256r1;
+
+impl Curve for Secp256r1 {
+    type Base = Fq;
+    fn a() -> Fq {
+        return -Fq::from_u64(3);
+    }
+    fn b() -> Fq {
+        return Fq::from_limbs(CURVE_B);
+    }
+}
+
+impl EcdsaCurve for Secp256r1 {
+    type BaseParams = BaseParams;
+    type ScalarParams = ScalarParams;
+    fn generator() -> Point<Self> {
+        return Point::from_affine(Fq::from_limbs(GENERATOR_X), Fq::from_limbs(GENERATOR_Y));
+    }
+}
+
+/// Recovers the compressed public key from a compact signature,
+/// which is a recovery byte in the range 27..=34 followed by `r` and `s`.
+pub fn recover_compressed(sig: &[u8; 65], digest: &[u8; 32]) -> Option<[u8; 33]> {
+    if sig[0] < 27 || sig[0] >= 35 {
+        return None;
+    }
+    let (x, y) = ecdsa::recover::<Secp256r1>((sig[0] - 27) & 3, &sig[1..], digest)?;
+    return Some(ecdsa::serialize_compressed::<Secp256r1>(&x, &y));
+}
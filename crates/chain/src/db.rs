@@ -3,6 +3,13 @@ use core::convert::{
     Into,
 };
 
+use core::ops::{
+    Bound,
+    RangeBounds,
+};
+
+use core::cell::Cell;
+
 use crate::structs::{ Uint128, Uint256, Float128 };
 use crate::serializer::Packer;
 
@@ -20,16 +27,23 @@ use crate::serializer::{
     Encoder,
 };
 
-use crate::asset::Asset;
+use crate::asset::{
+    Asset,
+    SymbolCode,
+};
 
 use crate::{
     check,
+    vec::Vec,
 };
 
 use crate::print::{
     Printable
 };
 
+/// RAM billing of table rows.
+pub mod ram;
+
 ///
 #[derive(Clone, Debug, Default)]
 pub struct TableError {
@@ -59,6 +73,10 @@ impl PrimaryValueInterface for Asset {
 pub trait PrimaryValueInterface {
     ///
     fn get_primary(&self) -> u64;
+    /// Version of a `#[chain(table = "...", version = N)]` struct, `0` if the table is not versioned.
+    fn table_version() -> u8 where Self: Sized {
+        return 0;
+    }
 }
 
 pub trait SecondaryValueInterface: core::any::Any {
@@ -252,6 +270,165 @@ pub enum SecondaryValue {
     IdxF128(Float128),
 }
 
+impl PartialOrd for SecondaryValue {
+    /// Compares two values of the same index type in the order the chain sorts them, `None` if the types differ.
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        // maps the bits of an IEEE float to an unsigned integer with the same order
+        fn f128_key(value: &Float128) -> u128 {
+            let bits = u128::from_le_bytes(value.data);
+            if bits >> 127 == 1 { !bits } else { bits | 1 << 127 }
+        }
+
+        match (self, other) {
+            (SecondaryValue::Idx64(a), SecondaryValue::Idx64(b)) => a.partial_cmp(b),
+            (SecondaryValue::Idx128(a), SecondaryValue::Idx128(b)) => a.partial_cmp(b),
+            (SecondaryValue::Idx256(a), SecondaryValue::Idx256(b)) => a.data.partial_cmp(&b.data),
+            (SecondaryValue::IdxF64(a), SecondaryValue::IdxF64(b)) => a.partial_cmp(b),
+            (SecondaryValue::IdxF128(a), SecondaryValue::IdxF128(b)) => f128_key(a).partial_cmp(&f128_key(b)),
+            _ => None,
+        }
+    }
+}
+
+/// Converts a value to a `u64` whose unsigned order matches the order of the value,
+/// so that it can be a part of a composite secondary key declared with
+/// `#[chain(secondary = "name", fields = "a,b")]`.
+pub trait SecondaryKeyPart: Sized {
+    ///
+    fn to_key_part(&self) -> u64;
+    ///
+    fn from_key_part(part: u64) -> Self;
+}
+
+macro_rules! impl_unsigned_key_part {
+    ( $( $ty:ty ),* ) => {
+        $(
+            impl SecondaryKeyPart for $ty {
+                fn to_key_part(&self) -> u64 {
+                    *self as u64
+                }
+
+                fn from_key_part(part: u64) -> Self {
+                    part as $ty
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_signed_key_part {
+    ( $( $ty:ty ),* ) => {
+        $(
+            impl SecondaryKeyPart for $ty {
+                // flipping the sign bit moves negative values below positive ones
+                fn to_key_part(&self) -> u64 {
+                    (*self as i64 as u64) ^ (1 << 63)
+                }
+
+                fn from_key_part(part: u64) -> Self {
+                    (part ^ (1 << 63)) as i64 as $ty
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned_key_part!(u8, u16, u32, u64);
+impl_signed_key_part!(i8, i16, i32, i64);
+
+impl SecondaryKeyPart for bool {
+    fn to_key_part(&self) -> u64 {
+        *self as u64
+    }
+
+    fn from_key_part(part: u64) -> Self {
+        part != 0
+    }
+}
+
+impl SecondaryKeyPart for f64 {
+    // negative values have all bits flipped, positive values only the sign bit
+    fn to_key_part(&self) -> u64 {
+        let bits = self.to_bits();
+        if bits >> 63 == 1 { !bits } else { bits | 1 << 63 }
+    }
+
+    fn from_key_part(part: u64) -> Self {
+        let bits = if part >> 63 == 1 { part & !(1 << 63) } else { !part };
+        f64::from_bits(bits)
+    }
+}
+
+impl SecondaryKeyPart for f32 {
+    fn to_key_part(&self) -> u64 {
+        (*self as f64).to_key_part()
+    }
+
+    fn from_key_part(part: u64) -> Self {
+        f64::from_key_part(part) as f32
+    }
+}
+
+impl SecondaryKeyPart for Name {
+    fn to_key_part(&self) -> u64 {
+        self.value()
+    }
+
+    fn from_key_part(part: u64) -> Self {
+        Name{n: part}
+    }
+}
+
+impl SecondaryKeyPart for SymbolCode {
+    fn to_key_part(&self) -> u64 {
+        self.value
+    }
+
+    fn from_key_part(part: u64) -> Self {
+        SymbolCode{value: part}
+    }
+}
+
+/// Packs 1 to 4 key parts into the smallest secondary value that holds them, the first part is the most significant.
+/// One part is an `Idx64` key, two parts an `Idx128` key and three or four parts an `Idx256` key.
+pub fn pack_composite_key(parts: &[u64]) -> SecondaryValue {
+    match parts.len() {
+        1 => SecondaryValue::Idx64(parts[0]),
+        2 => SecondaryValue::Idx128((parts[0] as u128) << 64 | parts[1] as u128),
+        3 | 4 => {
+            let last = parts.get(3).copied().unwrap_or(0);
+            SecondaryValue::Idx256(Uint256{data: [(parts[0] as u128) << 64 | parts[1] as u128, (parts[2] as u128) << 64 | last as u128]})
+        }
+        _ => {
+            check(false, "pack_composite_key: only 1 to 4 parts are supported");
+            SecondaryValue::None
+        }
+    }
+}
+
+/// Reverse of [`pack_composite_key`], missing parts are zero.
+pub fn unpack_composite_key(value: SecondaryValue) -> [u64; 4] {
+    match value {
+        SecondaryValue::Idx64(x) => [x, 0, 0, 0],
+        SecondaryValue::Idx128(x) => [(x >> 64) as u64, x as u64, 0, 0],
+        SecondaryValue::Idx256(x) => [(x.data[0] >> 64) as u64, x.data[0] as u64, (x.data[1] >> 64) as u64, x.data[1] as u64],
+        _ => {
+            check(false, "unpack_composite_key: bad secondary type");
+            [0; 4]
+        }
+    }
+}
+
+fn min_secondary_value(ty: SecondaryType) -> SecondaryValue {
+    match ty {
+        SecondaryType::Idx64 => SecondaryValue::Idx64(0),
+        SecondaryType::Idx128 => SecondaryValue::Idx128(0),
+        SecondaryType::Idx256 => SecondaryValue::Idx256(Uint256{data: [0; 2]}),
+        SecondaryType::IdxF64 => SecondaryValue::IdxF64(f64::NEG_INFINITY),
+        SecondaryType::IdxF128 => SecondaryValue::IdxF128(Float128{data: (0xffffu128 << 112).to_le_bytes()}),
+    }
+}
+
 impl From<u64> for SecondaryValue {
     fn from(value: u64) -> Self {
         SecondaryValue::Idx64(value)
@@ -348,6 +525,8 @@ where
     pub scope: u64,
     ///
     pub table: u64,
+    /// cached `(recorded, legacy_from)` of a versioned table, see `TABLE_VERSIONS`
+    legacy_rows: Cell<Option<(bool, Option<u64>)>>,
     _marker: core::marker::PhantomData<T>,
 }
 
@@ -361,13 +540,102 @@ where
             code: code.value(),
             scope: scope.value(),
             table: table.value(),
+            legacy_rows: Cell::new(None),
             _marker: core::marker::PhantomData::<T>{}
         }
     }
 
+    /// Returns whether the rows without version prefix are recorded in `TABLE_VERSIONS`
+    /// and the primary key from which the rows have no version prefix.
+    fn legacy_rows(&self) -> (bool, Option<u64>) {
+        if T::table_version() == 0 {
+            return (true, None);
+        }
+        if let Some(state) = self.legacy_rows.get() {
+            return state;
+        }
+        let it = db_find_i64(self.code, self.scope, TABLE_VERSIONS.value(), self.table);
+        let state = if it >= 0 {
+            let mut legacy_from: Option<u64> = None;
+            legacy_from.unpack(&db_get_i64(it));
+            (true, legacy_from)
+        } else if db_lowerbound_i64(self.code, self.scope, self.table, 0) >= 0 {
+            // all rows were written by a plain table struct
+            (false, Some(0))
+        } else {
+            (false, None)
+        };
+        self.legacy_rows.set(Some(state));
+        return state;
+    }
+
+    /// Records in `TABLE_VERSIONS` that the rows from the primary key `legacy_from` have no version prefix,
+    /// the contract pays for the record.
+    pub(crate) fn set_legacy_rows(&self, legacy_from: Option<u64>) {
+        let data = Encoder::pack(&legacy_from);
+        let it = db_find_i64(self.code, self.scope, TABLE_VERSIONS.value(), self.table);
+        if it >= 0 {
+            db_update_i64(it, 0, data.as_ptr(), data.len() as u32);
+        } else {
+            db_store_i64(self.scope, TABLE_VERSIONS.value(), self.code, self.table, data.as_ptr(), data.len() as u32);
+        }
+        self.legacy_rows.set(Some((true, legacy_from)));
+    }
+
+    /// Returns the primary key from which the rows have no version prefix, see `TABLE_VERSIONS`.
+    pub fn legacy_from(&self) -> Option<u64> {
+        return self.legacy_rows().1;
+    }
+
+    /// Sets the primary key from which the rows have no version prefix without recording it,
+    /// used while the rows are migrated.
+    pub(crate) fn cache_legacy_from(&self, legacy_from: Option<u64>) {
+        let (recorded, _) = self.legacy_rows();
+        self.legacy_rows.set(Some((recorded, legacy_from)));
+    }
+
+    /// Returns true if the row pointed by `iterator` was written before the table was versioned.
+    fn is_legacy_row(&self, iterator: &Iterator<T>) -> bool {
+        return match self.legacy_from() {
+            None => false,
+            Some(0) => true,
+            Some(legacy_from) => self.primary_of(iterator) >= legacy_from,
+        };
+    }
+
+    /// Returns the primary key of the row pointed by `iterator` without unpacking the row.
+    pub(crate) fn primary_of(&self, iterator: &Iterator<T>) -> u64 {
+        if let Some(primary) = iterator.primary {
+            return primary;
+        }
+        // the previous row of the next row is the row itself
+        let mut primary = 0;
+        let next = db_next_i64(iterator.i, &mut primary);
+        db_previous_i64(next, &mut primary);
+        return primary;
+    }
+
+    /// Checks that a row of a versioned table with primary key `primary` can be written.
+    fn check_write(&self, primary: u64) {
+        if T::table_version() == 0 {
+            return;
+        }
+        match self.legacy_rows() {
+            (false, None) => {
+                // the first row of a versioned table in the scope
+                self.set_legacy_rows(None);
+            }
+            (_, Some(legacy_from)) => {
+                check(primary < legacy_from, "rows written before the table was versioned must be migrated first");
+            }
+            _ => {}
+        }
+    }
+
     ///
     pub fn store(&self, value: &T, payer: Name) -> Iterator<T> {
         let key = value.get_primary();
+        self.check_write(key);
         let data = Encoder::pack(value);
         let it = db_store_i64(self.scope, self.table, payer.value(), key, data.as_ptr(), data.len() as u32);
         Iterator::<T> { i: it, primary: Some(key), db: self }
@@ -387,6 +655,7 @@ where
     pub fn update(&self, iterator: &Iterator<T>, value: &T, payer: Name) {
         check(iterator.is_ok(), "TableI64::update:invalid iterator");
         check(iterator.get_primary().unwrap() == value.get_primary(), "TableI64::update: can not change primary value during update!");
+        self.check_write(value.get_primary());
         let data = Encoder::pack(value);
         db_update_i64(iterator.i, payer.value(), data.as_ptr(), data.len() as u32);
     }
@@ -404,7 +673,15 @@ where
 
         let data = db_get_i64(iterator.i);
         let mut ret = T::default();
-        ret.unpack(&data); 
+        if T::table_version() != 0 && self.is_legacy_row(iterator) {
+            // rows written before the table was versioned are version 0
+            let mut row = Vec::with_capacity(data.len() + 1);
+            row.push(0u8);
+            row.extend_from_slice(&data);
+            ret.unpack(&row);
+        } else {
+            ret.unpack(&data);
+        }
         Some(ret)
     }
 
@@ -456,6 +733,181 @@ where
         let it = db_end_i64(self.code, self.scope, self.table);
         Iterator::<T> { i: it, primary: None, db: self }
     }
+
+    /// Iterates over all rows in ascending primary key order, yields `(primary, value)`.
+    pub fn iter(&self) -> TableIter<'_, T> {
+        self.range(..)
+    }
+
+    /// Iterates over the rows with a primary key in `range`, e.g. `table.range(10..20)`.
+    pub fn range<R: RangeBounds<u64>>(&self, range: R) -> TableIter<'_, T> {
+        let lower: u128 = match range.start_bound() {
+            Bound::Included(&key) => key as u128,
+            Bound::Excluded(&key) => key as u128 + 1,
+            Bound::Unbounded => 0,
+        };
+        let upper: u128 = match range.end_bound() {
+            Bound::Included(&key) => key as u128 + 1,
+            Bound::Excluded(&key) => key as u128,
+            Bound::Unbounded => u64::MAX as u128 + 1,
+        };
+
+        let back = if upper > u64::MAX as u128 {
+            self.end()
+        } else {
+            self.lower_bound(upper as u64)
+        };
+        let front = if lower >= upper {
+            Iterator::<T> { i: back.i, primary: None, db: self }
+        } else {
+            self.lower_bound(lower as u64)
+        };
+        TableIter { front, back }
+    }
+
+    /// Iterates over all rows in descending primary key order.
+    pub fn rev(&self) -> core::iter::Rev<TableIter<'_, T>> {
+        self.iter().rev()
+    }
+}
+
+/// A `core::iter::Iterator` over the rows of a `TableI64`, created by [`TableI64::iter`] and [`TableI64::range`].
+pub struct TableIter<'a, T>
+where T: Packer + PrimaryValueInterface + Default
+{
+    // next row to yield from the front
+    front: Iterator<'a, T>,
+    // one past the next row to yield from the back
+    back: Iterator<'a, T>,
+}
+
+impl<'a, T> core::iter::Iterator for TableIter<'a, T>
+where T: Packer + PrimaryValueInterface + Default
+{
+    type Item = (u64, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front.i == self.back.i || !self.front.is_ok() {
+            return None;
+        }
+        let value = self.front.get_value()?;
+        let next = self.front.db.next(&self.front);
+        self.front = next;
+        Some((value.get_primary(), value))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for TableIter<'a, T>
+where T: Packer + PrimaryValueInterface + Default
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front.i == self.back.i {
+            return None;
+        }
+        let previous = self.back.db.previous(&self.back);
+        self.back = previous;
+        let value = self.back.get_value()?;
+        Some((value.get_primary(), value))
+    }
+}
+
+/// Table of a scope recording the rows of the versioned tables in the scope which have no version prefix,
+/// with the name of the versioned table as primary key and `Option<u64>` as value:
+/// the rows with a primary key not less than the value were written before the table was versioned.
+/// A scope without the record of a versioned table which has rows was written by a plain `#[chain(table = "...")]` struct.
+pub const TABLE_VERSIONS: Name = Name{n: crate::name::static_str_to_name("tableversion")};
+
+/// Upgrades rows packed by an older version of a `#[chain(table = "...", version = N)]` struct.
+/// The generated `Packer::unpack` calls it when the version of a row is less than `N`,
+/// so every versioned table struct implements it.
+pub trait MigrateRow: Sized {
+    /// `data` is the row packed by version `version` of the struct without the version prefix, followed by
+    /// any trailing data. Version `0` is a row written before the table was versioned.
+    /// Returns the row and the number of bytes of `data` it was unpacked from.
+    fn migrate(version: u8, data: &[u8]) -> (Self, usize);
+}
+
+/// A table holding a single row whose primary key is the table name, like `eosio::singleton`.
+pub struct Singleton<T>
+where
+    T: Packer + Default,
+{
+    ///
+    pub code: u64,
+    ///
+    pub scope: u64,
+    ///
+    pub table: u64,
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<T> Singleton<T>
+where
+    T: Packer + Default,
+{
+    /// Creates the singleton `table` of contract `code` in `scope`.
+    pub fn new(code: Name, scope: Name, table: Name) -> Self {
+        Singleton {
+            code: code.value(),
+            scope: scope.value(),
+            table: table.value(),
+            _marker: core::marker::PhantomData::<T>{}
+        }
+    }
+
+    fn find(&self) -> i32 {
+        return db_find_i64(self.code, self.scope, self.table, self.table);
+    }
+
+    /// Returns true if the row has been set.
+    pub fn exists(&self) -> bool {
+        return self.find() >= 0;
+    }
+
+    ///
+    pub fn get(&self) -> Option<T> {
+        let it = self.find();
+        if it < 0 {
+            return None;
+        }
+        let data = db_get_i64(it);
+        let mut ret = T::default();
+        ret.unpack(&data);
+        return Some(ret);
+    }
+
+    /// Returns the row, or `T::default()` if it has not been set.
+    pub fn get_or_default(&self) -> T {
+        return self.get().unwrap_or_default();
+    }
+
+    /// Returns the row, stores `default` with `payer` first if it has not been set.
+    pub fn get_or_create(&self, payer: Name, default: T) -> T {
+        if let Some(value) = self.get() {
+            return value;
+        }
+        self.set(&default, payer);
+        return default;
+    }
+
+    /// Stores `value` with `payer`, `SAME_PAYER` keeps the payer of an existing row.
+    pub fn set(&self, value: &T, payer: Name) {
+        let data = Encoder::pack(value);
+        let it = self.find();
+        if it >= 0 {
+            db_update_i64(it, payer.value(), data.as_ptr(), data.len() as u32);
+        } else {
+            db_store_i64(self.scope, self.table, payer.value(), self.table, data.as_ptr(), data.len() as u32);
+        }
+    }
+
+    /// Removes the row if it exists.
+    pub fn remove(&self) {
+        let it = self.find();
+        if it >= 0 {
+            db_remove_i64(it);
+        }
+    }
 }
 
 ///
@@ -635,6 +1087,96 @@ impl<'a, T: From<SecondaryValue> + Into<SecondaryValue> + Printable + Default, c
         return self.db.end();
     }
 
+    /// Iterates over the index in ascending secondary key order, yields `(primary, secondary)`.
+    pub fn iter(&self) -> IdxTableIter<'a, T>
+    where T: Clone
+    {
+        self.range(..)
+    }
+
+    /// Iterates over the entries with a secondary key in `range`, e.g. `idx.range(10..20)`.
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> IdxTableIter<'a, T>
+    where T: Clone
+    {
+        let lower = match range.start_bound() {
+            Bound::Included(key) => Bound::Included(key.clone().into()),
+            Bound::Excluded(key) => Bound::Excluded(key.clone().into()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let upper = match range.end_bound() {
+            Bound::Included(key) => Bound::Included(key.clone().into()),
+            Bound::Excluded(key) => Bound::Excluded(key.clone().into()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        let back = match upper {
+            Bound::Included(key) => self.db.upper_bound(key).0,
+            Bound::Excluded(key) => self.db.lower_bound(key).0,
+            Bound::Unbounded => self.db.end(),
+        };
+
+        let empty = match (&lower, &upper) {
+            (Bound::Included(a), Bound::Included(b)) => a > b,
+            (Bound::Included(a), Bound::Excluded(b)) | (Bound::Excluded(a), Bound::Included(b)) | (Bound::Excluded(a), Bound::Excluded(b)) => a >= b,
+            _ => false,
+        };
+        let front = if empty {
+            back
+        } else {
+            match lower {
+                Bound::Included(key) => self.db.lower_bound(key).0,
+                Bound::Excluded(key) => self.db.upper_bound(key).0,
+                Bound::Unbounded => self.db.lower_bound(min_secondary_value(self._secondary_type)).0,
+            }
+        };
+        IdxTableIter { db: self.db, front, back, _marker: core::marker::PhantomData::<T>{} }
+    }
+
+    /// Iterates over the index in descending secondary key order.
+    pub fn rev(&self) -> core::iter::Rev<IdxTableIter<'a, T>>
+    where T: Clone
+    {
+        self.iter().rev()
+    }
+}
+
+/// A `core::iter::Iterator` over a secondary index, created by [`IdxTableProxy::iter`] and [`IdxTableProxy::range`].
+pub struct IdxTableIter<'a, T: From<SecondaryValue>> {
+    db: &'a dyn IdxTable,
+    // next entry to yield from the front
+    front: SecondaryIterator,
+    // one past the next entry to yield from the back
+    back: SecondaryIterator,
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<'a, T: From<SecondaryValue>> core::iter::Iterator for IdxTableIter<'a, T> {
+    type Item = (u64, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front.i == self.back.i || !self.front.is_ok() {
+            return None;
+        }
+        let primary = self.front.primary;
+        let (_, secondary) = self.db.find_primary(primary);
+        self.front = self.db.next(&self.front);
+        Some((primary, secondary.into()))
+    }
+}
+
+impl<'a, T: From<SecondaryValue>> DoubleEndedIterator for IdxTableIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front.i == self.back.i {
+            return None;
+        }
+        self.back = self.db.previous(&self.back);
+        if !self.back.is_ok() {
+            return None;
+        }
+        let primary = self.back.primary;
+        let (_, secondary) = self.db.find_primary(primary);
+        Some((primary, secondary.into()))
+    }
 }
 
 impl Idx64Table {
@@ -1110,3 +1652,31 @@ impl IdxTable for IdxF128Table {
         return SecondaryIterator{ i: ret, primary: 0, db_index: self.db_index };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_composite_key_order() {
+        let ints = [i64::MIN, -100, -1, 0, 1, 100, i64::MAX];
+        for w in ints.windows(2) {
+            assert!(w[0].to_key_part() < w[1].to_key_part());
+        }
+        let floats = [f64::NEG_INFINITY, -1.5, -0.25, 0.0, 0.25, 1.5, f64::INFINITY];
+        for w in floats.windows(2) {
+            assert!(w[0].to_key_part() < w[1].to_key_part());
+        }
+        for x in floats {
+            assert_eq!(f64::from_key_part(x.to_key_part()), x);
+        }
+        assert_eq!(i8::from_key_part((-5i8).to_key_part()), -5);
+
+        let a = pack_composite_key(&[(-1i64).to_key_part(), 7]);
+        let b = pack_composite_key(&[1i64.to_key_part(), 0]);
+        assert!(a < b);
+        let key = pack_composite_key(&[1, 2, 3]);
+        assert_eq!(unpack_composite_key(key), [1, 2, 3, 0]);
+        assert_eq!(unpack_composite_key(pack_composite_key(&[9, 8])), [9, 8, 0, 0]);
+    }
+}
@@ -0,0 +1,72 @@
+//! RAM billed by the chain for table data, computed with the `billable_size` constants of nodeos.
+//!
+//! A new row is billed its packed size plus [`ROW_OVERHEAD`], and [`secondary_row_bytes`] for each
+//! secondary index. The first row stored in a scope also creates the tables of the primary and the
+//! secondary indexes, each billed [`TABLE_OVERHEAD`].
+
+use crate::db::SecondaryType;
+use crate::serializer::Packer;
+
+/// Overhead of a row in each chainbase index, `overhead_per_row_per_index_ram_bytes`.
+pub const OVERHEAD_PER_ROW_PER_INDEX: u64 = 32;
+
+/// Bytes billed for a table of a scope, `billable_size_v<table_id_object>`.
+pub const TABLE_OVERHEAD: u64 = 44 + OVERHEAD_PER_ROW_PER_INDEX * 2;
+
+/// Bytes billed for a primary row in addition to its data, `billable_size_v<key_value_object>`.
+pub const ROW_OVERHEAD: u64 = 32 + 8 + 4 + OVERHEAD_PER_ROW_PER_INDEX * 2;
+
+/// Bytes billed for a row of a secondary index of type `ty`.
+pub fn secondary_row_bytes(ty: SecondaryType) -> u64 {
+    let key_size = match ty {
+        SecondaryType::Idx64 => 8,
+        SecondaryType::Idx128 => 16,
+        SecondaryType::Idx256 => 32,
+        SecondaryType::IdxF64 => 8,
+        SecondaryType::IdxF128 => 16,
+    };
+    return 24 + key_size + OVERHEAD_PER_ROW_PER_INDEX * 3;
+}
+
+/// Bytes billed for a primary row with `size` bytes of data.
+pub fn primary_row_bytes(size: usize) -> u64 {
+    return size as u64 + ROW_OVERHEAD;
+}
+
+/// Bytes billed for storing `row` in a table with the secondary indexes `indices`,
+/// excluding the tables created by the first row of a scope.
+pub fn row_bytes<T: Packer>(row: &T, indices: &[SecondaryType]) -> u64 {
+    let secondary: u64 = indices.iter().map(|ty| secondary_row_bytes(*ty)).sum();
+    return primary_row_bytes(row.size()) + secondary;
+}
+
+/// Bytes billed for creating the tables of a scope with the secondary indexes `indices`.
+pub fn tables_bytes(indices: &[SecondaryType]) -> u64 {
+    return TABLE_OVERHEAD * (1 + indices.len() as u64);
+}
+
+/// Change of the bytes billed to the same payer when the data of a row changes from `old_size` to `new_size` bytes.
+pub fn update_delta(old_size: usize, new_size: usize) -> i64 {
+    return new_size as i64 - old_size as i64;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_billable_sizes() {
+        assert_eq!(TABLE_OVERHEAD, 108);
+        assert_eq!(ROW_OVERHEAD, 108);
+        assert_eq!(secondary_row_bytes(SecondaryType::Idx64), 128);
+        assert_eq!(secondary_row_bytes(SecondaryType::Idx128), 136);
+        assert_eq!(secondary_row_bytes(SecondaryType::Idx256), 152);
+        assert_eq!(secondary_row_bytes(SecondaryType::IdxF64), 128);
+        assert_eq!(secondary_row_bytes(SecondaryType::IdxF128), 136);
+
+        let indices = [SecondaryType::Idx64, SecondaryType::Idx256];
+        assert_eq!(row_bytes(&1u64, &indices), 8 + 108 + 128 + 152);
+        assert_eq!(tables_bytes(&indices), 324);
+        assert_eq!(update_delta(16, 10), -6);
+    }
+}
@@ -22,5 +22,10 @@ pub use crate::structs::{
     Uint256,
     TimePoint,
     TimePointSec,
+    BlockTimeStampType,
     ProducerKey,
 };
+
+pub use crate::time::{
+    Microseconds,
+};
@@ -29,6 +29,7 @@ pub use chaintester;
 #[cfg(feature = "std")]
 pub use chaintester::{
     ChainTester,
+    MockTester,
 };
 
 cfg_if! {
@@ -106,11 +107,16 @@ pub use self::structs:: {
     ProducerAuthority,
 };
 
+///
+pub mod time;
+pub use self::time::Microseconds;
+
 ///
 pub mod transaction;
 pub use self::transaction::{
     TransactionExtension,
     Transaction,
+    TransactionBuilder,
 };
 
 ///
@@ -119,6 +125,7 @@ pub use serializer::{
     Encoder,
     Decoder,
     Packer,
+    Unpack,
 };
 
 ///
@@ -131,6 +138,8 @@ pub use db::{
     SecondaryIterator,
     SecondaryValue,
     TableI64,
+    Singleton,
+    MigrateRow,
     Idx64Table,
     Idx128Table,
     Idx256Table,
@@ -138,6 +147,8 @@ pub use db::{
     IdxF128Table,
     IdxTable,
     IdxTableProxy,
+    TableIter,
+    IdxTableIter,
 };
 
 ///
@@ -156,7 +167,9 @@ pub use asset::{
     Asset,
     Symbol,
     SymbolCode,
-    ExtendedAsset
+    ExtendedAsset,
+    Decimal,
+    Rounding,
 };
 
 mod privileged;
@@ -176,8 +189,23 @@ pub use crypto::{
 
     recover_key,
     assert_recover_key,
+
+    sha3,
+    keccak256,
+    k1_recover,
+    blake2_f,
+    G1Point,
+    G2Point,
+    alt_bn128_add,
+    alt_bn128_mul,
+    alt_bn128_pair,
+    BigUint,
+    mod_exp,
 };
 
+#[cfg(feature = "std")]
+mod crypto_primitives;
+
 pub use self::vmapi::eosio::{
     get_active_producers,
     check_transaction_authorization,
@@ -249,8 +277,18 @@ pub use action::{
     Action,
 };
 
+#[cfg(feature = "std")]
+pub use action::decode_action_return_value;
+
 ///
 pub mod utils;
+pub use utils::{
+    ParseError,
+    ParseErrorKind,
+};
+///
+pub mod base58;
+mod ripemd160;
 ///
 pub mod varint;
 pub use varint::{
@@ -267,6 +305,8 @@ pub use intrinsic_abi_types::*;
 
 pub use eosio_macro::{
     contract,
+    Packer,
+    ChainVariant,
     // chain,
 };
 
@@ -275,3 +315,6 @@ pub use eosio_scale_info;
 
 #[cfg(feature = "std")]
 pub mod abi;
+
+#[cfg(feature = "std")]
+pub mod tester;
@@ -14,9 +14,11 @@ use crate::{
 use crate::{
     check,
     serializer::Packer,
+    print::Printable,
 };
 
 use crate::boxed::Box;
+use crate::vmapi::db::db_get_i64;
 ///
 pub struct MultiIndex<T>
 where T: PrimaryValueInterface + SecondaryValueInterface + Packer + Default
@@ -31,6 +33,7 @@ where T: PrimaryValueInterface + SecondaryValueInterface + Packer + Default
     pub db: TableI64<T>,
     ///
     pub idxdbs: Vec<Box<dyn IdxTable>>,
+    secondary_types: Vec<SecondaryType>,
     _marker: core::marker::PhantomData<T>,
 }
 
@@ -78,6 +81,7 @@ where T: PrimaryValueInterface + SecondaryValueInterface + Packer + Default
             table,
             db: TableI64::new(code, scope, table),
             idxdbs,
+            secondary_types: indices.to_vec(),
             _marker: core::marker::PhantomData::<T>{},
         }
     }
@@ -93,6 +97,16 @@ where T: PrimaryValueInterface + SecondaryValueInterface + Packer + Default
         return it;
     }
 
+    /// Returns the bytes of RAM billed to the payer for storing `value` as a new row,
+    /// including the tables created when the scope is still empty.
+    pub fn estimate_store_cost(&self, value: &T) -> u64 {
+        let mut cost = ram::row_bytes(value, &self.secondary_types);
+        if self.db.end().get_i() == -1 {
+            cost += ram::tables_bytes(&self.secondary_types);
+        }
+        return cost;
+    }
+
     /// Searches for a record with a given primary key and returns an iterator.
     pub fn find(&self, id: u64) -> Iterator<T> {
         return self.db.find(id);
@@ -113,6 +127,66 @@ where T: PrimaryValueInterface + SecondaryValueInterface + Packer + Default
         }
     }
 
+    /// Applies `f` to the record pointed by the given iterator and stores it with `payer`,
+    /// `SAME_PAYER` keeps the current payer. Only the secondary indexes whose values changed are updated.
+    pub fn modify<F: FnOnce(&mut T)>(&self, iterator: &Iterator<T>, payer: Name, f: F) {
+        check(iterator.is_ok(), "MultiIndex::modify: invalid iterator");
+        let primary = iterator.get_primary().unwrap();
+        let mut value = self.db.get(iterator).unwrap();
+        let old_values: Vec<SecondaryValue> = (0..self.idxdbs.len()).map(|i| value.get_secondary_value(i)).collect();
+        f(&mut value);
+        check(value.get_primary() == primary, "MultiIndex::modify: can not change primary value");
+        self.db.update(iterator, &value, payer);
+        for i in 0..self.idxdbs.len() {
+            let v2 = value.get_secondary_value(i);
+            if old_values[i] == v2 {
+                continue;
+            }
+            let (it_secondary, _) = self.idxdbs[i].find_primary(primary);
+            self.idxdbs[i].update(&it_secondary, v2, payer);
+        }
+    }
+
+    /// Rewrites with `payer` the rows whose version is not `version`, which upgrades the rows of a versioned table,
+    /// `SAME_PAYER` keeps the payer of each row. Scans at most `limit` rows starting from the primary key `start`,
+    /// returns the number of rewritten rows and the primary key to continue from, or `None` if all rows were scanned.
+    /// The rows written before the table was versioned are migrated in the order of their primary keys.
+    pub fn migrate(&self, version: u8, payer: Name, start: u64, limit: u32) -> (u32, Option<u64>) {
+        let legacy_from = self.db.legacy_from();
+        if let Some(legacy_from) = legacy_from {
+            check(start <= legacy_from, "migrate: rows written before the table was versioned must be migrated in order");
+        }
+        let mut count: u32 = 0;
+        let mut scanned: u32 = 0;
+        let mut it = self.db.lower_bound(start);
+        let mut primary = if it.is_ok() { Some(self.db.primary_of(&it)) } else { None };
+        while let Some(current) = primary {
+            if scanned >= limit {
+                break;
+            }
+            scanned += 1;
+            it.set_primary(current);
+            let next = self.db.next(&it);
+            let next_primary = if next.is_ok() { next.primary } else { None };
+            let is_legacy = legacy_from.is_some_and(|legacy_from| current >= legacy_from);
+            if is_legacy || db_get_i64(it.get_i()).first() != Some(&version) {
+                let value = self.db.get(&it).unwrap();
+                if is_legacy {
+                    // the rows before the next row have the version prefix once this row is rewritten
+                    self.db.cache_legacy_from(next_primary);
+                }
+                self.update(&it, &value, payer);
+                count += 1;
+            }
+            primary = next_primary;
+            it = next;
+        }
+        if legacy_from.is_some() {
+            self.db.set_legacy_rows(self.db.legacy_from());
+        }
+        return (count, primary);
+    }
+
     /// Removes the record pointed by the given iterator.
     pub fn remove(&self, iterator: &Iterator<T>) {
         check(iterator.is_ok(), "remove: invalid iterator");
@@ -163,6 +237,21 @@ where T: PrimaryValueInterface + SecondaryValueInterface + Packer + Default
         return self.db.end();
     }
 
+    /// Iterates over all rows in ascending primary key order, yields `(primary, value)`.
+    pub fn iter(&self) -> TableIter<'_, T> {
+        return self.db.iter();
+    }
+
+    /// Iterates over the rows with a primary key in `range`.
+    pub fn range<R: core::ops::RangeBounds<u64>>(&self, range: R) -> TableIter<'_, T> {
+        return self.db.range(range);
+    }
+
+    /// Iterates over all rows in descending primary key order.
+    pub fn rev(&self) -> core::iter::Rev<TableIter<'_, T>> {
+        return self.db.rev();
+    }
+
     /// Retrieves a reference to the secondary index database at the given index.
     pub fn get_idx_db(&self, i: usize) -> &dyn IdxTable {
         return self.idxdbs[i].as_ref();
@@ -178,3 +267,83 @@ where T: PrimaryValueInterface + SecondaryValueInterface + Packer + Default
         idx_db.update(it, value, payer);    
     }
 }
+
+/// A secondary index of a `MultiIndex` whose queries return the rows together with their secondary keys.
+/// `K` is the type of the secondary key and `IDX_TYPE` the index of its `SecondaryType`.
+pub struct SecondaryIndex<'a, K, T, const IDX_TYPE: usize>
+where
+    K: From<SecondaryValue> + Into<SecondaryValue> + Printable + Default + Clone,
+    T: PrimaryValueInterface + SecondaryValueInterface + Packer + Default,
+{
+    mi: &'a MultiIndex<T>,
+    idx: IdxTableProxy<'a, K, IDX_TYPE>,
+}
+
+impl<'a, K, T, const IDX_TYPE: usize> SecondaryIndex<'a, K, T, IDX_TYPE>
+where
+    K: From<SecondaryValue> + Into<SecondaryValue> + Printable + Default + Clone,
+    T: PrimaryValueInterface + SecondaryValueInterface + Packer + Default,
+{
+    /// Creates the handle of the `db_index`th secondary index of `mi`.
+    pub fn new(mi: &'a MultiIndex<T>, db_index: usize) -> Self {
+        Self {
+            mi,
+            idx: IdxTableProxy::new(mi.get_idx_db(db_index)),
+        }
+    }
+
+    fn row(&self, it: SecondaryIterator, key: K) -> Option<(SecondaryIterator, K, T)> {
+        if !it.is_ok() {
+            return None;
+        }
+        let value = self.mi.get_by_primary(it.primary);
+        check(value.is_some(), "SecondaryIndex: row of secondary key not found");
+        return Some((it, key, value.unwrap()));
+    }
+
+    /// Returns the first row whose secondary key equals `key`.
+    pub fn find(&self, key: K) -> Option<(SecondaryIterator, K, T)> {
+        let it = self.idx.find(key.clone());
+        return self.row(it, key);
+    }
+
+    /// Returns the first row whose secondary key is not less than `key`.
+    pub fn lower_bound(&self, key: K) -> Option<(SecondaryIterator, K, T)> {
+        let (it, key) = self.idx.lower_bound(key);
+        return self.row(it, key);
+    }
+
+    /// Returns the first row whose secondary key is greater than `key`.
+    pub fn upper_bound(&self, key: K) -> Option<(SecondaryIterator, K, T)> {
+        let (it, key) = self.idx.upper_bound(key);
+        return self.row(it, key);
+    }
+
+    /// Iterates over the rows with a secondary key in `range`, yields `(secondary, row)`.
+    pub fn range<R: core::ops::RangeBounds<K>>(&self, range: R) -> impl DoubleEndedIterator<Item = (K, T)> + 'a
+    where K: 'a, T: 'a
+    {
+        let mi = self.mi;
+        return self.idx.range(range).map(move |(primary, key)| {
+            let value = mi.get_by_primary(primary);
+            check(value.is_some(), "SecondaryIndex: row of secondary key not found");
+            (key, value.unwrap())
+        });
+    }
+
+    /// Applies `f` to the row pointed by `it` and stores it with `payer`, see [`MultiIndex::modify`].
+    pub fn modify<F: FnOnce(&mut T)>(&self, it: &SecondaryIterator, payer: Name, f: F) {
+        check(it.is_ok(), "SecondaryIndex::modify: invalid iterator");
+        let it_primary = self.mi.find(it.primary).expect("SecondaryIndex::modify: invalid primary");
+        self.mi.modify(&it_primary, payer, f);
+    }
+
+    /// Removes the row pointed by `it` from the table and all of its indexes, returns the iterator after `it`.
+    pub fn erase(&self, it: &SecondaryIterator) -> SecondaryIterator {
+        check(it.is_ok(), "SecondaryIndex::erase: invalid iterator");
+        let next = self.idx.next(it);
+        let it_primary = self.mi.find(it.primary).expect("SecondaryIndex::erase: invalid primary");
+        self.mi.remove(&it_primary);
+        return next;
+    }
+}
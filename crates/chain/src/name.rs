@@ -21,6 +21,11 @@ use crate::vmapi::eosio::{
     eosio_memcpy,
 };
 
+use crate::utils::{
+    ParseError,
+    ParseErrorKind,
+};
+
 const INVALID_NAME_CHAR: u8 = 0xffu8;
 
 /// a helper function that converts a single ASCII character to
@@ -201,7 +206,7 @@ fn str_to_name_checked(s: &str) -> u64 {
 
 /// a wrapper around a 64-bit unsigned integer that represents a name in the EOSIO blockchain
 #[repr(C, align(8))]
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
 #[cfg_attr(feature = "std", derive(TypeInfo))]
 pub struct Name {
     ///
@@ -229,6 +234,17 @@ impl Name {
 		return Name{ n: str_to_name_checked(s) };
     }
 
+    /// Like `from_str`, but returns an error instead of aborting the action.
+    pub fn try_from_str(s: &str) -> Result<Self, ParseError> {
+        if s.len() > 13 {
+            return Err(ParseError::new(ParseErrorKind::BadLength, "bad name string"));
+        }
+        match str_to_name(s) {
+            INVALID_NAME => Err(ParseError::new(ParseErrorKind::BadCharacter, "bad name string")),
+            n => Ok(Name{ n }),
+        }
+    }
+
 	///
     pub fn to_string(&self) -> String {
         n2s(self.n)
@@ -252,6 +268,22 @@ impl Packer for Name {
     }
 }
 
+impl core::str::FromStr for Name {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_str(s)
+    }
+}
+
+impl TryFrom<&str> for Name {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_from_str(s)
+    }
+}
+
 pub const SAME_PAYER: Name = Name{n: 0};
 pub const ACTIVE: Name = Name{n: static_str_to_name("active")};
 pub const OWNER: Name = Name{n: static_str_to_name("owner")};
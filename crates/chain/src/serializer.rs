@@ -3,6 +3,11 @@ use core::{
 };
 
 use crate::{
+    boxed::Box,
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
     string::String,
     vec::Vec,
     vmapi::eosio::{
@@ -57,6 +62,53 @@ pub trait Packer {
     fn unpack(&mut self, data: &[u8]) -> usize;
 }
 
+/// Unpacks a value which borrows from the packed data instead of copying it,
+/// e.g. `&'a [u8]` and `&'a str` are views into the action data.
+/// Every `Packer` is also an `Unpack` which returns an owned value.
+pub trait Unpack<'a>: Sized {
+    /// Unpacks a value from `data`, returns the value and the number of bytes read.
+    fn unpack_borrowed(data: &'a [u8]) -> (Self, usize);
+}
+
+impl<'a, T> Unpack<'a> for T where T: Packer + Default {
+    fn unpack_borrowed(data: &'a [u8]) -> (Self, usize) {
+        let mut value = T::default();
+        let size = value.unpack(data);
+        (value, size)
+    }
+}
+
+/// Implement `Unpack` for `&[u8]`, a view of the bytes of a packed `Vec<u8>`.
+impl<'a> Unpack<'a> for &'a [u8] {
+    fn unpack_borrowed(data: &'a [u8]) -> (Self, usize) {
+        let mut length = VarUint32{n: 0};
+        let size = length.unpack(data);
+        let end = match size.checked_add(length.value() as usize) {
+            Some(end) => end,
+            None => {
+                check(false, "&[u8]::unpack_borrowed: buffer overflow");
+                return (&[], size);
+            }
+        };
+        check(data.len() >= end, "&[u8]::unpack_borrowed: buffer overflow");
+        (&data[size..end], end)
+    }
+}
+
+/// Implement `Unpack` for `&str`, a view of the bytes of a packed `String`.
+impl<'a> Unpack<'a> for &'a str {
+    fn unpack_borrowed(data: &'a [u8]) -> (Self, usize) {
+        let (raw, size) = <&[u8]>::unpack_borrowed(data);
+        match core::str::from_utf8(raw) {
+            Ok(s) => (s, size),
+            Err(_) => {
+                check(false, "invalid utf8 string");
+                ("", size)
+            }
+        }
+    }
+}
+
 /// The `Encoder` struct provides methods for packing values that implement the `Packer` trait.
 ///
 /// # Examples
@@ -195,6 +247,23 @@ impl<'a> Decoder<'a> {
         self.pos
     }
 
+    /// Unpacks a value which may borrow from the data of the decoder, see [`Unpack`].
+    ///
+    /// ```
+    /// use rust_chain::serializer::Decoder;
+    ///
+    /// let data = vec![2, b'h', b'i', 7, 0, 0, 0];
+    /// let mut decoder = Decoder::new(&data);
+    /// let s: &str = decoder.unpack_borrowed();
+    /// let n: u32 = decoder.unpack_borrowed();
+    /// assert_eq!((s, n), ("hi", 7));
+    /// ```
+    pub fn unpack_borrowed<T: Unpack<'a>>(&mut self) -> T {
+        let (value, size) = T::unpack_borrowed(&self.buf[self.pos..]);
+        self.pos += size;
+        value
+    }
+
 }
 
 /// A trait for packing and unpacking values
@@ -340,12 +409,20 @@ impl Packer for String {
     fn unpack(&mut self, data: &[u8]) -> usize {
         let mut length = VarUint32{n: 0};
         let size = length.unpack(data);
-        if let Ok(s) = String::from_utf8(data[size..size+length.value() as usize].to_vec()) {
+        let end = match size.checked_add(length.value() as usize) {
+            Some(end) => end,
+            None => {
+                check(false, "String::unpack: buffer overflow");
+                return size;
+            }
+        };
+        check(data.len() >= end, "String::unpack: buffer overflow");
+        if let Ok(s) = String::from_utf8(data[size..end].to_vec()) {
             *self = s;
         } else {
             check(false, "invalid utf8 string");
         }
-        size + length.value() as usize
+        end
     }
 }
 
@@ -433,4 +510,211 @@ impl<T> Packer for Option<T> where T: Packer + Default {
         *self = Some(value);
         dec.get_pos()
     }
-}
\ No newline at end of file
+}
+
+/// Implement `Packer` for fixed size arrays, packed as their elements without a length prefix.
+impl<T, const N: usize> Packer for [T; N] where T: Packer {
+    /// Returns the size of this value in bytes.
+    fn size(&self) -> usize {
+        self.iter().map(|v| v.size()).sum()
+    }
+
+    /// Packs this value into the given encoder.
+    fn pack(&self, enc: &mut Encoder) -> usize {
+        let pos = enc.get_size();
+        for v in self {
+            v.pack(enc);
+        }
+        enc.get_size() - pos
+    }
+
+    /// Unpacks this value from the given data.
+    fn unpack(&mut self, data: &[u8]) -> usize {
+        let mut dec = Decoder::new(data);
+        for v in self.iter_mut() {
+            dec.unpack(v);
+        }
+        dec.get_pos()
+    }
+}
+
+/// Implement `Packer` for tuples, packed as their elements in order.
+macro_rules! impl_packed_tuple {
+    ( $($name:ident $index:tt),+ ) => {
+        impl<$($name: Packer),+> Packer for ($($name,)+) {
+            /// Returns the size of this value in bytes.
+            fn size(&self) -> usize {
+                0 $(+ self.$index.size())+
+            }
+
+            /// Packs this value into the given encoder.
+            fn pack(&self, enc: &mut Encoder) -> usize {
+                let pos = enc.get_size();
+                $(self.$index.pack(enc);)+
+                enc.get_size() - pos
+            }
+
+            /// Unpacks this value from the given data.
+            fn unpack(&mut self, data: &[u8]) -> usize {
+                let mut dec = Decoder::new(data);
+                $(dec.unpack(&mut self.$index);)+
+                dec.get_pos()
+            }
+        }
+    };
+}
+
+impl_packed_tuple!(A 0);
+impl_packed_tuple!(A 0, B 1);
+impl_packed_tuple!(A 0, B 1, C 2);
+impl_packed_tuple!(A 0, B 1, C 2, D 3);
+impl_packed_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_packed_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_packed_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_packed_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_packed_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_packed_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_packed_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_packed_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+/// Implement `Packer` for `BTreeMap<K, V>` type, packed as an array of `(key, value)` pairs in key order.
+impl<K, V> Packer for BTreeMap<K, V> where K: Packer + Default + Ord, V: Packer + Default {
+    /// Returns the size of this value in bytes.
+    fn size(&self) -> usize {
+        let size: usize = self.iter().map(|(k, v)| k.size() + v.size()).sum();
+        VarUint32::new(self.len() as u32).size() + size
+    }
+
+    /// Packs this value into the given encoder.
+    fn pack(&self, enc: &mut Encoder) -> usize {
+        let pos = enc.get_size();
+        VarUint32::new(self.len() as u32).pack(enc);
+        for (k, v) in self {
+            k.pack(enc);
+            v.pack(enc);
+        }
+        enc.get_size() - pos
+    }
+
+    /// Unpacks this value from the given data.
+    fn unpack(&mut self, data: &[u8]) -> usize {
+        let mut dec = Decoder::new(data);
+        let mut size = VarUint32{n: 0};
+        dec.unpack(&mut size);
+        self.clear();
+        for _ in 0..size.value() {
+            let mut k: K = Default::default();
+            let mut v: V = Default::default();
+            dec.unpack(&mut k);
+            dec.unpack(&mut v);
+            check(self.insert(k, v).is_none(), "BTreeMap::unpack: duplicate key");
+        }
+        dec.get_pos()
+    }
+}
+
+/// Implement `Packer` for `BTreeSet<T>` type, packed as an array in order.
+impl<T> Packer for BTreeSet<T> where T: Packer + Default + Ord {
+    /// Returns the size of this value in bytes.
+    fn size(&self) -> usize {
+        let size: usize = self.iter().map(|v| v.size()).sum();
+        VarUint32::new(self.len() as u32).size() + size
+    }
+
+    /// Packs this value into the given encoder.
+    fn pack(&self, enc: &mut Encoder) -> usize {
+        let pos = enc.get_size();
+        VarUint32::new(self.len() as u32).pack(enc);
+        for v in self {
+            v.pack(enc);
+        }
+        enc.get_size() - pos
+    }
+
+    /// Unpacks this value from the given data.
+    fn unpack(&mut self, data: &[u8]) -> usize {
+        let mut dec = Decoder::new(data);
+        let mut size = VarUint32{n: 0};
+        dec.unpack(&mut size);
+        self.clear();
+        for _ in 0..size.value() {
+            let mut v: T = Default::default();
+            dec.unpack(&mut v);
+            check(self.insert(v), "BTreeSet::unpack: duplicate value");
+        }
+        dec.get_pos()
+    }
+}
+
+/// Implement `Packer` for `Box<T>` type, packed as the boxed value.
+impl<T> Packer for Box<T> where T: Packer {
+    /// Returns the size of this value in bytes.
+    fn size(&self) -> usize {
+        (**self).size()
+    }
+
+    /// Packs this value into the given encoder.
+    fn pack(&self, enc: &mut Encoder) -> usize {
+        (**self).pack(enc)
+    }
+
+    /// Unpacks this value from the given data.
+    fn unpack(&mut self, data: &[u8]) -> usize {
+        (**self).unpack(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T: Packer + Default>(value: &T) -> (Vec<u8>, T) {
+        let data = Encoder::pack(value);
+        assert_eq!(data.len(), value.size());
+        let mut unpacked = T::default();
+        assert_eq!(unpacked.unpack(&data), data.len());
+        (data, unpacked)
+    }
+
+    #[test]
+    fn test_pack_containers() {
+        let (data, value) = round_trip(&[1u16, 2, 3]);
+        assert_eq!(data, [1, 0, 2, 0, 3, 0]);
+        assert_eq!(value, [1, 2, 3]);
+
+        let tuple = (1u8, String::from("ab"), 2u32, true, 3i64, 4u16, 5u8, 6u8, 7u8, 8u8, 9u8, Some(10u8));
+        let (data, value) = round_trip(&tuple);
+        assert_eq!(data[..4], [1, 2, b'a', b'b']);
+        assert_eq!(value, tuple);
+
+        let map = BTreeMap::from([(2u64, String::from("b")), (1u64, String::from("a"))]);
+        let (data, value) = round_trip(&map);
+        assert_eq!(data, [2, 1, 0, 0, 0, 0, 0, 0, 0, 1, b'a', 2, 0, 0, 0, 0, 0, 0, 0, 1, b'b']);
+        assert_eq!(value, map);
+
+        let set = BTreeSet::from([3u8, 1, 2]);
+        let (data, value) = round_trip(&set);
+        assert_eq!(data, [3, 1, 2, 3]);
+        assert_eq!(value, set);
+
+        let (data, value) = round_trip(&Box::new(7u32));
+        assert_eq!(data, [7, 0, 0, 0]);
+        assert_eq!(*value, 7);
+    }
+
+    #[test]
+    fn test_unpack_borrowed() {
+        let mut data = Encoder::pack(&vec![1u8, 2, 3]);
+        data.extend(Encoder::pack(&String::from("hello")));
+        data.extend(Encoder::pack(&5u64));
+        let mut dec = Decoder::new(&data);
+        let bytes: &[u8] = dec.unpack_borrowed();
+        let s: &str = dec.unpack_borrowed();
+        let n: u64 = dec.unpack_borrowed();
+        assert_eq!(bytes, [1, 2, 3]);
+        assert_eq!(s, "hello");
+        assert_eq!(n, 5);
+        assert_eq!(dec.get_pos(), data.len());
+        assert_eq!(bytes.as_ptr(), data[1..].as_ptr());
+    }
+}
@@ -1,5 +1,9 @@
 use crate::utils::{
+    ParseError,
+    ParseErrorKind,
     decode_hex,
+    try_decode_hex,
+    check_parse,
 };
 
 use crate::vmapi::eosio::{
@@ -25,6 +29,8 @@ use crate::varint::{
     VarUint32,
 };
 
+use crate::base58;
+
 use crate::{
     vec::Vec,
     string::String,
@@ -83,11 +89,34 @@ pub struct Checksum160 {
 impl Checksum160 {
     ///
     pub fn from_hex(s: &str) -> Self {
-        check(s.len() == 40, "Checksum160: bad hex string length");
-        let data = decode_hex(s);
+        check_parse(Self::try_from_hex(s))
+    }
+
+    /// Like `from_hex`, but returns an error instead of aborting the action.
+    pub fn try_from_hex(s: &str) -> Result<Self, ParseError> {
+        if s.len() != 40 {
+            return Err(ParseError::new(ParseErrorKind::BadLength, "Checksum160: bad hex string length"));
+        }
+        let data = try_decode_hex(s)?;
         let mut ret = Self::default();
         slice_copy(&mut ret.data, &data);
-        return ret;
+        Ok(ret)
+    }
+}
+
+impl core::str::FromStr for Checksum160 {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_hex(s)
+    }
+}
+
+impl TryFrom<&str> for Checksum160 {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_from_hex(s)
     }
 }
 
@@ -122,11 +151,34 @@ pub struct Checksum256 {
 impl Checksum256 {
     ///
     pub fn from_hex(s: &str) -> Self {
-        check(s.len() == 64, "Checksum256: bad hex string length");
-        let data = decode_hex(s);
+        check_parse(Self::try_from_hex(s))
+    }
+
+    /// Like `from_hex`, but returns an error instead of aborting the action.
+    pub fn try_from_hex(s: &str) -> Result<Self, ParseError> {
+        if s.len() != 64 {
+            return Err(ParseError::new(ParseErrorKind::BadLength, "Checksum256: bad hex string length"));
+        }
+        let data = try_decode_hex(s)?;
         let mut ret = Self::default();
         slice_copy(&mut ret.data, &data);
-        return ret;
+        Ok(ret)
+    }
+}
+
+impl core::str::FromStr for Checksum256 {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_hex(s)
+    }
+}
+
+impl TryFrom<&str> for Checksum256 {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_from_hex(s)
     }
 }
 
@@ -160,11 +212,34 @@ pub struct Checksum512 {
 impl Checksum512 {
     ///
     pub fn from_hex(s: &str) -> Self {
-        check(s.len() == 128, "Checksum512: bad hex string length");
-        let data = decode_hex(s);
+        check_parse(Self::try_from_hex(s))
+    }
+
+    /// Like `from_hex`, but returns an error instead of aborting the action.
+    pub fn try_from_hex(s: &str) -> Result<Self, ParseError> {
+        if s.len() != 128 {
+            return Err(ParseError::new(ParseErrorKind::BadLength, "Checksum512: bad hex string length"));
+        }
+        let data = try_decode_hex(s)?;
         let mut ret = Self::default();
         slice_copy(&mut ret.data, &data);
-        return ret;
+        Ok(ret)
+    }
+}
+
+impl core::str::FromStr for Checksum512 {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_hex(s)
+    }
+}
+
+impl TryFrom<&str> for Checksum512 {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_from_hex(s)
     }
 }
 
@@ -213,6 +288,22 @@ impl ECCPublicKey {
         slice_copy(&mut ret.data, &data);
         return ret;
     }
+
+    /// Parses a K1 or R1 key in the `EOS...`, `PUB_K1_...` or `PUB_R1_...` form.
+    pub fn from_str(s: &str) -> Self {
+        match PublicKey::from_str(s) {
+            PublicKey::K1(x) | PublicKey::R1(x) => x,
+            PublicKey::WebAuth(_) => {
+                check(false, "ECCPublicKey: WebAuthN key not supported");
+                Self::default()
+            }
+        }
+    }
+
+    /// Formats the key in the legacy `EOS...` form.
+    pub fn to_string(&self) -> String {
+        PublicKey::K1(*self).to_string()
+    }
 }
 
 impl Default for ECCPublicKey {
@@ -351,6 +442,44 @@ pub enum PublicKey {
     WebAuth(WebAuthNPublicKey),
 }
 
+impl PublicKey {
+    /// Parses a key in the legacy `EOS...` form or in the `PUB_K1_...`, `PUB_R1_...`, `PUB_WA_...` form.
+    pub fn from_str(s: &str) -> Self {
+        if let Some(encoded) = s.strip_prefix("EOS") {
+            return PublicKey::K1(Self::decode_ecc_key(encoded, ""));
+        } else if let Some(encoded) = s.strip_prefix("PUB_K1_") {
+            return PublicKey::K1(Self::decode_ecc_key(encoded, "K1"));
+        } else if let Some(encoded) = s.strip_prefix("PUB_R1_") {
+            return PublicKey::R1(Self::decode_ecc_key(encoded, "R1"));
+        } else if let Some(encoded) = s.strip_prefix("PUB_WA_") {
+            let data = base58::decode_with_checksum(encoded, "WA");
+            let mut key = WebAuthNPublicKey::default();
+            let size = key.unpack(&data);
+            check(size == data.len(), "PublicKey: bad WebAuthN key length");
+            return PublicKey::WebAuth(key);
+        }
+        check(false, "PublicKey: unknown key prefix");
+        Self::default()
+    }
+
+    /// Formats the key the way nodeos does: `EOS...` for K1 keys, `PUB_R1_...` and `PUB_WA_...` for the others.
+    pub fn to_string(&self) -> String {
+        match self {
+            PublicKey::K1(x) => String::from("EOS") + &base58::encode_with_checksum(&x.data, ""),
+            PublicKey::R1(x) => String::from("PUB_R1_") + &base58::encode_with_checksum(&x.data, "R1"),
+            PublicKey::WebAuth(x) => String::from("PUB_WA_") + &base58::encode_with_checksum(&Encoder::pack(x), "WA"),
+        }
+    }
+
+    fn decode_ecc_key(encoded: &str, suffix: &str) -> ECCPublicKey {
+        let data = base58::decode_with_checksum(encoded, suffix);
+        check(data.len() == 33, "PublicKey: bad key length");
+        let mut ret = ECCPublicKey::default();
+        slice_copy(&mut ret.data, &data);
+        ret
+    }
+}
+
 impl Default for PublicKey {
     ///
     #[inline]
@@ -421,10 +550,12 @@ impl Packer for PublicKey {
 #[cfg_attr(feature = "std", derive(eosio_scale_info::TypeInfo))]
 #[derive(Clone, Eq, PartialEq)]
 pub struct Signature {
-    /// Signature type
+    /// Signature type, 0: K1, 1: R1, 2: WebAuthN
 	ty: u8,
     ///
 	data: [u8; 65],
+    /// packed `auth_data` and `client_json` of a WebAuthN signature
+    webauthn: Vec<u8>,
 }
 
 impl Signature {
@@ -437,18 +568,54 @@ impl Signature {
         slice_copy(&mut ret.data, &data);
         return ret;
     }
+
+    /// Parses a signature in the `SIG_K1_...`, `SIG_R1_...` or `SIG_WA_...` form.
+    pub fn from_str(s: &str) -> Self {
+        let (ty, suffix, encoded) = if let Some(encoded) = s.strip_prefix("SIG_K1_") {
+            (0u8, "K1", encoded)
+        } else if let Some(encoded) = s.strip_prefix("SIG_R1_") {
+            (1u8, "R1", encoded)
+        } else if let Some(encoded) = s.strip_prefix("SIG_WA_") {
+            (2u8, "WA", encoded)
+        } else {
+            check(false, "Signature: unknown signature prefix");
+            return Self::default();
+        };
+
+        let data = base58::decode_with_checksum(encoded, suffix);
+        let mut raw = Vec::with_capacity(data.len() + 1);
+        raw.push(ty);
+        raw.extend_from_slice(&data);
+        let mut ret = Self::default();
+        let size = ret.unpack(&raw);
+        check(size == raw.len(), "Signature: bad signature length");
+        return ret;
+    }
+
+    ///
+    pub fn to_string(&self) -> String {
+        let (prefix, suffix) = match self.ty {
+            0 => ("SIG_K1_", "K1"),
+            1 => ("SIG_R1_", "R1"),
+            _ => ("SIG_WA_", "WA"),
+        };
+        let mut data = Vec::with_capacity(self.data.len() + self.webauthn.len());
+        data.extend_from_slice(&self.data);
+        data.extend_from_slice(&self.webauthn);
+        String::from(prefix) + &base58::encode_with_checksum(&data, suffix)
+    }
 }
 
 impl Default for Signature {
     fn default() -> Self {
-        Self { ty: 0, data: [0; 65] }
+        Self { ty: 0, data: [0; 65], webauthn: Vec::new() }
     }
 }
 
 impl Packer for Signature {
     ///
     fn size(&self) -> usize {
-        return 66;
+        return 66 + self.webauthn.len();
     }
 
     ///
@@ -456,16 +623,26 @@ impl Packer for Signature {
         self.ty.pack(enc);
         let data = enc.alloc(self.data.len());
         slice_copy(data, &self.data);
+        let data = enc.alloc(self.webauthn.len());
+        slice_copy(data, &self.webauthn);
         self.size()
     }
 
     ///
     fn unpack(&mut self, data: &[u8]) -> usize {
-        let size = self.size();
-        check(data.len() >= size, "Signature::unpack: buffer overflow");
+        check(data.len() >= 66, "Signature::unpack: buffer overflow");
         self.ty = data[0];
-        check(self.ty == 0, "bad signature type");
-        slice_copy(&mut self.data, &data[1..size]);
+        check(self.ty <= 2, "bad signature type");
+        slice_copy(&mut self.data, &data[1..66]);
+        self.webauthn.clear();
+        if self.ty == 2 {
+            let mut dec = Decoder::new(&data[66..]);
+            let mut auth_data: Vec<u8> = Vec::new();
+            let mut client_json = String::new();
+            dec.unpack(&mut auth_data);
+            dec.unpack(&mut client_json);
+            self.webauthn.extend_from_slice(&data[66..66 + dec.get_pos()]);
+        }
         return self.size();
     }
 }
@@ -568,7 +745,7 @@ impl Default for Uint256 {
 
 ///
 #[cfg_attr(feature = "std", derive(eosio_scale_info::TypeInfo))]
-#[derive(Copy, Clone, Default, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
 pub struct TimePoint {
     /// elapsed in microseconds
     pub elapsed: u64,
@@ -591,7 +768,7 @@ impl Packer for TimePoint {
 
 ///
 #[cfg_attr(feature = "std", derive(eosio_scale_info::TypeInfo))]
-#[derive(Copy, Clone, Eq, PartialEq, Default)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
 pub struct TimePointSec {
     ///
     pub seconds: u32,
@@ -624,7 +801,7 @@ impl Packer for TimePointSec {
 
 ///
 #[cfg_attr(feature = "std", derive(eosio_scale_info::TypeInfo))]
-#[derive(Copy, Clone, Default)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
 pub struct BlockTimeStampType {
     ///
     pub slot: u32,
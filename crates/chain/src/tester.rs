@@ -0,0 +1,280 @@
+//! Building blocks of the typed test clients generated by `#[chain::contract]`.
+
+use core::marker::PhantomData;
+use std::collections::HashSet;
+
+use serde_json::{Map, Value};
+
+use chaintester::{
+    client::{ChainTesterError, Result},
+    GetTableRowsPrams,
+    Tester,
+};
+
+use crate::serializer::{
+    Packer,
+};
+
+use crate::db::PrimaryValueInterface;
+use crate::name::Name;
+
+use crate::action::decode_action_return_value;
+
+/// An action that is ready to be pushed to a `Tester`, `R` is the return type of the action.
+pub struct ActionCall<'a, R> {
+    tester: &'a mut dyn Tester,
+    contract: String,
+    action: String,
+    data: Vec<u8>,
+    permissions: Map<String, Value>,
+    _result: PhantomData<R>,
+}
+
+impl<'a, R> ActionCall<'a, R> {
+    ///
+    pub fn new(tester: &'a mut dyn Tester, contract: &str, action: &str, data: Vec<u8>) -> Self {
+        Self {
+            tester,
+            contract: contract.into(),
+            action: action.into(),
+            data,
+            permissions: Map::new(),
+            _result: PhantomData,
+        }
+    }
+
+    /// Adds an authorization in the `actor@permission` form, `actor` alone stands for `actor@active`.
+    pub fn auth(mut self, auth: &str) -> Self {
+        let (actor, permission) = auth.split_once('@').unwrap_or((auth, "active"));
+        self.permissions.insert(actor.into(), Value::String(permission.into()));
+        self
+    }
+
+    /// Pushes the action and returns the transaction trace.
+    pub fn send(self) -> Result<Value> {
+        let permissions = Value::Object(self.permissions).to_string();
+        self.tester.push_action(&self.contract, &self.action, self.data.into(), &permissions)
+    }
+}
+
+impl<'a, R: Packer + Default> ActionCall<'a, R> {
+    /// Pushes the action and decodes its return value.
+    pub fn call(self) -> Result<R> {
+        let tx = self.send()?;
+        decode_action_return_value(&tx).ok_or(ChainTesterError {
+            json: Some(tx),
+            error_string: Some("action has no return value".into()),
+        })
+    }
+}
+
+/// A `get_table_rows` query whose rows are unpacked into `T`, see [`TableReader::get_table`].
+pub struct TableQuery<'a, T> {
+    tester: &'a mut dyn Tester,
+    code: String,
+    scope: String,
+    table: String,
+    lower_bound: String,
+    upper_bound: String,
+    index_position: String,
+    key_type: String,
+    reverse: bool,
+    limit: i64,
+    _row: PhantomData<T>,
+}
+
+impl<'a, T> TableQuery<'a, T>
+where T: Packer + Default + PrimaryValueInterface
+{
+    ///
+    pub fn new(tester: &'a mut dyn Tester, code: &str, scope: &str, table: &str) -> Self {
+        Self {
+            tester,
+            code: code.into(),
+            scope: scope.into(),
+            table: table.into(),
+            lower_bound: String::new(),
+            upper_bound: String::new(),
+            index_position: String::new(),
+            key_type: String::new(),
+            reverse: false,
+            limit: 100,
+            _row: PhantomData,
+        }
+    }
+
+    /// Inclusive lower bound of the index key.
+    pub fn lower_bound(mut self, bound: &str) -> Self {
+        self.lower_bound = bound.into();
+        self
+    }
+
+    /// Inclusive upper bound of the index key.
+    pub fn upper_bound(mut self, bound: &str) -> Self {
+        self.upper_bound = bound.into();
+        self
+    }
+
+    /// Queries by a secondary index instead of the primary key,
+    /// `position` is 2 for the first secondary index and `key_type` is the ABI index type, e.g. `i64` or `float64`.
+    pub fn index(mut self, position: usize, key_type: &str) -> Self {
+        self.index_position = position.to_string();
+        self.key_type = key_type.into();
+        self
+    }
+
+    /// Returns the rows in descending key order.
+    pub fn reverse(mut self) -> Self {
+        self.reverse = !self.reverse;
+        self
+    }
+
+    /// Number of rows requested per page, 100 by default.
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Runs the query and yields `(primary_key, payer, row)` for every matching row,
+    /// the pages are requested as the rows are consumed.
+    pub fn iter(self) -> TableRows<'a, T> {
+        let lower_bound = self.lower_bound.clone();
+        let upper_bound = self.upper_bound.clone();
+        let limit = self.limit;
+        TableRows {
+            query: self,
+            lower_bound,
+            upper_bound,
+            limit,
+            rows: Vec::new().into_iter(),
+            seen: HashSet::new(),
+            done: false,
+        }
+    }
+}
+
+/// The rows of a [`TableQuery`], see [`TableQuery::iter`].
+pub struct TableRows<'a, T> {
+    query: TableQuery<'a, T>,
+    lower_bound: String,
+    upper_bound: String,
+    limit: i64,
+    rows: std::vec::IntoIter<(u64, Name, T)>,
+    // a page of a secondary index starts at the key of the row after the previous page,
+    // rows which have the same key as that row were returned already
+    seen: HashSet<u64>,
+    done: bool,
+}
+
+impl<'a, T> TableRows<'a, T>
+where T: Packer + Default + PrimaryValueInterface
+{
+    fn next_page(&mut self) -> Result<()> {
+        let query = &mut self.query;
+        let params = GetTableRowsPrams {
+            json: false,
+            code: &query.code,
+            scope: &query.scope,
+            table: &query.table,
+            lower_bound: &self.lower_bound,
+            upper_bound: &self.upper_bound,
+            limit: self.limit,
+            key_type: &query.key_type,
+            index_position: &query.index_position,
+            encode_type: "",
+            reverse: query.reverse,
+            show_payer: true,
+        };
+        let ret = query.tester.get_table_rows_ex(&params)?;
+        let mut rows: Vec<(u64, Name, T)> = Vec::new();
+        for row in ret["rows"].as_array().into_iter().flatten() {
+            let invalid_row = || ChainTesterError {
+                json: Some(row.clone()),
+                error_string: Some("invalid table row".into()),
+            };
+            let data = row["data"].as_str().and_then(|data| hex::decode(data).ok()).ok_or_else(invalid_row)?;
+            let payer = row["payer"].as_str().ok_or_else(invalid_row)?;
+            let mut value = T::default();
+            value.unpack(&data);
+            let primary = value.get_primary();
+            if self.seen.insert(primary) {
+                rows.push((primary, Name::from_str(payer), value));
+            }
+        }
+
+        if ret["more"] != Value::Bool(true) {
+            self.done = true;
+        } else {
+            let next_key = ret["next_key"].as_str().unwrap_or_default();
+            if next_key.is_empty() {
+                return Err(ChainTesterError {
+                    json: Some(ret.clone()),
+                    error_string: Some("get_table_rows returned more rows without next_key".into()),
+                });
+            }
+            if query.reverse {
+                self.upper_bound = next_key.into();
+            } else {
+                self.lower_bound = next_key.into();
+            }
+            // all rows of the page have the same key, a larger page gets past them
+            if rows.is_empty() {
+                self.limit *= 2;
+            }
+        }
+        self.rows = rows.into_iter();
+        Ok(())
+    }
+}
+
+impl<'a, T> Iterator for TableRows<'a, T>
+where T: Packer + Default + PrimaryValueInterface
+{
+    type Item = Result<(u64, Name, T)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.rows.next() {
+                return Some(Ok(row));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(err) = self.next_page() {
+                self.done = true;
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+/// Typed table reads for `ChainTester` and `MockTester`.
+pub trait TableReader {
+    /// Starts a query on `table` of `code` in `scope`, rows are unpacked with `Packer::unpack`,
+    /// so tests can read a table with the struct the contract declares for it.
+    fn get_table<T>(&mut self, code: &str, scope: &str, table: &str) -> TableQuery<'_, T>
+    where T: Packer + Default + PrimaryValueInterface;
+}
+
+impl<X: Tester> TableReader for X {
+    fn get_table<T>(&mut self, code: &str, scope: &str, table: &str) -> TableQuery<'_, T>
+    where T: Packer + Default + PrimaryValueInterface
+    {
+        TableQuery::new(self, code, scope, table)
+    }
+}
+
+impl TableReader for dyn Tester + '_ {
+    fn get_table<T>(&mut self, code: &str, scope: &str, table: &str) -> TableQuery<'_, T>
+    where T: Packer + Default + PrimaryValueInterface
+    {
+        TableQuery::new(self, code, scope, table)
+    }
+}
+
+/// Reads all rows of `table` in `scope` and unpacks them into `T`.
+pub fn get_table_rows<T>(tester: &mut dyn Tester, code: &str, scope: &str, table: &str) -> Result<Vec<T>>
+where T: Packer + Default + PrimaryValueInterface
+{
+    tester.get_table::<T>(code, scope, table).iter().map(|row| row.map(|(_, _, value)| value)).collect()
+}
@@ -0,0 +1,606 @@
+use core::ops::{
+    Add,
+    AddAssign,
+    Div,
+    Mul,
+    Neg,
+    Sub,
+    SubAssign,
+};
+
+use crate::format;
+use crate::string::String;
+
+use crate::serializer::{
+    Packer,
+    Encoder,
+};
+
+use crate::structs::{
+    TimePoint,
+    TimePointSec,
+    BlockTimeStampType,
+};
+
+use crate::utils::{
+    ParseError,
+    ParseErrorKind,
+    check_parse,
+};
+
+use crate::vmapi::eosio::check;
+
+/// Interval between two blocks in milliseconds.
+pub const BLOCK_INTERVAL_MS: i64 = 500;
+
+/// Epoch of block timestamps, 2000-01-01T00:00:00.000, in milliseconds since the Unix epoch.
+pub const BLOCK_TIMESTAMP_EPOCH_MS: i64 = 946_684_800_000;
+
+/// A signed duration in microseconds, the same as `eosio::microseconds`.
+#[cfg_attr(feature = "std", derive(eosio_scale_info::TypeInfo))]
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Microseconds {
+    ///
+    pub count: i64,
+}
+
+impl Microseconds {
+    ///
+    pub const fn new(count: i64) -> Self {
+        Self { count }
+    }
+
+    ///
+    pub fn milliseconds(ms: i64) -> Self {
+        return Self::new(1_000) * ms;
+    }
+
+    ///
+    pub fn seconds(s: i64) -> Self {
+        return Self::new(1_000_000) * s;
+    }
+
+    ///
+    pub fn minutes(m: i64) -> Self {
+        return Self::seconds(60) * m;
+    }
+
+    ///
+    pub fn hours(h: i64) -> Self {
+        return Self::minutes(60) * h;
+    }
+
+    ///
+    pub fn days(d: i64) -> Self {
+        return Self::hours(24) * d;
+    }
+
+    ///
+    pub const fn count(&self) -> i64 {
+        self.count
+    }
+
+    /// Whole seconds, truncated towards zero.
+    pub const fn to_seconds(&self) -> i64 {
+        self.count / 1_000_000
+    }
+
+    /// Returns `None` if the result overflows.
+    pub fn checked_add(&self, other: Self) -> Option<Self> {
+        return Some(Self::new(self.count.checked_add(other.count)?));
+    }
+
+    /// Returns `None` if the result overflows.
+    pub fn checked_sub(&self, other: Self) -> Option<Self> {
+        return Some(Self::new(self.count.checked_sub(other.count)?));
+    }
+
+    /// Returns `None` if the result overflows.
+    pub fn checked_mul(&self, n: i64) -> Option<Self> {
+        return Some(Self::new(self.count.checked_mul(n)?));
+    }
+}
+
+impl Add for Microseconds {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        let ret = self.checked_add(other);
+        check(ret.is_some(), "microseconds: overflow");
+        return ret.unwrap();
+    }
+}
+
+impl Sub for Microseconds {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        let ret = self.checked_sub(other);
+        check(ret.is_some(), "microseconds: overflow");
+        return ret.unwrap();
+    }
+}
+
+impl Neg for Microseconds {
+    type Output = Self;
+    fn neg(self) -> Self {
+        let ret = self.count.checked_neg();
+        check(ret.is_some(), "microseconds: overflow");
+        return Self::new(ret.unwrap());
+    }
+}
+
+impl Mul<i64> for Microseconds {
+    type Output = Self;
+    fn mul(self, n: i64) -> Self {
+        let ret = self.checked_mul(n);
+        check(ret.is_some(), "microseconds: overflow");
+        return ret.unwrap();
+    }
+}
+
+impl Div<i64> for Microseconds {
+    type Output = Self;
+    fn div(self, n: i64) -> Self {
+        check(n != 0, "microseconds: division by zero");
+        let ret = self.count.checked_div(n);
+        check(ret.is_some(), "microseconds: overflow");
+        return Self::new(ret.unwrap());
+    }
+}
+
+impl AddAssign for Microseconds {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Microseconds {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Packer for Microseconds {
+    fn size(&self) -> usize {
+        return 8;
+    }
+
+    fn pack(&self, enc: &mut Encoder) -> usize {
+        self.count.pack(enc)
+    }
+
+    fn unpack(&mut self, raw: &[u8]) -> usize {
+        check(raw.len() >= self.size(), "Microseconds.unpack: buffer overflow!");
+        return self.count.unpack(raw);
+    }
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    return era * 146_097 + doe - 719_468;
+}
+
+/// Inverse of `days_from_civil`, returns `(year, month, day)`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    return (year, month, day);
+}
+
+fn is_leap_year(year: i64) -> bool {
+    return year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Formats seconds since the Unix epoch as `YYYY-MM-DDTHH:MM:SS`.
+fn format_seconds(seconds: i64) -> String {
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let secs = seconds.rem_euclid(86_400);
+    return format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}", year, month, day, secs / 3_600, secs % 3_600 / 60, secs % 60);
+}
+
+fn parse_number(s: &str) -> Result<u32, ParseError> {
+    if s.is_empty() || !s.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(ParseError::new(ParseErrorKind::BadCharacter, "bad time string"));
+    }
+    return s.parse::<u32>().map_err(|_| ParseError::new(ParseErrorKind::BadCharacter, "bad time string"));
+}
+
+/// Parses `YYYY-MM-DDTHH:MM:SS[.fff][Z]` into microseconds since the Unix epoch.
+/// The fraction may have up to 6 digits.
+fn parse_iso(s: &str) -> Result<i64, ParseError> {
+    let s = s.strip_suffix('Z').unwrap_or(s);
+    let (datetime, fraction) = match s.split_once('.') {
+        Some((datetime, fraction)) => (datetime, Some(fraction)),
+        None => (s, None),
+    };
+    let b = datetime.as_bytes();
+    if b.len() != 19 {
+        return Err(ParseError::new(ParseErrorKind::BadLength, "bad time string"));
+    }
+    if b[4] != b'-' || b[7] != b'-' || b[10] != b'T' || b[13] != b':' || b[16] != b':' {
+        return Err(ParseError::new(ParseErrorKind::BadCharacter, "bad time string"));
+    }
+
+    let year = parse_number(&datetime[0..4])? as i64;
+    let month = parse_number(&datetime[5..7])?;
+    let day = parse_number(&datetime[8..10])?;
+    let hour = parse_number(&datetime[11..13])? as i64;
+    let minute = parse_number(&datetime[14..16])? as i64;
+    let second = parse_number(&datetime[17..19])? as i64;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) || hour > 23 || minute > 59 || second > 59 {
+        return Err(ParseError::new(ParseErrorKind::OutOfRange, "bad time string"));
+    }
+
+    let mut micros = 0i64;
+    if let Some(fraction) = fraction {
+        if fraction.len() > 6 {
+            return Err(ParseError::new(ParseErrorKind::BadLength, "bad time string"));
+        }
+        micros = parse_number(fraction)? as i64 * 10i64.pow(6 - fraction.len() as u32);
+    }
+
+    let seconds = days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second;
+    return Ok(seconds * 1_000_000 + micros);
+}
+
+impl TimePoint {
+    ///
+    pub const fn new(elapsed: u64) -> Self {
+        Self { elapsed }
+    }
+
+    ///
+    pub fn time_since_epoch(&self) -> Microseconds {
+        return Microseconds::new(self.elapsed as i64);
+    }
+
+    ///
+    pub fn sec_since_epoch(&self) -> u32 {
+        let seconds = self.elapsed / 1_000_000;
+        check(seconds <= u32::MAX as u64, "time_point: seconds since epoch overflow");
+        return seconds as u32;
+    }
+
+    /// Returns `None` if the result is before 1970-01-01 or overflows.
+    pub fn checked_add(&self, d: Microseconds) -> Option<Self> {
+        let elapsed = self.elapsed as i128 + d.count as i128;
+        if elapsed < 0 || elapsed > u64::MAX as i128 {
+            return None;
+        }
+        return Some(Self::new(elapsed as u64));
+    }
+
+    /// Returns `None` if the result is before 1970-01-01 or overflows.
+    pub fn checked_sub(&self, d: Microseconds) -> Option<Self> {
+        return self.checked_add(Microseconds::new(d.count.checked_neg()?));
+    }
+
+    /// Formats the time as `2023-01-01T00:00:00.000`, the form used in ABI JSON.
+    pub fn to_string(&self) -> String {
+        let millis = (self.elapsed % 1_000_000) / 1_000;
+        return format_seconds((self.elapsed / 1_000_000) as i64) + &format!(".{:03}", millis);
+    }
+
+    /// Parses `2023-01-01T00:00:00.000`, the fraction and a trailing `Z` are optional.
+    pub fn from_iso_str(s: &str) -> Self {
+        check_parse(Self::try_from_iso_str(s))
+    }
+
+    /// Like `from_iso_str`, but returns an error instead of aborting the action.
+    pub fn try_from_iso_str(s: &str) -> Result<Self, ParseError> {
+        let micros = parse_iso(s)?;
+        if micros < 0 {
+            return Err(ParseError::new(ParseErrorKind::OutOfRange, "time is before 1970-01-01"));
+        }
+        return Ok(Self::new(micros as u64));
+    }
+}
+
+impl Add<Microseconds> for TimePoint {
+    type Output = Self;
+    fn add(self, d: Microseconds) -> Self {
+        let ret = self.checked_add(d);
+        check(ret.is_some(), "time_point: out of range");
+        return ret.unwrap();
+    }
+}
+
+impl Sub<Microseconds> for TimePoint {
+    type Output = Self;
+    fn sub(self, d: Microseconds) -> Self {
+        let ret = self.checked_sub(d);
+        check(ret.is_some(), "time_point: out of range");
+        return ret.unwrap();
+    }
+}
+
+impl Sub for TimePoint {
+    type Output = Microseconds;
+    fn sub(self, other: Self) -> Microseconds {
+        let count = self.elapsed as i128 - other.elapsed as i128;
+        check(count >= i64::MIN as i128 && count <= i64::MAX as i128, "time_point: difference overflow");
+        return Microseconds::new(count as i64);
+    }
+}
+
+impl AddAssign<Microseconds> for TimePoint {
+    fn add_assign(&mut self, d: Microseconds) {
+        *self = *self + d;
+    }
+}
+
+impl SubAssign<Microseconds> for TimePoint {
+    fn sub_assign(&mut self, d: Microseconds) {
+        *self = *self - d;
+    }
+}
+
+impl From<TimePointSec> for TimePoint {
+    fn from(t: TimePointSec) -> Self {
+        return t.to_time_point();
+    }
+}
+
+impl From<BlockTimeStampType> for TimePoint {
+    fn from(t: BlockTimeStampType) -> Self {
+        return t.to_time_point();
+    }
+}
+
+impl TimePointSec {
+    ///
+    pub fn to_time_point(&self) -> TimePoint {
+        return TimePoint::new(self.seconds as u64 * 1_000_000);
+    }
+
+    ///
+    pub fn sec_since_epoch(&self) -> u32 {
+        return self.seconds;
+    }
+
+    /// Formats the time as `2023-01-01T00:00:00`, the form used in ABI JSON.
+    pub fn to_string(&self) -> String {
+        return format_seconds(self.seconds as i64);
+    }
+
+    /// Whole seconds of the duration are added, returns `None` if the result is out of range.
+    pub fn checked_add(&self, d: Microseconds) -> Option<Self> {
+        return self.add_seconds(d.to_seconds());
+    }
+
+    /// Whole seconds of the duration are subtracted, returns `None` if the result is out of range.
+    pub fn checked_sub(&self, d: Microseconds) -> Option<Self> {
+        return self.add_seconds(-d.to_seconds());
+    }
+
+    fn add_seconds(&self, seconds: i64) -> Option<Self> {
+        let seconds = self.seconds as i64 + seconds;
+        if seconds < 0 || seconds > u32::MAX as i64 {
+            return None;
+        }
+        return Some(Self::new(seconds as u32));
+    }
+
+    /// Parses `2023-01-01T00:00:00`, a fraction is truncated and a trailing `Z` is optional.
+    pub fn from_iso_str(s: &str) -> Self {
+        check_parse(Self::try_from_iso_str(s))
+    }
+
+    /// Like `from_iso_str`, but returns an error instead of aborting the action.
+    pub fn try_from_iso_str(s: &str) -> Result<Self, ParseError> {
+        let seconds = parse_iso(s)? / 1_000_000;
+        if seconds < 0 || seconds > u32::MAX as i64 {
+            return Err(ParseError::new(ParseErrorKind::OutOfRange, "time is out of the range of time_point_sec"));
+        }
+        return Ok(Self::new(seconds as u32));
+    }
+}
+
+impl Add<u32> for TimePointSec {
+    type Output = Self;
+    fn add(self, seconds: u32) -> Self {
+        let ret = self.seconds.checked_add(seconds);
+        check(ret.is_some(), "time_point_sec: out of range");
+        return Self::new(ret.unwrap());
+    }
+}
+
+impl Sub<u32> for TimePointSec {
+    type Output = Self;
+    fn sub(self, seconds: u32) -> Self {
+        let ret = self.seconds.checked_sub(seconds);
+        check(ret.is_some(), "time_point_sec: out of range");
+        return Self::new(ret.unwrap());
+    }
+}
+
+/// Whole seconds of the duration are added, the rest is truncated.
+impl Add<Microseconds> for TimePointSec {
+    type Output = Self;
+    fn add(self, d: Microseconds) -> Self {
+        let ret = self.checked_add(d);
+        check(ret.is_some(), "time_point_sec: out of range");
+        return ret.unwrap();
+    }
+}
+
+/// Whole seconds of the duration are subtracted, the rest is truncated.
+impl Sub<Microseconds> for TimePointSec {
+    type Output = Self;
+    fn sub(self, d: Microseconds) -> Self {
+        let ret = self.checked_sub(d);
+        check(ret.is_some(), "time_point_sec: out of range");
+        return ret.unwrap();
+    }
+}
+
+impl Sub for TimePointSec {
+    type Output = Microseconds;
+    fn sub(self, other: Self) -> Microseconds {
+        return Microseconds::seconds(self.seconds as i64 - other.seconds as i64);
+    }
+}
+
+impl AddAssign<u32> for TimePointSec {
+    fn add_assign(&mut self, seconds: u32) {
+        *self = *self + seconds;
+    }
+}
+
+impl SubAssign<u32> for TimePointSec {
+    fn sub_assign(&mut self, seconds: u32) {
+        *self = *self - seconds;
+    }
+}
+
+/// Truncates to whole seconds.
+impl From<TimePoint> for TimePointSec {
+    fn from(t: TimePoint) -> Self {
+        return Self::new(t.sec_since_epoch());
+    }
+}
+
+impl BlockTimeStampType {
+    ///
+    pub const fn new(slot: u32) -> Self {
+        Self { slot }
+    }
+
+    /// Returns the timestamp of the block slot which `t` falls in, aborts if `t` is before 2000-01-01.
+    pub fn from_time_point(t: TimePoint) -> Self {
+        let ret = Self::checked_from_time_point(t);
+        check(ret.is_some(), "block_timestamp_type: time is out of range");
+        return ret.unwrap();
+    }
+
+    /// Returns `None` if `t` is before 2000-01-01 or after the last block slot.
+    pub fn checked_from_time_point(t: TimePoint) -> Option<Self> {
+        return Self::from_millis((t.elapsed / 1_000) as i64);
+    }
+
+    fn from_millis(ms: i64) -> Option<Self> {
+        let slot = (ms - BLOCK_TIMESTAMP_EPOCH_MS).div_euclid(BLOCK_INTERVAL_MS);
+        if slot < 0 || slot > u32::MAX as i64 {
+            return None;
+        }
+        return Some(Self::new(slot as u32));
+    }
+
+    ///
+    pub fn to_time_point(&self) -> TimePoint {
+        let ms = self.slot as i64 * BLOCK_INTERVAL_MS + BLOCK_TIMESTAMP_EPOCH_MS;
+        return TimePoint::new(ms as u64 * 1_000);
+    }
+
+    ///
+    pub fn next(&self) -> Self {
+        check(self.slot < u32::MAX, "block timestamp overflow");
+        return Self::new(self.slot + 1);
+    }
+
+    /// Formats the time as `2023-01-01T00:00:00.000`, the form used in ABI JSON.
+    pub fn to_string(&self) -> String {
+        return self.to_time_point().to_string();
+    }
+
+    /// Parses `2023-01-01T00:00:00.000`, the time is rounded down to a block slot.
+    pub fn from_iso_str(s: &str) -> Self {
+        check_parse(Self::try_from_iso_str(s))
+    }
+
+    /// Like `from_iso_str`, but returns an error instead of aborting the action.
+    pub fn try_from_iso_str(s: &str) -> Result<Self, ParseError> {
+        match Self::from_millis(parse_iso(s)?.div_euclid(1_000)) {
+            Some(t) => {
+                return Ok(t);
+            }
+            None => {
+                return Err(ParseError::new(ParseErrorKind::OutOfRange, "time is out of the range of block_timestamp_type"));
+            }
+        }
+    }
+}
+
+impl From<TimePoint> for BlockTimeStampType {
+    fn from(t: TimePoint) -> Self {
+        return Self::from_time_point(t);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_conversions() {
+        let t = TimePoint::from_iso_str("2023-01-01T00:00:00.000");
+        assert_eq!(t.elapsed, 1_672_531_200_000_000);
+        assert_eq!(t.to_string(), "2023-01-01T00:00:00.000");
+        assert_eq!(TimePoint::from_iso_str("2024-02-29T23:59:59.5Z").to_string(), "2024-02-29T23:59:59.500");
+        assert_eq!(TimePoint::new(0).to_string(), "1970-01-01T00:00:00.000");
+        assert_eq!(TimePoint::try_from_iso_str("2023-02-29T00:00:00").unwrap_err().kind(), ParseErrorKind::OutOfRange);
+        assert_eq!(TimePoint::try_from_iso_str("2023-01-01 00:00:00").unwrap_err().kind(), ParseErrorKind::BadCharacter);
+        assert_eq!(TimePoint::try_from_iso_str("2023-1-1T00:00:00").unwrap_err().kind(), ParseErrorKind::BadLength);
+
+        let later = t + Microseconds::days(31) + Microseconds::milliseconds(250);
+        assert_eq!(later.to_string(), "2023-02-01T00:00:00.250");
+        assert_eq!(later - t, Microseconds::days(31) + Microseconds::milliseconds(250));
+        assert!(later > t);
+
+        let sec = TimePointSec::from(later);
+        assert_eq!(sec.to_string(), "2023-02-01T00:00:00");
+        assert_eq!(TimePointSec::from_iso_str("2023-02-01T00:00:00"), sec);
+        assert_eq!((sec + 60u32).to_string(), "2023-02-01T00:01:00");
+        assert_eq!((sec - Microseconds::hours(1)).to_string(), "2023-01-31T23:00:00");
+        assert_eq!(sec - TimePointSec::from(t), Microseconds::days(31));
+        assert_eq!(TimePoint::from(sec).to_string(), "2023-02-01T00:00:00.000");
+
+        let block = BlockTimeStampType::new(0);
+        assert_eq!(block.to_string(), "2000-01-01T00:00:00.000");
+        assert_eq!(block.next().to_string(), "2000-01-01T00:00:00.500");
+        let block = BlockTimeStampType::from_iso_str("2023-01-01T00:00:00.700");
+        assert_eq!(block.to_string(), "2023-01-01T00:00:00.500");
+        assert_eq!(BlockTimeStampType::from(block.to_time_point()), block);
+        assert!(block.next() > block);
+        assert_eq!(BlockTimeStampType::try_from_iso_str("1999-12-31T23:59:59.999").unwrap_err().kind(), ParseErrorKind::OutOfRange);
+
+        assert_eq!(TimePoint::new(5).checked_sub(Microseconds::new(6)), None);
+        assert_eq!(TimePoint::new(5).checked_add(Microseconds::new(-5)), Some(TimePoint::new(0)));
+        assert_eq!(TimePoint::new(u64::MAX).checked_add(Microseconds::new(1)), None);
+        assert_eq!(TimePoint::new(0).checked_sub(Microseconds::new(i64::MIN)), None);
+        assert_eq!(TimePointSec::new(u32::MAX).checked_add(Microseconds::seconds(1)), None);
+        assert_eq!(TimePointSec::new(1).checked_sub(Microseconds::seconds(2)), None);
+        assert_eq!(TimePointSec::new(1).checked_sub(Microseconds::new(i64::MAX)), None);
+        assert_eq!(TimePointSec::new(1).checked_sub(Microseconds::milliseconds(1500)), Some(TimePointSec::new(0)));
+        assert_eq!(BlockTimeStampType::checked_from_time_point(TimePoint::from_iso_str("1999-12-31T23:59:59.999")), None);
+        assert_eq!(BlockTimeStampType::checked_from_time_point(TimePoint::new(0)), None);
+        assert_eq!(BlockTimeStampType::checked_from_time_point(block.to_time_point()), Some(block));
+
+        assert_eq!(Microseconds::new(i64::MAX).checked_add(Microseconds::new(1)), None);
+        assert_eq!(Microseconds::new(i64::MIN).checked_sub(Microseconds::new(1)), None);
+        assert_eq!(Microseconds::new(i64::MAX / 2 + 1).checked_mul(2), None);
+        assert_eq!(Microseconds::seconds(3).checked_mul(-2), Some(Microseconds::seconds(-6)));
+        assert_eq!(Microseconds::days(2) - Microseconds::hours(48), Microseconds::new(0));
+    }
+}
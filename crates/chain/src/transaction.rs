@@ -5,6 +5,7 @@ use crate::serializer::{
 };
 
 use crate::structs::{
+    Checksum256,
     TimePointSec,
     Uint128,
 };
@@ -17,6 +18,8 @@ use crate::action::{
     Action,
 };
 
+use crate::crypto::sha256;
+
 use crate::{
     vec::Vec,
     Name,
@@ -123,10 +126,37 @@ impl Transaction {
         return &self.context_free_actions;
     }
 
+    pub fn add_context_free_action(&mut self, action: Action) {
+        self.context_free_actions.push(action);
+    }
+
     pub fn extension(&self) -> &Vec<TransactionExtension> {
         return &self.extension;
     }
 
+    pub fn add_extension(&mut self, ty: u16, data: Vec<u8>) {
+        self.extension.push(TransactionExtension{ty, data});
+    }
+
+    /// The transaction id, which is the sha256 hash of the packed transaction.
+    pub fn id(&self) -> Checksum256 {
+        return sha256(&Encoder::pack(self));
+    }
+
+    /// The digest signed by the transaction signatures:
+    /// sha256 of the chain id, the packed transaction and the digest of the context-free data,
+    /// which is 32 zero bytes if there is no context-free data.
+    pub fn sig_digest(&self, chain_id: &Checksum256, cfd: &[Vec<u8>]) -> Checksum256 {
+        let mut data = chain_id.data.to_vec();
+        data.extend(Encoder::pack(self));
+        if cfd.is_empty() {
+            data.extend([0u8; 32]);
+        } else {
+            data.extend(sha256(&Encoder::pack(&cfd.to_vec())).data);
+        }
+        return sha256(&data);
+    }
+
     pub fn send(&self, payer: Name, id: u128, replace_existing: bool) {
         let id = Uint128{lo: (id & u64::MAX as u128) as u64, hi: (id >> 64) as u64};
         send_deferred(&id, payer, &Encoder::pack(self), replace_existing.into());
@@ -180,8 +210,122 @@ impl Packer for Transaction {
     }
 }
 
+/// Builds a [`Transaction`] together with its context-free data.
+///
+/// Unless [`TransactionBuilder::ref_block`] is called, `build` fills the TaPoS fields
+/// from `tapos_block_num` and `tapos_block_prefix` of the current transaction.
+#[derive(Clone, Default)]
+pub struct TransactionBuilder {
+    trx: Transaction,
+    tapos: Option<(u16, u32)>,
+    context_free_data: Vec<Vec<u8>>,
+}
+
+impl TransactionBuilder {
+    ///
+    pub fn new(expiration: TimePointSec) -> Self {
+        let trx = Transaction { expiration, ..Default::default() };
+        Self { trx, tapos: None, context_free_data: Vec::new() }
+    }
+
+    /// Sets the TaPoS fields explicitly, `ref_block_num` is the lower 16 bits of the block number.
+    pub fn ref_block(mut self, ref_block_num: u16, ref_block_prefix: u32) -> Self {
+        self.tapos = Some((ref_block_num, ref_block_prefix));
+        self
+    }
+
+    ///
+    pub fn delay_sec(mut self, delay_sec: u32) -> Self {
+        self.trx.delay_sec = VarUint32::new(delay_sec);
+        self
+    }
+
+    ///
+    pub fn max_net_usage_words(mut self, words: u32) -> Self {
+        self.trx.max_net_usage_words = VarUint32::new(words);
+        self
+    }
+
+    ///
+    pub fn max_cpu_usage_ms(mut self, ms: u8) -> Self {
+        self.trx.max_cpu_usage_ms = ms;
+        self
+    }
+
+    ///
+    pub fn action(mut self, action: Action) -> Self {
+        self.trx.actions.push(action);
+        self
+    }
+
+    ///
+    pub fn context_free_action(mut self, action: Action) -> Self {
+        self.trx.context_free_actions.push(action);
+        self
+    }
+
+    /// Appends an entry of context-free data, which is not part of the packed transaction.
+    pub fn context_free_data(mut self, data: Vec<u8>) -> Self {
+        self.context_free_data.push(data);
+        self
+    }
+
+    ///
+    pub fn extension(mut self, ty: u16, data: Vec<u8>) -> Self {
+        self.trx.extension.push(TransactionExtension{ty, data});
+        self
+    }
+
+    ///
+    pub fn build(self) -> Transaction {
+        return self.into_parts().0;
+    }
+
+    /// Returns the transaction and its context-free data, as needed by `Transaction::sig_digest`.
+    pub fn into_parts(self) -> (Transaction, Vec<Vec<u8>>) {
+        let mut trx = self.trx;
+        let (ref_block_num, ref_block_prefix) = match self.tapos {
+            Some(tapos) => tapos,
+            None => (tapos_block_num() as u16, tapos_block_prefix()),
+        };
+        trx.ref_block_num = ref_block_num;
+        trx.ref_block_prefix = ref_block_prefix;
+        return (trx, self.context_free_data);
+    }
+}
+
 // bool
 // check_transaction_authorization( const transaction&                 trx,
 //                                  const std::set<permission_level>&  provided_permissions ,
 //                                  const std::set<public_key>&        provided_keys = std::set<public_key>()
 //                                )
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::PermissionLevel;
+
+    #[test]
+    fn test_transaction_builder() {
+        let data: Vec<u8> = vec![1, 2, 3];
+        let (trx, cfd) = TransactionBuilder::new(TimePointSec::new(1672531200))
+            .ref_block(1234, 0xdeadbeef)
+            .context_free_action(Action::new_ex(Name::new("hello"), Name::new("log"), Vec::new(), &data))
+            .action(Action::new(Name::new("eosio.token"), Name::new("transfer"), PermissionLevel::new(Name::new("alice"), Name::new("active")), &data))
+            .context_free_data(b"hello".to_vec())
+            .context_free_data(Vec::new())
+            .into_parts();
+        assert_eq!(trx.ref_block_num(), 1234);
+        assert_eq!(trx.ref_block_prefix(), 0xdeadbeef);
+        assert_eq!(trx.context_free_actions().len(), 1);
+
+        let id = Checksum256::from_hex("345cd62ce1408a3474adc4989ef084c897ba05d8b2c5151b96ac3e3ffc7df956");
+        assert_eq!(trx.id().data, id.data);
+
+        let chain_id = Checksum256::from_hex("aca376f206b8fc25a6ed44dbdc66547c36c6c33e3a119ffbeaef943642f0e906");
+        let digest = Checksum256::from_hex("a8c041f8a1987c6871da6085deea638da3d8395e94b744065213ed585bf13d0d");
+        assert_eq!(trx.sig_digest(&chain_id, &[]).data, digest.data);
+        let digest = Checksum256::from_hex("adf1b34bca7c666d821df4f2f334f26aed38c7f0f931c02d5423a9d728cd8f49");
+        assert_eq!(trx.sig_digest(&chain_id, &cfd).data, digest.data);
+    }
+}
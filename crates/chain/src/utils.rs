@@ -1,19 +1,117 @@
+use core::fmt;
+
 use crate::{
     check,
     vec::Vec,
 };
 
+/// The kind of a [`ParseError`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    /// The input contains a character that is not allowed at its position.
+    BadCharacter,
+    /// The input is empty, too short or too long.
+    BadLength,
+    /// The precision of an asset or symbol is out of range.
+    BadPrecision,
+    /// The amount of an asset is out of range.
+    BadAmount,
+    /// A date or time field is out of range.
+    OutOfRange,
+}
+
+/// Error returned by the `try_*` parsing functions, which return it instead of aborting the action.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    message: &'static str,
+}
+
+impl ParseError {
+    ///
+    pub fn new(kind: ParseErrorKind, message: &'static str) -> Self {
+        Self{kind, message}
+    }
+
+    ///
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// The message the aborting counterpart of the `try_*` function passes to `check`.
+    pub fn message(&self) -> &'static str {
+        self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Unwraps the result of a `try_*` function, aborts the action with the error message on failure.
+pub(crate) fn check_parse<T: Default>(ret: Result<T, ParseError>) -> T {
+    match ret {
+        Ok(value) => value,
+        Err(err) => {
+            check(false, err.message());
+            T::default()
+        }
+    }
+}
+
+pub fn try_decode_hex(s: &str) -> Result<Vec<u8>, ParseError> {
+    if s.len() % 2 != 0 {
+        return Err(ParseError::new(ParseErrorKind::BadLength, "decod_hex: bad hex string length"));
+    }
+    let hex_digit = |c: u8| (c as char).to_digit(16)
+        .ok_or(ParseError::new(ParseErrorKind::BadCharacter, "bad hex characters"));
+    s.as_bytes()
+        .chunks_exact(2)
+        .map(|c| Ok((hex_digit(c[0])? << 4 | hex_digit(c[1])?) as u8))
+        .collect()
+}
+
 pub fn decode_hex(s: &str) -> Vec<u8> {
-    check(s.len() % 2 == 0, "decod_hex: bad hex string length");
-    (0..s.len())
-        .step_by(2)
-        .map(|i| {
-            if let Ok(c) = u8::from_str_radix(&s[i..i + 2], 16) {
-                c
-            } else {
-                check(false, "bad hex characters");
-                0u8
-            }
-        })
-        .collect::<Vec<_>>()
-}
\ No newline at end of file
+    check_parse(try_decode_hex(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::name::Name;
+    use crate::asset::{Asset, Symbol, SymbolCode, Decimal};
+    use crate::structs::Checksum256;
+
+    #[test]
+    fn test_try_parse() {
+        assert_eq!(try_decode_hex("0aFf"), Ok(vec![0x0a, 0xff]));
+        assert_eq!(try_decode_hex("0a+f").err().unwrap().kind(), ParseErrorKind::BadCharacter);
+        assert_eq!(try_decode_hex("0af").err().unwrap().kind(), ParseErrorKind::BadLength);
+
+        assert!("alice".parse::<Name>() == Ok(Name::new("alice")));
+        assert_eq!(Name::try_from_str("Alice").err().unwrap().kind(), ParseErrorKind::BadCharacter);
+        assert_eq!(Name::try_from("aaaaaaaaaaaaaa").err().unwrap().kind(), ParseErrorKind::BadLength);
+
+        assert!(SymbolCode::try_from("EOS").ok().unwrap() == SymbolCode::new("EOS"));
+        assert_eq!(SymbolCode::try_new("EOSEOSE").err().unwrap().kind(), ParseErrorKind::BadLength);
+        assert!("4,EOS".parse::<Symbol>().ok().unwrap() == Symbol::new("EOS", 4));
+        assert_eq!(Symbol::try_new("EOS", 19).err().unwrap().kind(), ParseErrorKind::BadPrecision);
+        assert_eq!(Symbol::try_new("eos", 4).err().unwrap().kind(), ParseErrorKind::BadCharacter);
+
+        assert!("1.0000 EOS".parse::<Asset>().ok().unwrap() == Asset::new(10000, Symbol::new("EOS", 4)));
+        assert_eq!(Asset::try_from_string("1.0000000000000000000 EOS").err().unwrap().kind(), ParseErrorKind::BadPrecision);
+        assert_eq!(Asset::try_from_string("1.0000 eos").err().unwrap().kind(), ParseErrorKind::BadCharacter);
+        assert_eq!(Asset::try_from_string("1.0000").err().unwrap().kind(), ParseErrorKind::BadLength);
+        assert_eq!(Asset::try_from_string("4611686018427387904 EOS").err().unwrap().kind(), ParseErrorKind::BadAmount);
+        assert_eq!("1.5".parse::<Decimal>().ok().unwrap().value(), 15);
+
+        let hash = "2b1c1b5c0a4e33a3a8b4ebb0e3a7a0dca33ec2e7d1e47a8f81d1b0e4c2fa0f6e";
+        assert!(hash.parse::<Checksum256>().ok().unwrap() == Checksum256::from_hex(hash));
+        assert_eq!(Checksum256::try_from_hex(&hash[2..]).err().unwrap().message(), "Checksum256: bad hex string length");
+    }
+}
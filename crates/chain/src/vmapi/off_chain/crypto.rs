@@ -2,18 +2,28 @@ use crate::structs::*;
 
 use chaintester::{
     get_vm_api_client,
-	interfaces::{
-		TApplySyncClient,
-	}
 };
 
 use core::slice;
 
+use crate::vec::Vec;
+use crate::ripemd160 as ripemd;
+use crate::crypto_primitives::{
+    secp256k1,
+    secp256r1,
+    sha1,
+    sha2,
+};
+
+fn check_hash(actual: &[u8], expected: &[u8]) {
+    crate::vmapi::eosio::check(actual == expected, "hash mismatch");
+}
+
 ///
 pub fn assert_sha256( data: *const u8, length: u32, hash: *const Checksum256) {
     unsafe {
         let _data = slice::from_raw_parts(data, length as usize);
-        get_vm_api_client().assert_sha256(_data.into(), (*hash).data.into()).unwrap()
+        check_hash(&sha2::sha256(_data), &(*hash).data);
     }
 }
 
@@ -21,7 +31,7 @@ pub fn assert_sha256( data: *const u8, length: u32, hash: *const Checksum256) {
 pub fn assert_sha1( data: *const u8, length: u32, hash: *const Checksum160) {
     unsafe {
         let _data = slice::from_raw_parts(data, length as usize);
-        get_vm_api_client().assert_sha1(_data.into(), (*hash).data.into()).unwrap()
+        check_hash(&sha1::sha1(_data), &(*hash).data);
     }
 }
 
@@ -29,7 +39,7 @@ pub fn assert_sha1( data: *const u8, length: u32, hash: *const Checksum160) {
 pub fn assert_sha512(data: *const u8, length: u32, hash: *const Checksum512) {
     unsafe {
         let _data = slice::from_raw_parts(data, length as usize);
-        get_vm_api_client().assert_sha512(_data.into(), (*hash).data.into()).unwrap()
+        check_hash(&sha2::sha512(_data), &(*hash).data);
     }
 }
 
@@ -37,7 +47,7 @@ pub fn assert_sha512(data: *const u8, length: u32, hash: *const Checksum512) {
 pub fn assert_ripemd160(data: *const u8, length: u32, hash: *const Checksum160) {
     unsafe {
         let _data = slice::from_raw_parts(data, length as usize);
-        get_vm_api_client().assert_ripemd160(_data.into(), (*hash).data.into()).unwrap()
+        check_hash(&ripemd::ripemd160(_data), &(*hash).data);
     }    
 }
 
@@ -45,8 +55,7 @@ pub fn assert_ripemd160(data: *const u8, length: u32, hash: *const Checksum160)
 pub fn sha256( data: *const u8, length: u32, hash: *mut Checksum256 ) {
     unsafe {
         let _data = slice::from_raw_parts(data, length as usize);
-        let _hash = get_vm_api_client().sha256(_data.into()).unwrap();
-        crate::vmapi::eosio::memcpy((*hash).data.as_ptr() as *mut u8, _hash.as_ptr(), 32);
+        (*hash).data = sha2::sha256(_data);
     }
 }
 
@@ -54,8 +63,7 @@ pub fn sha256( data: *const u8, length: u32, hash: *mut Checksum256 ) {
 pub fn sha1(data: *const u8, length: u32, hash: *mut Checksum160) {
     unsafe {
         let _data = slice::from_raw_parts(data, length as usize);
-        let _hash = get_vm_api_client().sha1(_data.into()).unwrap();
-        crate::vmapi::eosio::memcpy((*hash).data.as_ptr() as *mut u8, _hash.as_ptr(), 20);
+        (*hash).data = sha1::sha1(_data);
     }
 }
 
@@ -63,8 +71,7 @@ pub fn sha1(data: *const u8, length: u32, hash: *mut Checksum160) {
 pub fn sha512(data: *const u8, length: u32, hash: *mut Checksum512 ) {
     unsafe {
         let _data = slice::from_raw_parts(data, length as usize);
-        let _hash = get_vm_api_client().sha512(_data.into()).unwrap();
-        crate::vmapi::eosio::memcpy((*hash).data.as_ptr() as *mut u8, _hash.as_ptr(), 64);
+        (*hash).data = sha2::sha512(_data);
     }
 }
 
@@ -72,11 +79,26 @@ pub fn sha512(data: *const u8, length: u32, hash: *mut Checksum512 ) {
 pub fn ripemd160(data: *const u8, length: u32, hash: *mut Checksum160 ) {
     unsafe {
         let _data = slice::from_raw_parts(data, length as usize);
-        let _hash = get_vm_api_client().ripemd160(_data.into()).unwrap();
-        crate::vmapi::eosio::memcpy((*hash).data.as_ptr() as *mut u8, _hash.as_ptr(), 20);
+        (*hash).data = ripemd::ripemd160(_data);
     }
 }
 
+/// Recovers the packed public key from a packed K1 or R1 signature,
+/// other signature types are left to the tester server.
+fn recover_packed_key(digest: &Checksum256, sig: &[u8]) -> Vec<u8> {
+    let key = match (sig.first(), <&[u8; 65]>::try_from(sig.get(1..).unwrap_or_default())) {
+        (Some(0), Ok(compact)) => secp256k1::recover_compressed(compact, &digest.data),
+        (Some(1), Ok(compact)) => secp256r1::recover_compressed(compact, &digest.data),
+        _ => {
+            return get_vm_api_client().recover_key(digest.data.into(), sig.into()).unwrap();
+        }
+    };
+    crate::vmapi::eosio::check(key.is_some(), "unable to reconstruct public key from signature");
+    let mut ret = vec![sig[0]];
+    ret.extend_from_slice(&key.unwrap());
+    return ret;
+}
+
 ///
 pub fn recover_key( digest: *const Checksum256 , sig: *const u8, siglen: usize, pubkey: *mut u8, publen: usize ) -> i32 {
     if publen != 34 {
@@ -85,7 +107,7 @@ pub fn recover_key( digest: *const Checksum256 , sig: *const u8, siglen: usize,
 
     unsafe {
         let _sig = slice::from_raw_parts(sig, siglen as usize);
-        let _pubkey = get_vm_api_client().recover_key((*digest).data.into(), _sig.into()).unwrap();
+        let _pubkey = recover_packed_key(&*digest, _sig);
         crate::vmapi::eosio::memcpy(pubkey, _pubkey.as_ptr(), _pubkey.len());
         _pubkey.len() as i32
     }
@@ -100,6 +122,7 @@ pub fn assert_recover_key(digest: *const Checksum256, sig: *const u8, siglen: us
     unsafe {
         let _sig = slice::from_raw_parts(sig, siglen as usize);
         let _pubkey = slice::from_raw_parts(pubkey, pubkey_len as usize);
-        get_vm_api_client().assert_recover_key((*digest).data.into(), _sig.into(), _pubkey.into()).unwrap();
+        let recovered = recover_packed_key(&*digest, _sig);
+        crate::vmapi::eosio::check(recovered == _pubkey, "Error expected key different than recovered key");
     }
 }
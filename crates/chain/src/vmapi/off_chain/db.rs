@@ -1,10 +1,7 @@
 use crate::structs::*;
 
 use chaintester::{
-    get_vm_api_client, 
-    interfaces::{
-        TApplySyncClient,
-    }
+    get_vm_api_client,
 };
 
 use core::slice;
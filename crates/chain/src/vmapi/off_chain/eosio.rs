@@ -30,9 +30,6 @@ use core::slice;
 
 use chaintester::{
     get_vm_api_client,
-	interfaces::{
-		TApplySyncClient,
-	}
 };
 
 ///
@@ -5,15 +5,20 @@ use crate::{
 
 use chaintester::{
     get_vm_api_client,
-	interfaces::{
-		TApplySyncClient,
-	}
 };
 
 use crate::name::{
 	Name,
 };
 
+use crate::crypto_primitives::{
+	BigNat,
+	blake2,
+	bn128,
+	keccak,
+	secp256k1,
+};
+
 // void set_action_return_value(const char *data, uint32_t data_size);
 pub fn set_action_return_value(data: Vec<u8>) {
     let ret = get_vm_api_client().set_action_return_value(data);
@@ -33,10 +38,106 @@ pub fn get_block_num() -> u32 {
 }
 
 // void sha3( const char* data, uint32_t data_len, char* hash, uint32_t hash_len, int32_t keccak );
+pub fn sha3(data: &[u8], hash: &mut [u8], keccak: bool) {
+    crate::vmapi::eosio::check(hash.len() == 32, "sha3: hash buffer must be 32 bytes");
+    let digest = if keccak {
+        keccak::keccak256(data)
+    } else {
+        keccak::sha3_256(data)
+    };
+    hash.copy_from_slice(&digest);
+}
+
 // int32_t blake2_f( uint32_t rounds, const char* state, uint32_t state_len, const char* msg, uint32_t msg_len, 
 //                 const char* t0_offset, uint32_t t0_len, const char* t1_offset, uint32_t t1_len, int32_t final, char* result, uint32_t result_len);
+pub fn blake2_f(rounds: u32, state: &[u8], msg: &[u8], t0_offset: &[u8], t1_offset: &[u8], final_block: bool, result: &mut [u8]) -> i32 {
+    let (Ok(state), Ok(msg), Ok(t0), Ok(t1)) = (state.try_into(), msg.try_into(), t0_offset.try_into(), t1_offset.try_into()) else {
+        return -1;
+    };
+    if result.len() != 64 {
+        return -1;
+    }
+    result.copy_from_slice(&blake2::blake2_f(rounds, state, msg, t0, t1, final_block));
+    return 0;
+}
+
 // int32_t k1_recover( const char* sig, uint32_t sig_len, const char* dig, uint32_t dig_len, char* pub, uint32_t pub_len);
+pub fn k1_recover(sig: &[u8], dig: &[u8], _pub: &mut [u8]) -> i32 {
+    let (Ok(sig), Ok(dig)) = (sig.try_into(), dig.try_into()) else {
+        return -1;
+    };
+    if _pub.len() != 65 {
+        return -1;
+    }
+    match secp256k1::recover(sig, dig) {
+        Some(key) => {
+            _pub.copy_from_slice(&key);
+            return 0;
+        }
+        None => {
+            return -1;
+        }
+    }
+}
+
 // int32_t alt_bn128_add( const char* op1, uint32_t op1_len, const char* op2, uint32_t op2_len, char* result, uint32_t result_len);
+pub fn alt_bn128_add(op1: &[u8], op2: &[u8], result: &mut [u8]) -> i32 {
+    let (Ok(op1), Ok(op2)) = (op1.try_into(), op2.try_into()) else {
+        return -1;
+    };
+    if result.len() != 64 {
+        return -1;
+    }
+    match bn128::add(op1, op2) {
+        Some(point) => {
+            result.copy_from_slice(&point);
+            return 0;
+        }
+        None => {
+            return -1;
+        }
+    }
+}
+
 // int32_t alt_bn128_mul( const char* g1, uint32_t g1_len, const char* scalar, uint32_t scalar_len, char* result, uint32_t result_len);
+pub fn alt_bn128_mul(g1: &[u8], scalar: &[u8], result: &mut [u8]) -> i32 {
+    let (Ok(g1), Ok(scalar)) = (g1.try_into(), scalar.try_into()) else {
+        return -1;
+    };
+    if result.len() != 64 {
+        return -1;
+    }
+    match bn128::mul(g1, scalar) {
+        Some(point) => {
+            result.copy_from_slice(&point);
+            return 0;
+        }
+        None => {
+            return -1;
+        }
+    }
+}
+
 // int32_t alt_bn128_pair( const char* pairs, uint32_t pairs_len);
+pub fn alt_bn128_pair(pairs: &[u8]) -> i32 {
+    match bn128::pairing_check(pairs) {
+        Some(true) => 0,
+        Some(false) => 1,
+        None => -1,
+    }
+}
+
 // int32_t mod_exp( const char* base, uint32_t base_len, const char* exp, uint32_t exp_len, const char* mod, uint32_t mod_len, char* result, uint32_t result_len);
+pub fn mod_exp(base: &[u8], exp: &[u8], _mod: &[u8], result: &mut [u8]) -> i32 {
+    if _mod.is_empty() || result.len() != _mod.len() {
+        return -1;
+    }
+    let modulus = BigNat::from_be_bytes(_mod);
+    if modulus.is_zero() {
+        result.fill(0);
+        return 0;
+    }
+    let value = BigNat::from_be_bytes(base).pow_mod(&BigNat::from_be_bytes(exp), &modulus);
+    result.copy_from_slice(&value.to_be_bytes(result.len()).unwrap());
+    return 0;
+}
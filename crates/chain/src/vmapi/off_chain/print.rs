@@ -1,5 +1,4 @@
 use std::ffi::CStr;
-use chaintester::interfaces::TApplySyncClient;
 
 use chaintester::{
     get_vm_api_client,
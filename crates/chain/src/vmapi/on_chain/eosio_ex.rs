@@ -16,13 +16,23 @@ mod intrinsics {
         //uint32_t get_block_num();
         pub fn get_block_num() -> u32;
         // void sha3( const char* data, uint32_t data_len, char* hash, uint32_t hash_len, int32_t keccak );
+        pub fn sha3(data: *const u8, data_len: u32, hash: *mut u8, hash_len: u32, keccak: i32);
         // int32_t blake2_f( uint32_t rounds, const char* state, uint32_t state_len, const char* msg, uint32_t msg_len, 
         //                 const char* t0_offset, uint32_t t0_len, const char* t1_offset, uint32_t t1_len, int32_t final, char* result, uint32_t result_len);
+        pub fn blake2_f(rounds: u32, state: *const u8, state_len: u32, msg: *const u8, msg_len: u32,
+                        t0_offset: *const u8, t0_len: u32, t1_offset: *const u8, t1_len: u32,
+                        final_block: i32, result: *mut u8, result_len: u32) -> i32;
         // int32_t k1_recover( const char* sig, uint32_t sig_len, const char* dig, uint32_t dig_len, char* pub, uint32_t pub_len);
+        pub fn k1_recover(sig: *const u8, sig_len: u32, dig: *const u8, dig_len: u32, _pub: *mut u8, pub_len: u32) -> i32;
         // int32_t alt_bn128_add( const char* op1, uint32_t op1_len, const char* op2, uint32_t op2_len, char* result, uint32_t result_len);
+        pub fn alt_bn128_add(op1: *const u8, op1_len: u32, op2: *const u8, op2_len: u32, result: *mut u8, result_len: u32) -> i32;
         // int32_t alt_bn128_mul( const char* g1, uint32_t g1_len, const char* scalar, uint32_t scalar_len, char* result, uint32_t result_len);
+        pub fn alt_bn128_mul(g1: *const u8, g1_len: u32, scalar: *const u8, scalar_len: u32, result: *mut u8, result_len: u32) -> i32;
         // int32_t alt_bn128_pair( const char* pairs, uint32_t pairs_len);
+        pub fn alt_bn128_pair(pairs: *const u8, pairs_len: u32) -> i32;
         // int32_t mod_exp( const char* base, uint32_t base_len, const char* exp, uint32_t exp_len, const char* mod, uint32_t mod_len, char* result, uint32_t result_len);
+        pub fn mod_exp(base: *const u8, base_len: u32, exp: *const u8, exp_len: u32,
+                       _mod: *const u8, mod_len: u32, result: *mut u8, result_len: u32) -> i32;
     }
 }
 
@@ -51,10 +61,54 @@ pub fn get_block_num() -> u32 {
 }
 
 // void sha3( const char* data, uint32_t data_len, char* hash, uint32_t hash_len, int32_t keccak );
+pub fn sha3(data: &[u8], hash: &mut [u8], keccak: bool) {
+    unsafe {
+        intrinsics::sha3(data.as_ptr(), data.len() as u32, hash.as_mut_ptr(), hash.len() as u32, keccak as i32);
+    }
+}
+
 // int32_t blake2_f( uint32_t rounds, const char* state, uint32_t state_len, const char* msg, uint32_t msg_len, 
 //                 const char* t0_offset, uint32_t t0_len, const char* t1_offset, uint32_t t1_len, int32_t final, char* result, uint32_t result_len);
+pub fn blake2_f(rounds: u32, state: &[u8], msg: &[u8], t0_offset: &[u8], t1_offset: &[u8], final_block: bool, result: &mut [u8]) -> i32 {
+    unsafe {
+        return intrinsics::blake2_f(rounds, state.as_ptr(), state.len() as u32, msg.as_ptr(), msg.len() as u32,
+                                    t0_offset.as_ptr(), t0_offset.len() as u32, t1_offset.as_ptr(), t1_offset.len() as u32,
+                                    final_block as i32, result.as_mut_ptr(), result.len() as u32);
+    }
+}
+
 // int32_t k1_recover( const char* sig, uint32_t sig_len, const char* dig, uint32_t dig_len, char* pub, uint32_t pub_len);
+pub fn k1_recover(sig: &[u8], dig: &[u8], _pub: &mut [u8]) -> i32 {
+    unsafe {
+        return intrinsics::k1_recover(sig.as_ptr(), sig.len() as u32, dig.as_ptr(), dig.len() as u32, _pub.as_mut_ptr(), _pub.len() as u32);
+    }
+}
+
 // int32_t alt_bn128_add( const char* op1, uint32_t op1_len, const char* op2, uint32_t op2_len, char* result, uint32_t result_len);
+pub fn alt_bn128_add(op1: &[u8], op2: &[u8], result: &mut [u8]) -> i32 {
+    unsafe {
+        return intrinsics::alt_bn128_add(op1.as_ptr(), op1.len() as u32, op2.as_ptr(), op2.len() as u32, result.as_mut_ptr(), result.len() as u32);
+    }
+}
+
 // int32_t alt_bn128_mul( const char* g1, uint32_t g1_len, const char* scalar, uint32_t scalar_len, char* result, uint32_t result_len);
+pub fn alt_bn128_mul(g1: &[u8], scalar: &[u8], result: &mut [u8]) -> i32 {
+    unsafe {
+        return intrinsics::alt_bn128_mul(g1.as_ptr(), g1.len() as u32, scalar.as_ptr(), scalar.len() as u32, result.as_mut_ptr(), result.len() as u32);
+    }
+}
+
 // int32_t alt_bn128_pair( const char* pairs, uint32_t pairs_len);
+pub fn alt_bn128_pair(pairs: &[u8]) -> i32 {
+    unsafe {
+        return intrinsics::alt_bn128_pair(pairs.as_ptr(), pairs.len() as u32);
+    }
+}
+
 // int32_t mod_exp( const char* base, uint32_t base_len, const char* exp, uint32_t exp_len, const char* mod, uint32_t mod_len, char* result, uint32_t result_len);
+pub fn mod_exp(base: &[u8], exp: &[u8], _mod: &[u8], result: &mut [u8]) -> i32 {
+    unsafe {
+        return intrinsics::mod_exp(base.as_ptr(), base.len() as u32, exp.as_ptr(), exp.len() as u32,
+                                   _mod.as_ptr(), _mod.len() as u32, result.as_mut_ptr(), result.len() as u32);
+    }
+}
@@ -16,10 +16,13 @@ use crate::interfaces::{
     IPCChainTesterSyncClient,
     TIPCChainTesterSyncClient,
     ApplySyncClient,
+    TApplySyncClient,
     Action,
     ActionArguments,
 };
 
+use crate::mock::MockChain;
+
 type ClientInputProtocol = TBinaryInputProtocol<TBufferedReadTransport<ReadHalf<TTcpChannel>>>;
 type ClientOutputProtocol = TBinaryOutputProtocol<TBufferedWriteTransport<WriteHalf<TTcpChannel>>>;
 
@@ -29,7 +32,8 @@ use std::convert::{From, Into, TryInto};
 use lazy_static::lazy_static; // 1.4.0
 use std::sync::{
     Mutex,
-    MutexGuard
+    MutexGuard,
+    PoisonError,
 };
 
 
@@ -118,6 +122,12 @@ impl fmt::Debug for TransactionReturn {
 
 pub type Result<T> = core::result::Result<T, ChainTesterError>;
 
+/// Returns the packed return value of the first action in a `push_action` result.
+pub fn get_action_return_value(tx: &Value) -> Option<Vec<u8>> {
+    let hex_data = tx["action_traces"][0]["return_value_hex_data"].as_str()?;
+    hex::decode(hex_data).ok()
+}
+
 pub struct GetTableRowsPrams<'a> {
     pub json: bool,
     pub code: &'a str,
@@ -152,8 +162,13 @@ impl<'a> Default for GetTableRowsPrams<'a> {
     }
 }
 
+pub(crate) enum VMAPIBackend {
+    Thrift(ApplySyncClient<ClientInputProtocol, ClientOutputProtocol>),
+    Mock(MockChain),
+}
+
 pub struct VMAPIClient {
-    vm_api_client: Option<ApplySyncClient<ClientInputProtocol, ClientOutputProtocol>>,
+    vm_api_client: Option<VMAPIBackend>,
     in_apply: bool,
 }
 
@@ -173,14 +188,16 @@ lazy_static! {
     static ref TEST_MUTEX: Mutex<i32> = Mutex::new(0);
 }
 
-type FnApply = fn(u64, u64, u64);
+pub type FnApply = fn(u64, u64, u64);
 
 lazy_static! {
     static ref CHAIN_TESTER_APPLYS: Mutex<HashMap<i32, HashMap<String, FnApply>>> = Mutex::new(HashMap::new());
 }
 
+/// Serializes the tests which use the global clients, `MockTester` holds it while it is alive.
+/// A test which failed while holding the lock does not fail the tests after it.
 pub fn get_test_mutex() -> MutexGuard<'static, i32> {
-    let ret = TEST_MUTEX.lock().unwrap();
+    let ret = TEST_MUTEX.lock().unwrap_or_else(PoisonError::into_inner);
     return ret;
 }
 
@@ -232,13 +249,20 @@ pub fn init_vm_api_client() {
 }
 
 pub fn get_vm_api_client() -> MutexGuard<'static, VMAPIClient> {
-    let mut ret = VM_API_CLIENT.lock().unwrap();
+    // a failed check in the mock chain unwinds while the client is locked
+    let mut ret = VM_API_CLIENT.lock().unwrap_or_else(PoisonError::into_inner);
     if ret.vm_api_client.is_none() {
         ret.init();
     }
     return ret;
 }
 
+/// Replaces the backend of the VM API client and returns the previous one.
+pub(crate) fn swap_vm_api_backend(backend: Option<VMAPIBackend>) -> Option<VMAPIBackend> {
+    let mut client = VM_API_CLIENT.lock().unwrap_or_else(PoisonError::into_inner);
+    return std::mem::replace(&mut client.vm_api_client, backend);
+}
+
 pub fn close_vm_api_client() {
     let mut ret = VM_API_CLIENT.lock().unwrap();
     ret.close();
@@ -251,10 +275,26 @@ impl VMAPIClient {
 
     pub fn init(&mut self) {
         if self.vm_api_client.is_none() {
+            if crate::get_debugger_config().use_mock_chain {
+                self.vm_api_client = Some(VMAPIBackend::Mock(MockChain::new()));
+                return;
+            }
             let host = crate::get_debugger_config().vm_api_server_address.clone();
             let port = crate::get_debugger_config().vm_api_server_port;
             let client = new_vm_api_client(&host, port).unwrap();
-            self.vm_api_client = Some(client);
+            self.vm_api_client = Some(VMAPIBackend::Thrift(client));
+        }
+    }
+
+    /// Replaces the current backend with an empty `MockChain`.
+    pub fn reset_mock_chain(&mut self) {
+        self.vm_api_client = Some(VMAPIBackend::Mock(MockChain::new()));
+    }
+
+    pub fn mock_chain(&mut self) -> Option<&mut MockChain> {
+        match self.vm_api_client.as_mut() {
+            Some(VMAPIBackend::Mock(chain)) => Some(chain),
+            _ => None,
         }
     }
 
@@ -278,11 +318,14 @@ impl VMAPIClient {
 }
 
 impl Deref for VMAPIClient {
-    type Target = ApplySyncClient<ClientInputProtocol, ClientOutputProtocol>;
+    type Target = dyn TApplySyncClient + Send;
 
-    fn deref(&self) -> &ApplySyncClient<ClientInputProtocol, ClientOutputProtocol>
+    fn deref(&self) -> &Self::Target
     {
-        self.vm_api_client.as_ref().unwrap()
+        match self.vm_api_client.as_ref().unwrap() {
+            VMAPIBackend::Thrift(client) => client,
+            VMAPIBackend::Mock(chain) => chain,
+        }
     }
 }
 
@@ -291,8 +334,10 @@ impl DerefMut for VMAPIClient {
         if !self.is_in_apply() {
             panic!("error: vm api function has been called out of apply context!");
         }
-        let client = self.vm_api_client.as_mut().unwrap();
-        return client;
+        match self.vm_api_client.as_mut().unwrap() {
+            VMAPIBackend::Thrift(client) => client,
+            VMAPIBackend::Mock(chain) => chain,
+        }
     }
 }
 // 
@@ -380,6 +425,23 @@ pub struct ChainTester {
     id: i32,
 }
 
+/// Operations shared by `ChainTester` and `MockTester`,
+/// used by the typed clients that `#[chain::contract]` generates.
+pub trait Tester {
+    fn push_action(&mut self, account: &str, action: &str, arguments: ActionArguments, permissions: &str) -> Result<Value>;
+    fn get_table_rows_ex(&mut self, params: &GetTableRowsPrams) -> Result<Value>;
+}
+
+impl Tester for ChainTester {
+    fn push_action(&mut self, account: &str, action: &str, arguments: ActionArguments, permissions: &str) -> Result<Value> {
+        ChainTester::push_action(self, account, action, arguments, permissions)
+    }
+
+    fn get_table_rows_ex(&mut self, params: &GetTableRowsPrams) -> Result<Value> {
+        ChainTester::get_table_rows_ex(self, params)
+    }
+}
+
 fn parse_ret(ret: &thrift::Result<String>) -> Result<Value> {
     match ret {
         Ok(ret) => {
@@ -739,6 +801,24 @@ pub fn new_vm_api_client(
     Ok(ApplySyncClient::new(i_prot, o_prot))
 }
 
+///
+pub fn s2n(s: &str) -> u64 {
+    let mut value = 0u64;
+    for (i, c) in s.bytes().enumerate().take(13) {
+        let c = match c {
+            b'a'..=b'z' => c - b'a' + 6,
+            b'1'..=b'5' => c - b'1' + 1,
+            _ => 0,
+        } as u64;
+        if i < 12 {
+            value |= (c & 0x1f) << (64 - 5 * (i + 1));
+        } else {
+            value |= c & 0x0f;
+        }
+    }
+    value
+}
+
 ///
 pub fn n2s(value: u64) -> String {
 	let charmap = ".12345abcdefghijklmnopqrstuvwxyz".as_bytes();
@@ -31,10 +31,18 @@ pub use client::{
     get_globals,
     get_test_mutex,
     n2s,
+    s2n,
+    get_action_return_value,
     GetTableRowsPrams,
+    Tester,
 };
 
 pub mod server;
+pub mod mock;
+pub use mock::{
+    MockChain,
+    MockTester,
+};
 
 
 pub struct DebuggerConfig {
@@ -44,6 +52,8 @@ pub struct DebuggerConfig {
     pub vm_api_server_port: u16,
     pub apply_request_server_address: String,
     pub apply_request_server_port: u16,
+    /// run the VM API on `MockChain` instead of connecting to the debugger
+    pub use_mock_chain: bool,
 }
 
 impl DebuggerConfig {
@@ -55,6 +65,7 @@ impl DebuggerConfig {
             vm_api_server_port: 9092,
             apply_request_server_address: "127.0.0.1".into(), 
             apply_request_server_port: 9091,
+            use_mock_chain: cfg!(feature = "mock") || std::env::var("CHAINTESTER_MOCK").is_ok(),
         }
     }
 }
@@ -0,0 +1,1624 @@
+//! In-process chain used to run native contracts without the ipyeos debugger.
+//!
+//! `MockChain` implements the whole `TApplySyncClient` interface in memory,
+//! so the `off_chain` VM API of `rust-chain` works unchanged when the mock
+//! backend is selected. `MockTester` mirrors the `ChainTester` API and drives
+//! `contract_apply` functions directly from `#[test]` functions.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::Bound::{Excluded, Included, Unbounded};
+use std::panic;
+use std::sync::MutexGuard;
+
+use serde_json::{json, Value};
+
+use thrift::{ApplicationError, ApplicationErrorKind};
+
+use crate::client::{get_test_mutex, get_vm_api_client, swap_vm_api_backend, ChainTesterError, FnApply, GetTableRowsPrams, Result, Tester, VMAPIBackend};
+use crate::interfaces::{
+    ActionArguments,
+    FindPrimaryReturn,
+    FindSecondaryReturn,
+    GetResourceLimitsReturn,
+    LowerBoundUpperBoundReturn,
+    NextPreviousReturn,
+    TApplySyncClient,
+    Uint64,
+};
+use crate::{n2s, s2n};
+
+/// 2018-06-01T12:00:00, the genesis time used by the test chains.
+const GENESIS_TIME: u64 = 1_527_854_400_000_000;
+const BLOCK_INTERVAL: u64 = 500_000;
+const MAX_INLINE_ACTION_DEPTH: usize = 4;
+
+type TableKey = (u64, u64, u64);
+
+// Billable sizes of nodeos, kept in sync with `rust_chain::db::ram`.
+const OVERHEAD_PER_ROW_PER_INDEX: i64 = 32;
+const TABLE_OVERHEAD: i64 = 44 + OVERHEAD_PER_ROW_PER_INDEX * 2;
+const ROW_OVERHEAD: i64 = 32 + 8 + 4 + OVERHEAD_PER_ROW_PER_INDEX * 2;
+const SECONDARY_ROW_OVERHEAD: i64 = 24 + OVERHEAD_PER_ROW_PER_INDEX * 3;
+
+#[derive(Clone, Copy)]
+enum IdxKind {
+    Idx64 = 0,
+    Idx128 = 1,
+    Idx256 = 2,
+    IdxDouble = 3,
+    IdxLongDouble = 4,
+}
+
+impl IdxKind {
+    fn size(self) -> usize {
+        match self {
+            IdxKind::Idx64 | IdxKind::IdxDouble => 8,
+            IdxKind::Idx128 | IdxKind::IdxLongDouble => 16,
+            IdxKind::Idx256 => 32,
+        }
+    }
+
+    /// Converts a little endian secondary value into bytes that sort the same way as the value.
+    fn sort_key(self, raw: &[u8]) -> Vec<u8> {
+        match self {
+            IdxKind::Idx64 => {
+                u64::from_le_bytes(raw.try_into().unwrap()).to_be_bytes().to_vec()
+            }
+            IdxKind::Idx128 => {
+                u128::from_le_bytes(raw.try_into().unwrap()).to_be_bytes().to_vec()
+            }
+            IdxKind::Idx256 => {
+                let mut key = u128::from_le_bytes(raw[..16].try_into().unwrap()).to_be_bytes().to_vec();
+                key.extend(u128::from_le_bytes(raw[16..].try_into().unwrap()).to_be_bytes());
+                key
+            }
+            IdxKind::IdxDouble => {
+                let bits = u64::from_le_bytes(raw.try_into().unwrap());
+                let bits = if bits >> 63 == 1 { !bits } else { bits | 1 << 63 };
+                bits.to_be_bytes().to_vec()
+            }
+            IdxKind::IdxLongDouble => {
+                let bits = u128::from_le_bytes(raw.try_into().unwrap());
+                let bits = if bits >> 127 == 1 { !bits } else { bits | 1 << 127 };
+                bits.to_be_bytes().to_vec()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct PrimaryTable {
+    payer: u64,
+    rows: BTreeMap<u64, (u64, Vec<u8>)>,
+}
+
+#[derive(Clone)]
+struct SecondaryRow {
+    payer: u64,
+    raw: Vec<u8>,
+    key: Vec<u8>,
+}
+
+#[derive(Clone, Default)]
+struct SecondaryTable {
+    payer: u64,
+    keys: BTreeSet<(Vec<u8>, u64)>,
+    rows: HashMap<u64, SecondaryRow>,
+}
+
+#[derive(Clone)]
+struct Database {
+    primary: BTreeMap<TableKey, PrimaryTable>,
+    secondary: Vec<BTreeMap<TableKey, SecondaryTable>>,
+}
+
+impl Database {
+    fn new() -> Self {
+        Self {
+            primary: BTreeMap::new(),
+            secondary: vec![BTreeMap::new(); 5],
+        }
+    }
+
+    /// Returns the bytes of RAM used by the tables and rows billed to each payer.
+    fn ram_usage(&self) -> BTreeMap<u64, i64> {
+        let mut usage = BTreeMap::new();
+        for t in self.primary.values() {
+            *usage.entry(t.payer).or_default() += TABLE_OVERHEAD;
+            for (payer, data) in t.rows.values() {
+                *usage.entry(*payer).or_default() += data.len() as i64 + ROW_OVERHEAD;
+            }
+        }
+        for t in self.secondary.iter().flat_map(|tables| tables.values()) {
+            *usage.entry(t.payer).or_default() += TABLE_OVERHEAD;
+            for row in t.rows.values() {
+                *usage.entry(row.payer).or_default() += row.raw.len() as i64 + SECONDARY_ROW_OVERHEAD;
+            }
+        }
+        usage
+    }
+}
+
+/// Iterators handed out to a contract during one apply, like the chain's keyval cache.
+/// End iterators are encoded as `-(table_index + 2)`, `-1` means no table.
+#[derive(Default)]
+struct IteratorCache {
+    tables: Vec<TableKey>,
+    iterators: Vec<Option<(usize, u64)>>,
+}
+
+impl IteratorCache {
+    fn table_index(&mut self, table: TableKey) -> usize {
+        match self.tables.iter().position(|t| *t == table) {
+            Some(index) => index,
+            None => {
+                self.tables.push(table);
+                self.tables.len() - 1
+            }
+        }
+    }
+
+    fn end_iterator(&mut self, table: TableKey) -> i32 {
+        -(self.table_index(table) as i32) - 2
+    }
+
+    fn add(&mut self, table: TableKey, primary: u64) -> i32 {
+        let index = self.table_index(table);
+        if let Some(it) = self.iterators.iter().position(|x| *x == Some((index, primary))) {
+            return it as i32;
+        }
+        self.iterators.push(Some((index, primary)));
+        (self.iterators.len() - 1) as i32
+    }
+
+    fn get(&self, it: i32) -> Option<(TableKey, u64)> {
+        if it < 0 {
+            return None;
+        }
+        self.iterators.get(it as usize).copied().flatten().map(|(index, primary)| (self.tables[index], primary))
+    }
+
+    fn end_table(&self, it: i32) -> Option<TableKey> {
+        if it >= -1 {
+            return None;
+        }
+        self.tables.get((-it - 2) as usize).copied()
+    }
+
+    fn remove(&mut self, it: i32) {
+        if let Some(x) = self.iterators.get_mut(it as usize) {
+            *x = None;
+        }
+    }
+}
+
+/// An action as it is serialized by `rust_chain::Action`.
+#[derive(Clone, Debug, Default)]
+pub struct MockAction {
+    pub account: u64,
+    pub name: u64,
+    pub authorization: Vec<(u64, u64)>,
+    pub data: Vec<u8>,
+}
+
+impl MockAction {
+    fn unpack(data: &[u8]) -> Option<Self> {
+        let mut pos = 0usize;
+        let account = read_u64(data, &mut pos)?;
+        let name = read_u64(data, &mut pos)?;
+        let count = read_varuint32(data, &mut pos)?;
+        let mut authorization = Vec::new();
+        for _ in 0..count {
+            let actor = read_u64(data, &mut pos)?;
+            let permission = read_u64(data, &mut pos)?;
+            authorization.push((actor, permission));
+        }
+        let size = read_varuint32(data, &mut pos)? as usize;
+        let action_data = data.get(pos..pos + size)?.to_vec();
+        Some(Self { account, name, authorization, data: action_data })
+    }
+
+    fn to_json(&self) -> Value {
+        let authorization: Vec<Value> = self.authorization
+            .iter()
+            .map(|(actor, permission)| json!({"actor": n2s(*actor), "permission": n2s(*permission)}))
+            .collect();
+        json!({
+            "account": n2s(self.account),
+            "name": n2s(self.name),
+            "authorization": authorization,
+            "hex_data": hex::encode(&self.data),
+        })
+    }
+}
+
+fn read_u64(data: &[u8], pos: &mut usize) -> Option<u64> {
+    let value = u64::from_le_bytes(data.get(*pos..*pos + 8)?.try_into().ok()?);
+    *pos += 8;
+    Some(value)
+}
+
+fn read_varuint32(data: &[u8], pos: &mut usize) -> Option<u32> {
+    let mut value = 0u32;
+    let mut shift = 0;
+    loop {
+        let b = *data.get(*pos)?;
+        *pos += 1;
+        value |= ((b & 0x7f) as u32) << shift;
+        if b & 0x80 == 0 {
+            return Some(value);
+        }
+        shift += 7;
+        if shift >= 35 {
+            return None;
+        }
+    }
+}
+
+#[derive(Default)]
+struct ApplyContext {
+    receiver: u64,
+    action: MockAction,
+    sender: u64,
+    primary_iterators: IteratorCache,
+    secondary_iterators: [IteratorCache; 5],
+    recipients: Vec<u64>,
+    inline_actions: Vec<MockAction>,
+    console: String,
+    return_value: Vec<u8>,
+    error: Option<String>,
+    exited: bool,
+}
+
+/// In-memory implementation of the VM API.
+pub struct MockChain {
+    db: Database,
+    accounts: BTreeMap<u64, u64>,
+    privileged: HashSet<u64>,
+    resource_limits: HashMap<u64, (i64, i64, i64)>,
+    applys: HashMap<u64, FnApply>,
+    block_num: u32,
+    block_time: u64,
+    context: ApplyContext,
+}
+
+impl Default for MockChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockChain {
+    pub fn new() -> Self {
+        let mut chain = Self {
+            db: Database::new(),
+            accounts: BTreeMap::new(),
+            privileged: HashSet::new(),
+            resource_limits: HashMap::new(),
+            applys: HashMap::new(),
+            block_num: 1,
+            block_time: GENESIS_TIME,
+            context: ApplyContext::default(),
+        };
+        chain.create_account(s2n("eosio"));
+        chain.privileged.insert(s2n("eosio"));
+        chain
+    }
+
+    pub fn create_account(&mut self, account: u64) {
+        let block_time = self.block_time;
+        self.accounts.entry(account).or_insert(block_time);
+    }
+
+    pub fn set_native_apply(&mut self, contract: u64, apply: Option<FnApply>) {
+        match apply {
+            Some(apply) => {
+                self.create_account(contract);
+                self.applys.insert(contract, apply);
+            }
+            None => {
+                self.applys.remove(&contract);
+            }
+        }
+    }
+
+    pub fn produce_block(&mut self, next_block_skip_seconds: i64) {
+        self.block_num += 1;
+        self.block_time += BLOCK_INTERVAL + next_block_skip_seconds as u64 * 1_000_000;
+    }
+
+    pub fn head_block_num(&self) -> u32 {
+        self.block_num
+    }
+
+    /// Microseconds since epoch of the head block.
+    pub fn head_block_time(&self) -> u64 {
+        self.block_time
+    }
+
+    /// Returns `(payer, data)` of every row in a table with a primary key in `[lower_bound, upper_bound]`.
+    pub fn get_table_rows(&self, code: u64, scope: u64, table: u64, lower_bound: u64, upper_bound: u64) -> Vec<(u64, u64, Vec<u8>)> {
+        match self.db.primary.get(&(code, scope, table)) {
+            Some(t) => t.rows
+                .range(lower_bound..=upper_bound)
+                .map(|(primary, (payer, data))| (*primary, *payer, data.clone()))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns `(primary, secondary, payer, data)` of every row with a key of the secondary index `index`
+    /// (0 for the first secondary index) in `[lower_bound, upper_bound]`, ordered by the secondary key.
+    /// Secondary keys are little endian values as stored by the contract.
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    fn get_table_rows_by_secondary(&self, code: u64, scope: u64, table: u64, index: usize, kind: IdxKind, lower_bound: Option<&[u8]>, upper_bound: Option<&[u8]>) -> Vec<(u64, Vec<u8>, u64, Vec<u8>)> {
+        let idx_table = (code, scope, (table & 0xffff_ffff_ffff_fff0) + index as u64);
+        let (t, primary) = match (self.db.secondary[kind as usize].get(&idx_table), self.db.primary.get(&(code, scope, table))) {
+            (Some(t), Some(primary)) => (t, primary),
+            _ => return Vec::new(),
+        };
+        let lower = match lower_bound {
+            Some(raw) => Included((kind.sort_key(raw), 0)),
+            None => Unbounded,
+        };
+        let upper = match upper_bound {
+            Some(raw) => Included((kind.sort_key(raw), u64::MAX)),
+            None => Unbounded,
+        };
+        t.keys
+            .range((lower, upper))
+            .filter_map(|(_, id)| {
+                let row = &t.rows[id];
+                primary.rows.get(id).map(|(payer, data)| (*id, row.raw.clone(), *payer, data.clone()))
+            })
+            .collect()
+    }
+
+    fn begin_apply(&mut self, receiver: u64, action: &MockAction, sender: u64) -> Option<FnApply> {
+        self.context = ApplyContext {
+            receiver,
+            action: action.clone(),
+            sender,
+            ..Default::default()
+        };
+        self.applys.get(&receiver).copied()
+    }
+
+    fn end_apply(&mut self) -> ApplyContext {
+        std::mem::take(&mut self.context)
+    }
+
+    fn fail<T>(&mut self, msg: &str) -> thrift::Result<T> {
+        if self.context.error.is_none() {
+            self.context.error = Some(msg.into());
+        }
+        Err(thrift::Error::Application(ApplicationError::new(ApplicationErrorKind::Unknown, msg.to_owned())))
+    }
+
+    fn unsupported<T>(&mut self, api: &str) -> thrift::Result<T> {
+        self.fail(&format!("{} is not supported by the mock chain", api))
+    }
+
+    fn has_auth_ex(&self, account: u64, permission: Option<u64>) -> bool {
+        self.context.action.authorization.iter().any(|(actor, perm)| {
+            *actor == account && (permission.is_none() || permission == Some(*perm))
+        })
+    }
+
+    fn check_code(&mut self, table: TableKey, api: &str) -> thrift::Result<()> {
+        if table.0 != self.context.receiver {
+            return self.fail(&format!("{}: db access violation", api));
+        }
+        Ok(())
+    }
+
+    fn primary_iterator(&mut self, it: i32, api: &str) -> thrift::Result<(TableKey, u64)> {
+        match self.context.primary_iterators.get(it) {
+            Some(x) => Ok(x),
+            None => self.fail(&format!("{}: invalid iterator", api)),
+        }
+    }
+
+    fn primary_result(&mut self, table: TableKey, primary: Option<u64>) -> NextPreviousReturn {
+        match primary {
+            Some(primary) => NextPreviousReturn::new(self.context.primary_iterators.add(table, primary), Uint64::from(primary)),
+            None => NextPreviousReturn::new(self.context.primary_iterators.end_iterator(table), None::<Uint64>),
+        }
+    }
+
+    fn primary_bound(&mut self, code: Uint64, scope: Uint64, table: Uint64, id: Uint64, upper: bool) -> i32 {
+        let table: TableKey = (code.into(), scope.into(), table.into());
+        let id: u64 = id.into();
+        let lower = if upper { Excluded(id) } else { Included(id) };
+        let found = match self.db.primary.get(&table) {
+            Some(t) => t.rows.range((lower, Unbounded)).next().map(|(k, _)| *k),
+            None => return -1,
+        };
+        self.primary_result(table, found).iterator.unwrap()
+    }
+
+    fn check_secondary(&mut self, kind: IdxKind, secondary: &[u8], api: &str) -> thrift::Result<()> {
+        if secondary.len() != kind.size() {
+            return self.fail(&format!("{}: invalid secondary value", api));
+        }
+        Ok(())
+    }
+
+    fn idx_iterator(&mut self, kind: IdxKind, it: i32, api: &str) -> thrift::Result<(TableKey, u64)> {
+        match self.context.secondary_iterators[kind as usize].get(it) {
+            Some(x) => Ok(x),
+            None => self.fail(&format!("{}: invalid iterator", api)),
+        }
+    }
+
+    fn idx_row(&self, kind: IdxKind, table: TableKey, primary: u64) -> Option<&SecondaryRow> {
+        self.db.secondary[kind as usize].get(&table).and_then(|t| t.rows.get(&primary))
+    }
+
+    fn idx_result(&mut self, kind: IdxKind, table: TableKey, primary: Option<u64>) -> LowerBoundUpperBoundReturn {
+        match primary {
+            Some(primary) => {
+                let raw = self.idx_row(kind, table, primary).unwrap().raw.clone();
+                let it = self.context.secondary_iterators[kind as usize].add(table, primary);
+                LowerBoundUpperBoundReturn::new(it, raw, Uint64::from(primary))
+            }
+            None => {
+                let it = self.context.secondary_iterators[kind as usize].end_iterator(table);
+                LowerBoundUpperBoundReturn::new(it, None::<Vec<u8>>, None::<Uint64>)
+            }
+        }
+    }
+
+    fn idx_store(&mut self, kind: IdxKind, scope: Uint64, table: Uint64, payer: Uint64, id: Uint64, secondary: Vec<u8>) -> thrift::Result<i32> {
+        self.check_secondary(kind, &secondary, "db_idx_store")?;
+        let table: TableKey = (self.context.receiver, scope.into(), table.into());
+        let id: u64 = id.into();
+        if self.idx_row(kind, table, id).is_some() {
+            return self.fail("db_idx_store: secondary key already exists for the primary key");
+        }
+        let key = kind.sort_key(&secondary);
+        let payer: u64 = payer.into();
+        let t = self.db.secondary[kind as usize].entry(table).or_insert_with(|| SecondaryTable { payer, ..Default::default() });
+        t.keys.insert((key.clone(), id));
+        t.rows.insert(id, SecondaryRow { payer, raw: secondary, key });
+        Ok(self.context.secondary_iterators[kind as usize].add(table, id))
+    }
+
+    fn idx_update(&mut self, kind: IdxKind, it: i32, payer: Uint64, secondary: Vec<u8>) -> thrift::Result<()> {
+        self.check_secondary(kind, &secondary, "db_idx_update")?;
+        let (table, primary) = self.idx_iterator(kind, it, "db_idx_update")?;
+        self.check_code(table, "db_idx_update")?;
+        let payer: u64 = payer.into();
+        let key = kind.sort_key(&secondary);
+        let t = self.db.secondary[kind as usize].get_mut(&table).unwrap();
+        let row = t.rows.get_mut(&primary).unwrap();
+        t.keys.remove(&(row.key.clone(), primary));
+        t.keys.insert((key.clone(), primary));
+        if payer != 0 {
+            row.payer = payer;
+        }
+        row.raw = secondary;
+        row.key = key;
+        Ok(())
+    }
+
+    fn idx_remove(&mut self, kind: IdxKind, it: i32) -> thrift::Result<()> {
+        let (table, primary) = self.idx_iterator(kind, it, "db_idx_remove")?;
+        self.check_code(table, "db_idx_remove")?;
+        let tables = &mut self.db.secondary[kind as usize];
+        let t = tables.get_mut(&table).unwrap();
+        let row = t.rows.remove(&primary).unwrap();
+        t.keys.remove(&(row.key, primary));
+        if t.rows.is_empty() {
+            tables.remove(&table);
+        }
+        self.context.secondary_iterators[kind as usize].remove(it);
+        Ok(())
+    }
+
+    fn idx_next(&mut self, kind: IdxKind, it: i32) -> thrift::Result<NextPreviousReturn> {
+        if it < -1 {
+            return Ok(NextPreviousReturn::new(-1, None::<Uint64>));
+        }
+        let (table, primary) = self.idx_iterator(kind, it, "db_idx_next")?;
+        let key = self.idx_row(kind, table, primary).unwrap().key.clone();
+        let next = self.db.secondary[kind as usize][&table].keys
+            .range((Excluded((key, primary)), Unbounded))
+            .next()
+            .map(|(_, p)| *p);
+        let ret = self.idx_result(kind, table, next);
+        Ok(NextPreviousReturn::new(ret.iterator, ret.primary))
+    }
+
+    fn idx_previous(&mut self, kind: IdxKind, it: i32) -> thrift::Result<NextPreviousReturn> {
+        let (table, previous) = match self.context.secondary_iterators[kind as usize].end_table(it) {
+            Some(table) => {
+                let last = self.db.secondary[kind as usize].get(&table).and_then(|t| t.keys.iter().next_back().map(|(_, p)| *p));
+                (table, last)
+            }
+            None => {
+                let (table, primary) = self.idx_iterator(kind, it, "db_idx_previous")?;
+                let key = self.idx_row(kind, table, primary).unwrap().key.clone();
+                let previous = self.db.secondary[kind as usize][&table].keys
+                    .range(..(key, primary))
+                    .next_back()
+                    .map(|(_, p)| *p);
+                (table, previous)
+            }
+        };
+        if previous.is_none() {
+            return Ok(NextPreviousReturn::new(-1, None::<Uint64>));
+        }
+        let ret = self.idx_result(kind, table, previous);
+        Ok(NextPreviousReturn::new(ret.iterator, ret.primary))
+    }
+
+    fn idx_find_primary(&mut self, kind: IdxKind, code: Uint64, scope: Uint64, table: Uint64, primary: Uint64) -> thrift::Result<FindPrimaryReturn> {
+        let table: TableKey = (code.into(), scope.into(), table.into());
+        if !self.db.secondary[kind as usize].contains_key(&table) {
+            return Ok(FindPrimaryReturn::new(-1, None::<Vec<u8>>));
+        }
+        let primary: u64 = primary.into();
+        let found = self.idx_row(kind, table, primary).map(|_| primary);
+        let ret = self.idx_result(kind, table, found);
+        Ok(FindPrimaryReturn::new(ret.iterator, ret.secondary))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn idx_bound(&mut self, kind: IdxKind, code: Uint64, scope: Uint64, table: Uint64, secondary: Vec<u8>, upper: bool, api: &str) -> thrift::Result<LowerBoundUpperBoundReturn> {
+        self.check_secondary(kind, &secondary, api)?;
+        let table: TableKey = (code.into(), scope.into(), table.into());
+        let key = kind.sort_key(&secondary);
+        let lower = if upper { Excluded((key, u64::MAX)) } else { Included((key, 0)) };
+        let found = match self.db.secondary[kind as usize].get(&table) {
+            Some(t) => t.keys.range((lower, Unbounded)).next().map(|(_, p)| *p),
+            None => return Ok(LowerBoundUpperBoundReturn::new(-1, None::<Vec<u8>>, None::<Uint64>)),
+        };
+        Ok(self.idx_result(kind, table, found))
+    }
+
+    fn idx_find_secondary(&mut self, kind: IdxKind, code: Uint64, scope: Uint64, table: Uint64, secondary: Vec<u8>) -> thrift::Result<FindSecondaryReturn> {
+        let ret = self.idx_bound(kind, code.clone(), scope.clone(), table.clone(), secondary.clone(), false, "db_idx_find_secondary")?;
+        if ret.secondary.is_some() && ret.secondary.as_ref() != Some(&secondary) {
+            let table: TableKey = (code.into(), scope.into(), table.into());
+            let end = self.context.secondary_iterators[kind as usize].end_iterator(table);
+            return Ok(FindSecondaryReturn::new(end, None::<Uint64>));
+        }
+        Ok(FindSecondaryReturn::new(ret.iterator, ret.primary))
+    }
+
+    fn idx_end(&mut self, kind: IdxKind, code: Uint64, scope: Uint64, table: Uint64) -> thrift::Result<i32> {
+        let table: TableKey = (code.into(), scope.into(), table.into());
+        if !self.db.secondary[kind as usize].contains_key(&table) {
+            return Ok(-1);
+        }
+        Ok(self.context.secondary_iterators[kind as usize].end_iterator(table))
+    }
+}
+
+impl TApplySyncClient for MockChain {
+    fn end_apply(&mut self) -> thrift::Result<i32> {
+        Ok(1)
+    }
+
+    fn get_active_producers(&mut self) -> thrift::Result<Vec<u8>> {
+        Ok(s2n("eosio").to_le_bytes().to_vec())
+    }
+
+    fn get_resource_limits(&mut self, account: Uint64) -> thrift::Result<GetResourceLimitsReturn> {
+        let (ram_bytes, net_weight, cpu_weight) = self.resource_limits.get(&account.into()).copied().unwrap_or((-1, -1, -1));
+        Ok(GetResourceLimitsReturn::new(ram_bytes, net_weight, cpu_weight))
+    }
+
+    fn set_resource_limits(&mut self, account: Uint64, ram_bytes: i64, net_weight: i64, cpu_weight: i64) -> thrift::Result<()> {
+        self.resource_limits.insert(account.into(), (ram_bytes, net_weight, cpu_weight));
+        Ok(())
+    }
+
+    fn set_proposed_producers(&mut self, _producer_data: Vec<u8>) -> thrift::Result<i64> {
+        self.unsupported("set_proposed_producers")
+    }
+
+    fn set_proposed_producers_ex(&mut self, _producer_data_format: Uint64, _producer_data: Vec<u8>) -> thrift::Result<i64> {
+        self.unsupported("set_proposed_producers_ex")
+    }
+
+    fn is_privileged(&mut self, account: Uint64) -> thrift::Result<bool> {
+        Ok(self.privileged.contains(&account.into()))
+    }
+
+    fn set_privileged(&mut self, account: Uint64, is_priv: bool) -> thrift::Result<()> {
+        if is_priv {
+            self.privileged.insert(account.into());
+        } else {
+            self.privileged.remove(&account.into());
+        }
+        Ok(())
+    }
+
+    fn set_blockchain_parameters_packed(&mut self, _data: Vec<u8>) -> thrift::Result<()> {
+        self.unsupported("set_blockchain_parameters_packed")
+    }
+
+    fn get_blockchain_parameters_packed(&mut self) -> thrift::Result<Vec<u8>> {
+        self.unsupported("get_blockchain_parameters_packed")
+    }
+
+    fn preactivate_feature(&mut self, _feature_digest: Vec<u8>) -> thrift::Result<()> {
+        Ok(())
+    }
+
+    fn check_transaction_authorization(&mut self, _trx_data: Vec<u8>, _pubkeys_data: Vec<u8>, _perms_data: Vec<u8>) -> thrift::Result<i32> {
+        self.unsupported("check_transaction_authorization")
+    }
+
+    fn check_permission_authorization(&mut self, _account: Uint64, _permission: Uint64, _pubkeys_data: Vec<u8>, _perms_data: Vec<u8>, _delay_us: Uint64) -> thrift::Result<i32> {
+        self.unsupported("check_permission_authorization")
+    }
+
+    fn get_permission_last_used(&mut self, _account: Uint64, _permission: Uint64) -> thrift::Result<i64> {
+        self.unsupported("get_permission_last_used")
+    }
+
+    fn get_account_creation_time(&mut self, account: Uint64) -> thrift::Result<i64> {
+        match self.accounts.get(&account.into()) {
+            Some(time) => Ok(*time as i64),
+            None => self.fail("get_account_creation_time: account does not exist"),
+        }
+    }
+
+    fn prints(&mut self, cstr: String) -> thrift::Result<()> {
+        self.context.console.push_str(&cstr);
+        Ok(())
+    }
+
+    fn prints_l(&mut self, cstr: Vec<u8>) -> thrift::Result<()> {
+        self.context.console.push_str(&String::from_utf8_lossy(&cstr));
+        Ok(())
+    }
+
+    fn printi(&mut self, n: i64) -> thrift::Result<()> {
+        self.context.console.push_str(&n.to_string());
+        Ok(())
+    }
+
+    fn printui(&mut self, n: Uint64) -> thrift::Result<()> {
+        self.context.console.push_str(&u64::from(n).to_string());
+        Ok(())
+    }
+
+    fn printi128(&mut self, value: Vec<u8>) -> thrift::Result<()> {
+        let value = i128::from_le_bytes(value.try_into().unwrap());
+        self.context.console.push_str(&value.to_string());
+        Ok(())
+    }
+
+    fn printui128(&mut self, value: Vec<u8>) -> thrift::Result<()> {
+        let value = u128::from_le_bytes(value.try_into().unwrap());
+        self.context.console.push_str(&value.to_string());
+        Ok(())
+    }
+
+    fn printsf(&mut self, value: Vec<u8>) -> thrift::Result<()> {
+        let value = f32::from_le_bytes(value.try_into().unwrap());
+        self.context.console.push_str(&value.to_string());
+        Ok(())
+    }
+
+    fn printdf(&mut self, value: Vec<u8>) -> thrift::Result<()> {
+        let value = f64::from_le_bytes(value.try_into().unwrap());
+        self.context.console.push_str(&value.to_string());
+        Ok(())
+    }
+
+    fn printqf(&mut self, value: Vec<u8>) -> thrift::Result<()> {
+        self.context.console.push_str(&hex::encode(value));
+        Ok(())
+    }
+
+    fn printn(&mut self, name: Uint64) -> thrift::Result<()> {
+        self.context.console.push_str(&n2s(name.into()));
+        Ok(())
+    }
+
+    fn printhex(&mut self, data: Vec<u8>) -> thrift::Result<()> {
+        self.context.console.push_str(&hex::encode(data));
+        Ok(())
+    }
+
+    fn action_data_size(&mut self) -> thrift::Result<i32> {
+        Ok(self.context.action.data.len() as i32)
+    }
+
+    fn read_action_data(&mut self) -> thrift::Result<Vec<u8>> {
+        Ok(self.context.action.data.clone())
+    }
+
+    fn require_recipient(&mut self, name: Uint64) -> thrift::Result<()> {
+        let name: u64 = name.into();
+        if !self.accounts.contains_key(&name) {
+            return self.fail(&format!("require_recipient: account {} does not exist", n2s(name)));
+        }
+        if !self.context.recipients.contains(&name) {
+            self.context.recipients.push(name);
+        }
+        Ok(())
+    }
+
+    fn require_auth(&mut self, name: Uint64) -> thrift::Result<()> {
+        let name: u64 = name.into();
+        if !self.has_auth_ex(name, None) {
+            return self.fail(&format!("missing authority of {}", n2s(name)));
+        }
+        Ok(())
+    }
+
+    fn has_auth(&mut self, name: Uint64) -> thrift::Result<bool> {
+        Ok(self.has_auth_ex(name.into(), None))
+    }
+
+    fn require_auth2(&mut self, name: Uint64, permission: Uint64) -> thrift::Result<()> {
+        let name: u64 = name.into();
+        let permission: u64 = permission.into();
+        if !self.has_auth_ex(name, Some(permission)) {
+            return self.fail(&format!("missing authority of {}/{}", n2s(name), n2s(permission)));
+        }
+        Ok(())
+    }
+
+    fn is_account(&mut self, name: Uint64) -> thrift::Result<bool> {
+        Ok(self.accounts.contains_key(&name.into()))
+    }
+
+    fn send_inline(&mut self, serialized_action: Vec<u8>) -> thrift::Result<()> {
+        match MockAction::unpack(&serialized_action) {
+            Some(action) => {
+                self.context.inline_actions.push(action);
+                Ok(())
+            }
+            None => self.fail("send_inline: invalid action data"),
+        }
+    }
+
+    fn send_context_free_inline(&mut self, serialized_data: Vec<u8>) -> thrift::Result<()> {
+        match MockAction::unpack(&serialized_data) {
+            Some(action) if action.authorization.is_empty() => {
+                self.context.inline_actions.push(action);
+                Ok(())
+            }
+            Some(_) => self.fail("context-free actions cannot have authorizations"),
+            None => self.fail("send_context_free_inline: invalid action data"),
+        }
+    }
+
+    fn publication_time(&mut self) -> thrift::Result<Uint64> {
+        Ok(self.block_time.into())
+    }
+
+    fn current_receiver(&mut self) -> thrift::Result<Uint64> {
+        Ok(self.context.receiver.into())
+    }
+
+    fn eosio_assert(&mut self, test: bool, msg: Vec<u8>) -> thrift::Result<()> {
+        if !test {
+            return self.fail(&String::from_utf8_lossy(&msg));
+        }
+        Ok(())
+    }
+
+    fn eosio_assert_message(&mut self, test: bool, msg: Vec<u8>) -> thrift::Result<()> {
+        if !test {
+            return self.fail(&String::from_utf8_lossy(&msg));
+        }
+        Ok(())
+    }
+
+    fn eosio_assert_code(&mut self, test: bool, code: Uint64) -> thrift::Result<()> {
+        if !test {
+            return self.fail(&format!("assertion failure with error code: {}", u64::from(code)));
+        }
+        Ok(())
+    }
+
+    fn eosio_exit(&mut self, _code: i32) -> thrift::Result<()> {
+        self.context.exited = true;
+        Err(thrift::Error::Application(ApplicationError::new(ApplicationErrorKind::Unknown, "eosio_exit")))
+    }
+
+    fn current_time(&mut self) -> thrift::Result<Uint64> {
+        Ok(self.block_time.into())
+    }
+
+    fn is_feature_activated(&mut self, _feature_digest: Vec<u8>) -> thrift::Result<bool> {
+        Ok(true)
+    }
+
+    fn get_sender(&mut self) -> thrift::Result<Uint64> {
+        Ok(self.context.sender.into())
+    }
+
+    fn assert_sha256(&mut self, _data: Vec<u8>, _hash: Vec<u8>) -> thrift::Result<()> {
+        self.unsupported("assert_sha256")
+    }
+
+    fn assert_sha1(&mut self, _data: Vec<u8>, _hash: Vec<u8>) -> thrift::Result<()> {
+        self.unsupported("assert_sha1")
+    }
+
+    fn assert_sha512(&mut self, _data: Vec<u8>, _hash: Vec<u8>) -> thrift::Result<()> {
+        self.unsupported("assert_sha512")
+    }
+
+    fn assert_ripemd160(&mut self, _data: Vec<u8>, _hash: Vec<u8>) -> thrift::Result<()> {
+        self.unsupported("assert_ripemd160")
+    }
+
+    fn sha256(&mut self, _data: Vec<u8>) -> thrift::Result<Vec<u8>> {
+        self.unsupported("sha256")
+    }
+
+    fn sha1(&mut self, _data: Vec<u8>) -> thrift::Result<Vec<u8>> {
+        self.unsupported("sha1")
+    }
+
+    fn sha512(&mut self, _data: Vec<u8>) -> thrift::Result<Vec<u8>> {
+        self.unsupported("sha512")
+    }
+
+    fn ripemd160(&mut self, _data: Vec<u8>) -> thrift::Result<Vec<u8>> {
+        self.unsupported("ripemd160")
+    }
+
+    fn recover_key(&mut self, _digest: Vec<u8>, _sig: Vec<u8>) -> thrift::Result<Vec<u8>> {
+        self.unsupported("recover_key")
+    }
+
+    fn assert_recover_key(&mut self, _digest: Vec<u8>, _sig: Vec<u8>, _pub: Vec<u8>) -> thrift::Result<()> {
+        self.unsupported("assert_recover_key")
+    }
+
+    fn send_deferred(&mut self, _sender_id: Vec<u8>, _payer: Uint64, _serialized_transaction: Vec<u8>, _replace_existing: i32) -> thrift::Result<()> {
+        self.unsupported("send_deferred")
+    }
+
+    fn cancel_deferred(&mut self, _sender_id: Vec<u8>) -> thrift::Result<i32> {
+        self.unsupported("cancel_deferred")
+    }
+
+    fn read_transaction(&mut self) -> thrift::Result<Vec<u8>> {
+        self.unsupported("read_transaction")
+    }
+
+    fn transaction_size(&mut self) -> thrift::Result<i32> {
+        self.unsupported("transaction_size")
+    }
+
+    fn tapos_block_num(&mut self) -> thrift::Result<i32> {
+        Ok((self.block_num & 0xffff) as i32)
+    }
+
+    fn tapos_block_prefix(&mut self) -> thrift::Result<i32> {
+        Ok(0)
+    }
+
+    fn expiration(&mut self) -> thrift::Result<i64> {
+        Ok((self.block_time / 1_000_000 + 30) as i64)
+    }
+
+    fn get_action(&mut self, _type: i32, _index: i32) -> thrift::Result<Vec<u8>> {
+        self.unsupported("get_action")
+    }
+
+    fn get_context_free_data(&mut self, _index: i32) -> thrift::Result<Vec<u8>> {
+        self.unsupported("get_context_free_data")
+    }
+
+    fn db_store_i64(&mut self, scope: Uint64, table: Uint64, payer: Uint64, id: Uint64, data: Vec<u8>) -> thrift::Result<i32> {
+        let table: TableKey = (self.context.receiver, scope.into(), table.into());
+        let id: u64 = id.into();
+        let payer: u64 = payer.into();
+        let rows = &mut self.db.primary.entry(table).or_insert_with(|| PrimaryTable { payer, ..Default::default() }).rows;
+        if rows.contains_key(&id) {
+            return self.fail("db_store_i64: could not insert object, most likely a uniqueness constraint was violated");
+        }
+        rows.insert(id, (payer, data));
+        Ok(self.context.primary_iterators.add(table, id))
+    }
+
+    fn db_update_i64(&mut self, iterator: i32, payer: Uint64, data: Vec<u8>) -> thrift::Result<()> {
+        let (table, primary) = self.primary_iterator(iterator, "db_update_i64")?;
+        self.check_code(table, "db_update_i64")?;
+        let payer: u64 = payer.into();
+        let row = self.db.primary.get_mut(&table).unwrap().rows.get_mut(&primary).unwrap();
+        if payer != 0 {
+            row.0 = payer;
+        }
+        row.1 = data;
+        Ok(())
+    }
+
+    fn db_remove_i64(&mut self, iterator: i32) -> thrift::Result<()> {
+        let (table, primary) = self.primary_iterator(iterator, "db_remove_i64")?;
+        self.check_code(table, "db_remove_i64")?;
+        let t = self.db.primary.get_mut(&table).unwrap();
+        t.rows.remove(&primary);
+        if t.rows.is_empty() {
+            self.db.primary.remove(&table);
+        }
+        self.context.primary_iterators.remove(iterator);
+        Ok(())
+    }
+
+    fn db_get_i64(&mut self, iterator: i32) -> thrift::Result<Vec<u8>> {
+        let (table, primary) = self.primary_iterator(iterator, "db_get_i64")?;
+        Ok(self.db.primary[&table].rows[&primary].1.clone())
+    }
+
+    fn db_next_i64(&mut self, iterator: i32) -> thrift::Result<NextPreviousReturn> {
+        if iterator < -1 {
+            return Ok(NextPreviousReturn::new(-1, None::<Uint64>));
+        }
+        let (table, primary) = self.primary_iterator(iterator, "db_next_i64")?;
+        let next = self.db.primary[&table].rows
+            .range((Excluded(primary), Unbounded))
+            .next()
+            .map(|(k, _)| *k);
+        Ok(self.primary_result(table, next))
+    }
+
+    fn db_previous_i64(&mut self, iterator: i32) -> thrift::Result<NextPreviousReturn> {
+        let (table, previous) = match self.context.primary_iterators.end_table(iterator) {
+            Some(table) => {
+                let last = self.db.primary.get(&table).and_then(|t| t.rows.keys().next_back().copied());
+                (table, last)
+            }
+            None => {
+                let (table, primary) = self.primary_iterator(iterator, "db_previous_i64")?;
+                let previous = self.db.primary[&table].rows.range(..primary).next_back().map(|(k, _)| *k);
+                (table, previous)
+            }
+        };
+        if previous.is_none() {
+            return Ok(NextPreviousReturn::new(-1, None::<Uint64>));
+        }
+        Ok(self.primary_result(table, previous))
+    }
+
+    fn db_find_i64(&mut self, code: Uint64, scope: Uint64, table: Uint64, id: Uint64) -> thrift::Result<i32> {
+        let table: TableKey = (code.into(), scope.into(), table.into());
+        let id: u64 = id.into();
+        let found = match self.db.primary.get(&table) {
+            Some(t) => t.rows.contains_key(&id),
+            None => return Ok(-1),
+        };
+        Ok(self.primary_result(table, if found { Some(id) } else { None }).iterator.unwrap())
+    }
+
+    fn db_lowerbound_i64(&mut self, code: Uint64, scope: Uint64, table: Uint64, id: Uint64) -> thrift::Result<i32> {
+        Ok(self.primary_bound(code, scope, table, id, false))
+    }
+
+    fn db_upperbound_i64(&mut self, code: Uint64, scope: Uint64, table: Uint64, id: Uint64) -> thrift::Result<i32> {
+        Ok(self.primary_bound(code, scope, table, id, true))
+    }
+
+    fn db_end_i64(&mut self, code: Uint64, scope: Uint64, table: Uint64) -> thrift::Result<i32> {
+        let table: TableKey = (code.into(), scope.into(), table.into());
+        if !self.db.primary.contains_key(&table) {
+            return Ok(-1);
+        }
+        Ok(self.context.primary_iterators.end_iterator(table))
+    }
+
+    fn db_idx64_store(&mut self, scope: Uint64, table: Uint64, payer: Uint64, id: Uint64, secondary: Uint64) -> thrift::Result<i32> {
+        let secondary = u64::from(secondary).to_le_bytes().to_vec();
+        self.idx_store(IdxKind::Idx64, scope, table, payer, id, secondary)
+    }
+
+    fn db_idx64_update(&mut self, iterator: i32, payer: Uint64, secondary: Uint64) -> thrift::Result<()> {
+        let secondary = u64::from(secondary).to_le_bytes().to_vec();
+        self.idx_update(IdxKind::Idx64, iterator, payer, secondary)
+    }
+
+    fn db_idx64_remove(&mut self, iterator: i32) -> thrift::Result<()> {
+        self.idx_remove(IdxKind::Idx64, iterator)
+    }
+
+    fn db_idx64_next(&mut self, iterator: i32) -> thrift::Result<NextPreviousReturn> {
+        self.idx_next(IdxKind::Idx64, iterator)
+    }
+
+    fn db_idx64_previous(&mut self, iteratory: i32) -> thrift::Result<NextPreviousReturn> {
+        self.idx_previous(IdxKind::Idx64, iteratory)
+    }
+
+    fn db_idx64_find_primary(&mut self, code: Uint64, scope: Uint64, table: Uint64, primary: Uint64) -> thrift::Result<FindPrimaryReturn> {
+        self.idx_find_primary(IdxKind::Idx64, code, scope, table, primary)
+    }
+
+    fn db_idx64_find_secondary(&mut self, code: Uint64, scope: Uint64, table: Uint64, secondary: Uint64) -> thrift::Result<FindSecondaryReturn> {
+        let secondary = u64::from(secondary).to_le_bytes().to_vec();
+        self.idx_find_secondary(IdxKind::Idx64, code, scope, table, secondary)
+    }
+
+    fn db_idx64_lowerbound(&mut self, code: Uint64, scope: Uint64, table: Uint64, secondary: Uint64, _primary: Uint64) -> thrift::Result<LowerBoundUpperBoundReturn> {
+        let secondary = u64::from(secondary).to_le_bytes().to_vec();
+        self.idx_bound(IdxKind::Idx64, code, scope, table, secondary, false, "db_idx64_lowerbound")
+    }
+
+    fn db_idx64_upperbound(&mut self, code: Uint64, scope: Uint64, table: Uint64, secondary: Uint64, _primary: Uint64) -> thrift::Result<LowerBoundUpperBoundReturn> {
+        let secondary = u64::from(secondary).to_le_bytes().to_vec();
+        self.idx_bound(IdxKind::Idx64, code, scope, table, secondary, true, "db_idx64_upperbound")
+    }
+
+    fn db_idx64_end(&mut self, code: Uint64, scope: Uint64, table: Uint64) -> thrift::Result<i32> {
+        self.idx_end(IdxKind::Idx64, code, scope, table)
+    }
+
+    fn db_idx128_store(&mut self, scope: Uint64, table: Uint64, payer: Uint64, id: Uint64, secondary: Vec<u8>) -> thrift::Result<i32> {
+        self.idx_store(IdxKind::Idx128, scope, table, payer, id, secondary)
+    }
+
+    fn db_idx128_update(&mut self, iterator: i32, payer: Uint64, secondary: Vec<u8>) -> thrift::Result<()> {
+        self.idx_update(IdxKind::Idx128, iterator, payer, secondary)
+    }
+
+    fn db_idx128_remove(&mut self, iterator: i32) -> thrift::Result<()> {
+        self.idx_remove(IdxKind::Idx128, iterator)
+    }
+
+    fn db_idx128_next(&mut self, iterator: i32) -> thrift::Result<NextPreviousReturn> {
+        self.idx_next(IdxKind::Idx128, iterator)
+    }
+
+    fn db_idx128_previous(&mut self, iterator: i32) -> thrift::Result<NextPreviousReturn> {
+        self.idx_previous(IdxKind::Idx128, iterator)
+    }
+
+    fn db_idx128_find_primary(&mut self, code: Uint64, scope: Uint64, table: Uint64, primary: Uint64) -> thrift::Result<FindPrimaryReturn> {
+        self.idx_find_primary(IdxKind::Idx128, code, scope, table, primary)
+    }
+
+    fn db_idx128_find_secondary(&mut self, code: Uint64, scope: Uint64, table: Uint64, secondary: Vec<u8>) -> thrift::Result<FindSecondaryReturn> {
+        self.idx_find_secondary(IdxKind::Idx128, code, scope, table, secondary)
+    }
+
+    fn db_idx128_lowerbound(&mut self, code: Uint64, scope: Uint64, table: Uint64, secondary: Vec<u8>, _primary: Uint64) -> thrift::Result<LowerBoundUpperBoundReturn> {
+        self.idx_bound(IdxKind::Idx128, code, scope, table, secondary, false, "db_idx128_lowerbound")
+    }
+
+    fn db_idx128_upperbound(&mut self, code: Uint64, scope: Uint64, table: Uint64, secondary: Vec<u8>, _primary: Uint64) -> thrift::Result<LowerBoundUpperBoundReturn> {
+        self.idx_bound(IdxKind::Idx128, code, scope, table, secondary, true, "db_idx128_upperbound")
+    }
+
+    fn db_idx128_end(&mut self, code: Uint64, scope: Uint64, table: Uint64) -> thrift::Result<i32> {
+        self.idx_end(IdxKind::Idx128, code, scope, table)
+    }
+
+    fn db_idx256_store(&mut self, scope: Uint64, table: Uint64, payer: Uint64, id: Uint64, data: Vec<u8>) -> thrift::Result<i32> {
+        self.idx_store(IdxKind::Idx256, scope, table, payer, id, data)
+    }
+
+    fn db_idx256_update(&mut self, iterator: i32, payer: Uint64, data: Vec<u8>) -> thrift::Result<()> {
+        self.idx_update(IdxKind::Idx256, iterator, payer, data)
+    }
+
+    fn db_idx256_remove(&mut self, iterator: i32) -> thrift::Result<()> {
+        self.idx_remove(IdxKind::Idx256, iterator)
+    }
+
+    fn db_idx256_next(&mut self, iterator: i32) -> thrift::Result<NextPreviousReturn> {
+        self.idx_next(IdxKind::Idx256, iterator)
+    }
+
+    fn db_idx256_previous(&mut self, iterator: i32) -> thrift::Result<NextPreviousReturn> {
+        self.idx_previous(IdxKind::Idx256, iterator)
+    }
+
+    fn db_idx256_find_primary(&mut self, code: Uint64, scope: Uint64, table: Uint64, primary: Uint64) -> thrift::Result<FindPrimaryReturn> {
+        self.idx_find_primary(IdxKind::Idx256, code, scope, table, primary)
+    }
+
+    fn db_idx256_find_secondary(&mut self, code: Uint64, scope: Uint64, table: Uint64, data: Vec<u8>) -> thrift::Result<FindSecondaryReturn> {
+        self.idx_find_secondary(IdxKind::Idx256, code, scope, table, data)
+    }
+
+    fn db_idx256_lowerbound(&mut self, code: Uint64, scope: Uint64, table: Uint64, data: Vec<u8>, _primary: Uint64) -> thrift::Result<LowerBoundUpperBoundReturn> {
+        self.idx_bound(IdxKind::Idx256, code, scope, table, data, false, "db_idx256_lowerbound")
+    }
+
+    fn db_idx256_upperbound(&mut self, code: Uint64, scope: Uint64, table: Uint64, data: Vec<u8>, _primary: Uint64) -> thrift::Result<LowerBoundUpperBoundReturn> {
+        self.idx_bound(IdxKind::Idx256, code, scope, table, data, true, "db_idx256_upperbound")
+    }
+
+    fn db_idx256_end(&mut self, code: Uint64, scope: Uint64, table: Uint64) -> thrift::Result<i32> {
+        self.idx_end(IdxKind::Idx256, code, scope, table)
+    }
+
+    fn db_idx_double_store(&mut self, scope: Uint64, table: Uint64, payer: Uint64, id: Uint64, secondary: Vec<u8>) -> thrift::Result<i32> {
+        self.idx_store(IdxKind::IdxDouble, scope, table, payer, id, secondary)
+    }
+
+    fn db_idx_double_update(&mut self, iterator: i32, payer: Uint64, secondary: Vec<u8>) -> thrift::Result<()> {
+        self.idx_update(IdxKind::IdxDouble, iterator, payer, secondary)
+    }
+
+    fn db_idx_double_remove(&mut self, iterator: i32) -> thrift::Result<()> {
+        self.idx_remove(IdxKind::IdxDouble, iterator)
+    }
+
+    fn db_idx_double_next(&mut self, iterator: i32) -> thrift::Result<NextPreviousReturn> {
+        self.idx_next(IdxKind::IdxDouble, iterator)
+    }
+
+    fn db_idx_double_previous(&mut self, iterator: i32) -> thrift::Result<NextPreviousReturn> {
+        self.idx_previous(IdxKind::IdxDouble, iterator)
+    }
+
+    fn db_idx_double_find_primary(&mut self, code: Uint64, scope: Uint64, table: Uint64, primary: Uint64) -> thrift::Result<FindPrimaryReturn> {
+        self.idx_find_primary(IdxKind::IdxDouble, code, scope, table, primary)
+    }
+
+    fn db_idx_double_find_secondary(&mut self, code: Uint64, scope: Uint64, table: Uint64, secondary: Vec<u8>) -> thrift::Result<FindSecondaryReturn> {
+        self.idx_find_secondary(IdxKind::IdxDouble, code, scope, table, secondary)
+    }
+
+    fn db_idx_double_lowerbound(&mut self, code: Uint64, scope: Uint64, table: Uint64, secondary: Vec<u8>, _primary: Uint64) -> thrift::Result<LowerBoundUpperBoundReturn> {
+        self.idx_bound(IdxKind::IdxDouble, code, scope, table, secondary, false, "db_idx_double_lowerbound")
+    }
+
+    fn db_idx_double_upperbound(&mut self, code: Uint64, scope: Uint64, table: Uint64, secondary: Vec<u8>, _primary: Uint64) -> thrift::Result<LowerBoundUpperBoundReturn> {
+        self.idx_bound(IdxKind::IdxDouble, code, scope, table, secondary, true, "db_idx_double_upperbound")
+    }
+
+    fn db_idx_double_end(&mut self, code: Uint64, scope: Uint64, table: Uint64) -> thrift::Result<i32> {
+        self.idx_end(IdxKind::IdxDouble, code, scope, table)
+    }
+
+    fn db_idx_long_double_store(&mut self, scope: Uint64, table: Uint64, payer: Uint64, id: Uint64, secondary: Vec<u8>) -> thrift::Result<i32> {
+        self.idx_store(IdxKind::IdxLongDouble, scope, table, payer, id, secondary)
+    }
+
+    fn db_idx_long_double_update(&mut self, iterator: i32, payer: Uint64, secondary: Vec<u8>) -> thrift::Result<()> {
+        self.idx_update(IdxKind::IdxLongDouble, iterator, payer, secondary)
+    }
+
+    fn db_idx_long_double_remove(&mut self, iterator: i32) -> thrift::Result<()> {
+        self.idx_remove(IdxKind::IdxLongDouble, iterator)
+    }
+
+    fn db_idx_long_double_next(&mut self, iterator: i32) -> thrift::Result<NextPreviousReturn> {
+        self.idx_next(IdxKind::IdxLongDouble, iterator)
+    }
+
+    fn db_idx_long_double_previous(&mut self, iterator: i32) -> thrift::Result<NextPreviousReturn> {
+        self.idx_previous(IdxKind::IdxLongDouble, iterator)
+    }
+
+    fn db_idx_long_double_find_primary(&mut self, code: Uint64, scope: Uint64, table: Uint64, primary: Uint64) -> thrift::Result<FindPrimaryReturn> {
+        self.idx_find_primary(IdxKind::IdxLongDouble, code, scope, table, primary)
+    }
+
+    fn db_idx_long_double_find_secondary(&mut self, code: Uint64, scope: Uint64, table: Uint64, secondary: Vec<u8>) -> thrift::Result<FindSecondaryReturn> {
+        self.idx_find_secondary(IdxKind::IdxLongDouble, code, scope, table, secondary)
+    }
+
+    fn db_idx_long_double_lowerbound(&mut self, code: Uint64, scope: Uint64, table: Uint64, secondary: Vec<u8>, _primary: Uint64) -> thrift::Result<LowerBoundUpperBoundReturn> {
+        self.idx_bound(IdxKind::IdxLongDouble, code, scope, table, secondary, false, "db_idx_long_double_lowerbound")
+    }
+
+    fn db_idx_long_double_upperbound(&mut self, code: Uint64, scope: Uint64, table: Uint64, secondary: Vec<u8>, _primary: Uint64) -> thrift::Result<LowerBoundUpperBoundReturn> {
+        self.idx_bound(IdxKind::IdxLongDouble, code, scope, table, secondary, true, "db_idx_long_double_upperbound")
+    }
+
+    fn db_idx_long_double_end(&mut self, code: Uint64, scope: Uint64, table: Uint64) -> thrift::Result<i32> {
+        self.idx_end(IdxKind::IdxLongDouble, code, scope, table)
+    }
+
+    fn set_action_return_value(&mut self, data: Vec<u8>) -> thrift::Result<()> {
+        self.context.return_value = data;
+        Ok(())
+    }
+
+    fn get_code_hash(&mut self, _account: Uint64, _struct_version: i64) -> thrift::Result<Vec<u8>> {
+        self.unsupported("get_code_hash")
+    }
+
+    fn get_block_num(&mut self) -> thrift::Result<i64> {
+        Ok(self.block_num as i64)
+    }
+
+    fn sha3(&mut self, _data: Vec<u8>, _keccak: i32) -> thrift::Result<Vec<u8>> {
+        self.unsupported("sha3")
+    }
+
+    fn blake2_f(&mut self, _rounds: i64, _state: Vec<u8>, _msg: Vec<u8>, _t0_offset: Vec<u8>, _t1_offset: Vec<u8>, _final: i32) -> thrift::Result<Vec<u8>> {
+        self.unsupported("blake2_f")
+    }
+
+    fn k1_recover(&mut self, _sig: Vec<u8>, _dig: Vec<u8>) -> thrift::Result<Vec<u8>> {
+        self.unsupported("k1_recover")
+    }
+
+    fn alt_bn128_add(&mut self, _op1: Vec<u8>, _op2: Vec<u8>) -> thrift::Result<Vec<u8>> {
+        self.unsupported("alt_bn128_add")
+    }
+
+    fn alt_bn128_mul(&mut self, _g1: Vec<u8>, _scalar: Vec<u8>) -> thrift::Result<Vec<u8>> {
+        self.unsupported("alt_bn128_mul")
+    }
+
+    fn alt_bn128_pair(&mut self, _pairs: Vec<u8>) -> thrift::Result<i32> {
+        self.unsupported("alt_bn128_pair")
+    }
+
+    fn mod_exp(&mut self, _base: Vec<u8>, _exp: Vec<u8>, _mod: Vec<u8>) -> thrift::Result<Vec<u8>> {
+        self.unsupported("mod_exp")
+    }
+}
+
+fn with_mock_chain<R>(f: impl FnOnce(&mut MockChain) -> R) -> R {
+    let mut client = get_vm_api_client();
+    f(client.mock_chain().expect("mock chain is not enabled"))
+}
+
+fn error_json(msg: &str) -> ChainTesterError {
+    let json = json!({"except": {"stack": [{"data": {"s": msg}}]}});
+    ChainTesterError { json: Some(json), error_string: None }
+}
+
+fn panic_message(err: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = err.downcast_ref::<&str>() {
+        return s.to_string();
+    }
+    if let Some(s) = err.downcast_ref::<String>() {
+        return s.clone();
+    }
+    "unknown error".into()
+}
+
+/// Parses the `index_position` of `get_table_rows`, 1 is the primary index.
+fn parse_index_position(position: &str) -> Option<usize> {
+    const NAMES: [&str; 10] = ["primary", "secondary", "tertiary", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth", "tenth"];
+    if position.is_empty() {
+        return Some(1);
+    }
+    if let Some(index) = NAMES.iter().position(|name| *name == position) {
+        return Some(index + 1);
+    }
+    position.parse::<usize>().ok().filter(|index| (1..=16).contains(index))
+}
+
+fn parse_key_type(key_type: &str) -> Option<IdxKind> {
+    match key_type {
+        "" | "i64" | "name" => Some(IdxKind::Idx64),
+        "i128" => Some(IdxKind::Idx128),
+        "i256" | "sha256" => Some(IdxKind::Idx256),
+        "float64" => Some(IdxKind::IdxDouble),
+        "float128" => Some(IdxKind::IdxLongDouble),
+        _ => None,
+    }
+}
+
+/// Parses a secondary bound into a little endian secondary value.
+/// `float128` and 256 bit keys are given as hex strings of their packed bytes.
+fn parse_secondary_bound(kind: IdxKind, bound: &str) -> Option<Vec<u8>> {
+    match kind {
+        IdxKind::Idx64 => parse_bound(bound).map(|n| n.to_le_bytes().to_vec()),
+        IdxKind::Idx128 => bound.parse::<u128>().ok().map(|n| n.to_le_bytes().to_vec()),
+        IdxKind::IdxDouble => bound.parse::<f64>().ok().map(|n| n.to_le_bytes().to_vec()),
+        IdxKind::Idx256 | IdxKind::IdxLongDouble => {
+            hex::decode(bound.trim_start_matches("0x")).ok().filter(|raw| raw.len() == kind.size())
+        }
+    }
+}
+
+/// Reverse of `parse_secondary_bound`, used for the `next_key` of a secondary query.
+fn format_secondary(kind: IdxKind, raw: &[u8]) -> String {
+    match kind {
+        IdxKind::Idx64 => u64::from_le_bytes(raw.try_into().unwrap()).to_string(),
+        IdxKind::Idx128 => u128::from_le_bytes(raw.try_into().unwrap()).to_string(),
+        IdxKind::IdxDouble => f64::from_le_bytes(raw.try_into().unwrap()).to_string(),
+        IdxKind::Idx256 | IdxKind::IdxLongDouble => hex::encode(raw),
+    }
+}
+
+/// Parses a bound of `get_table_rows`: a number, a symbol code or a name.
+fn parse_bound(bound: &str) -> Option<u64> {
+    if bound.is_empty() {
+        return None;
+    }
+    if let Ok(n) = bound.parse::<u64>() {
+        return Some(n);
+    }
+    if bound.len() <= 7 && bound.bytes().all(|c| c.is_ascii_uppercase()) {
+        let mut value = 0u64;
+        for (i, c) in bound.bytes().enumerate() {
+            value |= (c as u64) << (8 * i);
+        }
+        return Some(value);
+    }
+    Some(s2n(bound))
+}
+
+/// Runs native contracts against `MockChain`, with the same interface as `ChainTester`.
+///
+/// Creating a `MockTester` switches the VM API client to a fresh mock chain, dropping it switches back
+/// to the previous backend. The tester holds the lock of `get_test_mutex` as it shares the global client
+/// with `ChainTester`, so a test must not take the lock itself.
+pub struct MockTester {
+    previous_backend: Option<VMAPIBackend>,
+    previous_use_mock_chain: bool,
+    _guard: MutexGuard<'static, i32>,
+}
+
+impl MockTester {
+    pub fn new() -> Self {
+        let guard = get_test_mutex();
+        let previous_use_mock_chain = std::mem::replace(&mut crate::get_debugger_config().use_mock_chain, true);
+        let previous_backend = swap_vm_api_backend(Some(VMAPIBackend::Mock(MockChain::new())));
+        Self { previous_backend, previous_use_mock_chain, _guard: guard }
+    }
+
+    pub fn produce_block(&mut self) {
+        self.produce_block_ex(0);
+    }
+
+    pub fn produce_block_ex(&mut self, next_block_skip_seconds: i64) {
+        with_mock_chain(|chain| chain.produce_block(next_block_skip_seconds));
+    }
+
+    pub fn set_native_apply(&mut self, contract: &str, apply: Option<FnApply>) -> thrift::Result<()> {
+        with_mock_chain(|chain| chain.set_native_apply(s2n(contract), apply));
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_account(&mut self, _creator: &str, account: &str, _owner_key: &str, _active_key: &str, _ram_bytes: i64, _stake_net: i64, _stake_cpu: i64) -> Result<Value> {
+        with_mock_chain(|chain| chain.create_account(s2n(account)));
+        Ok(json!({}))
+    }
+
+    pub fn get_info(&mut self) -> Result<Value> {
+        let (block_num, block_time) = with_mock_chain(|chain| (chain.head_block_num(), chain.head_block_time()));
+        Ok(json!({"head_block_num": block_num, "head_block_time": block_time}))
+    }
+
+    pub fn push_action(&mut self, account: &str, action: &str, arguments: ActionArguments, permissions: &str) -> Result<Value> {
+        let data = match arguments {
+            ActionArguments::RawArgs(data) => data,
+            ActionArguments::JsonArgs(_) => {
+                return Err(ChainTesterError {
+                    json: None,
+                    error_string: Some("json arguments are not supported by the mock chain, pack the action data instead".into()),
+                });
+            }
+        };
+
+        let permissions: Value = serde_json::from_str(permissions).map_err(|err| {
+            ChainTesterError { json: None, error_string: Some(err.to_string()) }
+        })?;
+        let mut authorization = Vec::new();
+        if let Some(permissions) = permissions.as_object() {
+            for (actor, permission) in permissions {
+                authorization.push((s2n(actor), s2n(permission.as_str().unwrap_or("active"))));
+            }
+        }
+
+        let action = MockAction { account: s2n(account), name: s2n(action), authorization, data };
+        if !with_mock_chain(|chain| chain.accounts.contains_key(&action.account)) {
+            return Err(error_json(&format!("account {} does not exist", account)));
+        }
+
+        let snapshot = with_mock_chain(|chain| chain.db.clone());
+        let mut traces = Vec::new();
+        match self.execute_action(&action, 0, 0, &mut traces) {
+            Ok(()) => {
+                let before = snapshot.ram_usage();
+                let after = with_mock_chain(|chain| chain.db.ram_usage());
+                let accounts: BTreeSet<u64> = before.keys().chain(after.keys()).copied().collect();
+                let ram_deltas: Vec<Value> = accounts.into_iter()
+                    .map(|account| (account, after.get(&account).unwrap_or(&0) - before.get(&account).unwrap_or(&0)))
+                    .filter(|(_, delta)| *delta != 0)
+                    .map(|(account, delta)| json!({"account": n2s(account), "delta": delta}))
+                    .collect();
+                Ok(json!({"action_traces": traces, "account_ram_deltas": ram_deltas, "except": null}))
+            }
+            Err(msg) => {
+                with_mock_chain(|chain| chain.db = snapshot);
+                Err(error_json(&msg))
+            }
+        }
+    }
+
+    /// Runs an action on its receiver and all notified accounts, then its inline actions.
+    fn execute_action(&mut self, action: &MockAction, sender: u64, depth: usize, traces: &mut Vec<Value>) -> core::result::Result<(), String> {
+        if depth > MAX_INLINE_ACTION_DEPTH {
+            return Err("max inline action depth per transaction reached".into());
+        }
+
+        let mut recipients = vec![action.account];
+        let mut inline_actions = Vec::new();
+        let mut i = 0;
+        while i < recipients.len() {
+            let receiver = recipients[i];
+            let ctx = self.apply(receiver, action, sender)?;
+            for recipient in ctx.recipients {
+                if !recipients.contains(&recipient) {
+                    recipients.push(recipient);
+                }
+            }
+            for inline_action in ctx.inline_actions {
+                for (actor, permission) in &inline_action.authorization {
+                    if *actor != receiver && !action.authorization.contains(&(*actor, *permission)) {
+                        return Err(format!("missing authority of {}/{}", n2s(*actor), n2s(*permission)));
+                    }
+                }
+                inline_actions.push((receiver, inline_action));
+            }
+            traces.push(json!({
+                "receiver": n2s(receiver),
+                "act": action.to_json(),
+                "console": ctx.console,
+                "return_value_hex_data": hex::encode(&ctx.return_value),
+            }));
+            i += 1;
+        }
+
+        for (sender, inline_action) in inline_actions {
+            self.execute_action(&inline_action, sender, depth + 1, traces)?;
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, receiver: u64, action: &MockAction, sender: u64) -> core::result::Result<ApplyContext, String> {
+        let apply = {
+            let mut client = get_vm_api_client();
+            let apply = client.mock_chain().unwrap().begin_apply(receiver, action, sender);
+            client.set_in_apply(true);
+            apply
+        };
+
+        let ret = match apply {
+            Some(apply) => panic::catch_unwind(|| apply(receiver, action.account, action.name)),
+            None => Ok(()),
+        };
+
+        let mut client = get_vm_api_client();
+        client.set_in_apply(false);
+        let ctx = client.mock_chain().unwrap().end_apply();
+        match ret {
+            Err(_) if ctx.exited => Ok(ctx),
+            Err(err) => Err(ctx.error.unwrap_or_else(|| panic_message(&*err))),
+            Ok(()) => Ok(ctx),
+        }
+    }
+
+    /// Returns raw rows, only `json == false` is supported since the mock chain has no ABI.
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_table_rows(&mut self, json: bool, code: &str, scope: &str, table: &str, lower_bound: &str, upper_bound: &str, limit: i64) -> Result<Value> {
+        let params = GetTableRowsPrams {
+            json,
+            code,
+            scope,
+            table,
+            lower_bound,
+            upper_bound,
+            limit,
+            show_payer: true,
+            ..Default::default()
+        };
+        self.get_table_rows_ex(&params)
+    }
+
+    pub fn get_table_rows_ex(&mut self, params: &GetTableRowsPrams) -> Result<Value> {
+        let error = |msg: &str| ChainTesterError { json: None, error_string: Some(msg.into()) };
+        if params.json {
+            return Err(error("json table rows are not supported by the mock chain"));
+        }
+        let index_position = parse_index_position(params.index_position).ok_or_else(|| error("invalid index position"))?;
+        let code = s2n(params.code);
+        let scope = parse_bound(params.scope).unwrap_or(0);
+        let table = s2n(params.table);
+
+        // (primary, next key, payer, data)
+        let mut rows: Vec<(u64, String, u64, Vec<u8>)> = if index_position == 1 {
+            let lower_bound = parse_bound(params.lower_bound).unwrap_or(0);
+            let upper_bound = parse_bound(params.upper_bound).unwrap_or(u64::MAX);
+            with_mock_chain(|chain| chain.get_table_rows(code, scope, table, lower_bound, upper_bound))
+                .into_iter()
+                .map(|(primary, payer, data)| (primary, primary.to_string(), payer, data))
+                .collect()
+        } else {
+            let kind = parse_key_type(params.key_type).ok_or_else(|| error("invalid key type"))?;
+            let parse = |bound: &str| -> Result<Option<Vec<u8>>> {
+                if bound.is_empty() {
+                    return Ok(None);
+                }
+                parse_secondary_bound(kind, bound).map(Some).ok_or_else(|| error("invalid secondary bound"))
+            };
+            let lower_bound = parse(params.lower_bound)?;
+            let upper_bound = parse(params.upper_bound)?;
+            with_mock_chain(|chain| chain.get_table_rows_by_secondary(code, scope, table, index_position - 2, kind, lower_bound.as_deref(), upper_bound.as_deref()))
+                .into_iter()
+                .map(|(primary, secondary, payer, data)| (primary, format_secondary(kind, &secondary), payer, data))
+                .collect()
+        };
+
+        if params.reverse {
+            rows.reverse();
+        }
+        let limit = if params.limit <= 0 { usize::MAX } else { params.limit as usize };
+        let more = rows.len() > limit;
+        let next_key = if more { rows[limit].1.clone() } else { String::new() };
+        let rows: Vec<Value> = rows
+            .iter()
+            .take(limit)
+            .map(|(_, _, payer, data)| {
+                if params.show_payer {
+                    json!({"data": hex::encode(data), "payer": n2s(*payer)})
+                } else {
+                    json!(hex::encode(data))
+                }
+            })
+            .collect();
+        Ok(json!({"rows": rows, "more": more, "next_key": next_key}))
+    }
+
+    pub fn get_balance(&mut self, account: &str) -> u64 {
+        self.get_balance_ex(account, "eosio.token", "EOS")
+    }
+
+    pub fn get_balance_ex(&mut self, account: &str, token_account: &str, symbol: &str) -> u64 {
+        let ret = self.get_table_rows(false, token_account, account, "accounts", symbol, symbol, 1).unwrap();
+        let rows = ret["rows"].as_array().unwrap();
+        if rows.is_empty() {
+            return 0;
+        }
+        let balance = hex::decode(rows[0]["data"].as_str().unwrap()).unwrap();
+        u64::from_le_bytes(balance[0..8].try_into().unwrap())
+    }
+}
+
+impl Tester for MockTester {
+    fn push_action(&mut self, account: &str, action: &str, arguments: ActionArguments, permissions: &str) -> Result<Value> {
+        MockTester::push_action(self, account, action, arguments, permissions)
+    }
+
+    fn get_table_rows_ex(&mut self, params: &GetTableRowsPrams) -> Result<Value> {
+        MockTester::get_table_rows_ex(self, params)
+    }
+}
+
+impl Default for MockTester {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for MockTester {
+    fn drop(&mut self) {
+        swap_vm_api_backend(self.previous_backend.take());
+        crate::get_debugger_config().use_mock_chain = self.previous_use_mock_chain;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_tester_restores_backend() {
+        crate::get_debugger_config().use_mock_chain = false;
+        {
+            let _tester = MockTester::new();
+            assert!(crate::get_debugger_config().use_mock_chain);
+            assert!(get_vm_api_client().mock_chain().is_some());
+        }
+        assert!(!crate::get_debugger_config().use_mock_chain);
+        assert!(swap_vm_api_backend(None).is_none());
+
+        // the lock is released when the tester is dropped
+        drop(get_test_mutex());
+    }
+}
@@ -16,7 +16,6 @@ use thrift::TransportErrorKind;
 
 use crate::client::get_apply_map_mutex;
 use crate::interfaces::{Uint64};
-use crate::interfaces::TApplySyncClient;
 
 pub struct IPCServer<PRC, RTF, IPF, WTF, OPF>
 where
@@ -6,5 +6,7 @@ pub struct Action {
     /// The underlying Rust method item.
     pub item: syn::ImplItemMethod,
     pub is_notify: bool,
+    /// The contract a notification must come from, `None` accepts any contract.
+    pub notify_contract: Option<FixedString>,
     pub action_name: FixedString,
 }
@@ -87,6 +87,9 @@ pub enum AttributeArgKind {
     Singleton,
     Primary,
     Secondary,
+    Fields,
+    Version,
+    Migrate,
 }
 
 impl core::fmt::Display for AttributeArgKind {
@@ -100,6 +103,9 @@ impl core::fmt::Display for AttributeArgKind {
             Self::Variant => write!(f, "variant"),
             Self::Primary => write!(f, "primary"),
             Self::Secondary => write!(f, "secondary"),
+            Self::Fields => write!(f, "fields"),
+            Self::Version => write!(f, "version"),
+            Self::Migrate => write!(f, "migrate"),
             Self::Action => write!(f, "action"),
             Self::Notify => write!(f, "notify"),
         }
@@ -111,13 +117,22 @@ pub enum AttributeArg {
     MainStruct,
     SubStruct,
     Action(FixedString),
-    Notify,
+    /// `notify` or `notify = "contract::action"`, `*` matches any contract.
+    Notify(Option<FixedString>),
     Packer,
     Variant,
     Table(FixedString),
     Singleton,
     Primary,
-    Secondary
+    Secondary,
+    /// `secondary = "name"` on a table struct, a secondary key composed of the `fields` argument.
+    CompositeSecondary(FixedString),
+    /// `fields = "a,b"`, the comma separated fields of a composite secondary key.
+    Fields(FixedString),
+    /// `version = N` on a table struct, rows are prefixed with the version of the struct.
+    Version(u8),
+    /// `migrate = "action"` on a versioned table struct, the name of the generated migration action.
+    Migrate(FixedString),
 }
 
 impl AttributeArg {
@@ -127,13 +142,17 @@ impl AttributeArg {
             Self::MainStruct => AttributeArgKind::MainStruct,
             Self::SubStruct => AttributeArgKind::SubStruct,
             Self::Action(_) => AttributeArgKind::Action,
-            Self::Notify => AttributeArgKind::Notify,
+            Self::Notify(_) => AttributeArgKind::Notify,
             Self::Packer => AttributeArgKind::Packer,
             Self::Variant => AttributeArgKind::Variant,
             Self::Table(_) => AttributeArgKind::Table,
             Self::Singleton => AttributeArgKind::Singleton,
             Self::Primary => AttributeArgKind::Primary,
             Self::Secondary => AttributeArgKind::Secondary,
+            Self::CompositeSecondary(_) => AttributeArgKind::Secondary,
+            Self::Fields(_) => AttributeArgKind::Fields,
+            Self::Version(_) => AttributeArgKind::Version,
+            Self::Migrate(_) => AttributeArgKind::Migrate,
         }
     }
 }
@@ -187,6 +206,73 @@ impl TryFrom<syn::NestedMeta> for AttributeFrag {
                             }
                             return Err(format_err!(name_value, "expected `str` value type for `flag` in #[chain(table = name)]"))
                         }
+
+                        if name_value.path.is_ident("secondary") {
+                            if let syn::Lit::Str(lit_str) = &name_value.lit {
+                                let value = lit_str.value();
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::CompositeSecondary(FixedString::new(&value)),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected `str` value type for `flag` in #[chain(secondary = name)]"))
+                        }
+
+                        if name_value.path.is_ident("fields") {
+                            if let syn::Lit::Str(lit_str) = &name_value.lit {
+                                let value = lit_str.value();
+                                if value.len() > 64 {
+                                    return Err(format_err!(name_value, "fields list is too long"))
+                                }
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::Fields(FixedString::new(&value)),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected `str` value type for `flag` in #[chain(fields = \"a,b\")]"))
+                        }
+
+                        if name_value.path.is_ident("version") {
+                            if let syn::Lit::Int(lit_int) = &name_value.lit {
+                                return match lit_int.base10_parse::<u8>() {
+                                    Ok(version) if version > 0 => Ok(AttributeFrag {
+                                        ast: meta,
+                                        arg: AttributeArg::Version(version),
+                                    }),
+                                    _ => Err(format_err!(name_value, "table version must be in range 1..=255")),
+                                }
+                            }
+                            return Err(format_err!(name_value, "expected integer value type for `flag` in #[chain(version = N)]"))
+                        }
+
+                        if name_value.path.is_ident("migrate") {
+                            if let syn::Lit::Str(lit_str) = &name_value.lit {
+                                let value = lit_str.value();
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::Migrate(FixedString::new(&value)),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected `str` value type for `flag` in #[chain(migrate = name)]"))
+                        }
+
+                        if name_value.path.is_ident("notify") {
+                            if let syn::Lit::Str(lit_str) = &name_value.lit {
+                                let value = lit_str.value();
+                                match value.split_once("::") {
+                                    Some((contract, action)) if !contract.is_empty() && !action.is_empty() => {
+                                        return Ok(AttributeFrag {
+                                            ast: meta,
+                                            arg: AttributeArg::Notify(Some(FixedString::new(&value))),
+                                        })
+                                    }
+                                    _ => {
+                                        return Err(format_err!(name_value, "expected `contract::action` in #[chain(notify = \"contract::action\")]"))
+                                    }
+                                }
+                            }
+                            return Err(format_err!(name_value, "expected `str` value type for `flag` in #[chain(notify = \"contract::action\")]"))
+                        }
                         Err(format_err_spanned!(
                             meta,
                             "unknown chain attribute argument (name = value)",
@@ -205,7 +291,7 @@ impl TryFrom<syn::NestedMeta> for AttributeFrag {
                                 "variant" => Ok(AttributeArg::Variant),
                                 "primary" => Ok(AttributeArg::Primary),
                                 "secondary" => Ok(AttributeArg::Secondary),
-                                "notify" => Ok(AttributeArg::Notify),
+                                "notify" => Ok(AttributeArg::Notify(None)),
                                 _ => Err(format_err_spanned!(
                                     meta, "unknown chain attribute {}", ident
                                 ))
@@ -470,12 +556,30 @@ impl ChainAttribute {
         }
 
         if let AttributeArg::Table(_) = self.args[0].kind() {
-            if let AttributeArg::Singleton = self.args[1].kind() {
-                return true;
-            }    
+            return self.args().any(|arg| matches!(arg.kind(), AttributeArg::Singleton));
         }
 
-        return true;
+        return false;
+    }
+
+    /// Returns `N` of `#[chain(table = "name", version = N)]`.
+    pub fn table_version(&self) -> Option<u8> {
+        self.args().find_map(|arg| {
+            if let AttributeArg::Version(version) = arg.kind() {
+                return Some(*version)
+            }
+            None
+        })
+    }
+
+    /// Returns the action name of `#[chain(table = "name", version = N, migrate = "action")]`.
+    pub fn migrate_action(&self) -> Option<FixedString> {
+        self.args().find_map(|arg| {
+            if let AttributeArg::Migrate(name) = arg.kind() {
+                return Some(*name)
+            }
+            None
+        })
     }
 
     pub fn action_name(&self) -> Option<FixedString> {
@@ -484,13 +588,42 @@ impl ChainAttribute {
                 return Some(*name)
             }
             None
-        })
+        }).or_else(|| self.notify_source().map(|(_, action)| FixedString::new(&action)))
     }
 
     pub fn is_notify(&self) -> bool {
         self.args
             .iter()
-            .any(|arg| matches!(arg.kind(), AttributeArg::Notify))
+            .any(|arg| matches!(arg.kind(), AttributeArg::Notify(_)))
+    }
+
+    /// Returns `(name, fields)` of `#[chain(secondary = "name", fields = "a,b")]`.
+    pub fn composite_secondary(&self) -> Option<(String, Vec<String>)> {
+        let name = self.args().find_map(|arg| {
+            if let AttributeArg::CompositeSecondary(name) = arg.kind() {
+                return Some(name.str())
+            }
+            None
+        })?;
+        let fields = self.args().find_map(|arg| {
+            if let AttributeArg::Fields(fields) = arg.kind() {
+                return Some(fields.str().split(',').map(|field| field.trim().to_string()).collect())
+            }
+            None
+        }).unwrap_or_default();
+        Some((name, fields))
+    }
+
+    /// Returns `(contract, action)` of `notify = "contract::action"`.
+    pub fn notify_source(&self) -> Option<(String, String)> {
+        self.args().find_map(|arg| {
+            if let AttributeArg::Notify(Some(source)) = arg.kind() {
+                let source = source.str();
+                let (contract, action) = source.split_once("::")?;
+                return Some((contract.into(), action.into()))
+            }
+            None
+        })
     }
 
 }
@@ -19,7 +19,7 @@ use crate::{
     action::Action,
     table::Table,
     attrs,
-    // FixedString,
+    FixedString,
     name::{
         s2n,
         is_name_valid,
@@ -28,6 +28,18 @@ use crate::{
     attrs::Attrs as _,
 };
 
+/// A secondary index of a table, either a `#[chain(secondary)]` field
+/// or a key composed of several fields with `#[chain(secondary = "name", fields = "a,b")]`.
+struct SecondaryKey {
+    /// field name or name of the composite key
+    ident: syn::Ident,
+    ty: syn::Type,
+    /// `Idx64`, `Idx128`, ...
+    secondary_type: &'static str,
+    /// fields of a composite key, empty for a field key
+    fields: Vec<syn::Field>,
+}
+
 /// An contract definition consisting of the configuration and module.
 
 #[derive(Debug, PartialEq, Eq)]
@@ -201,10 +213,13 @@ impl Contract {
                     }
 
                     if chain_attrs.len() > 1 {
-                        return Err(format_err_spanned!(
-                            x,
-                            "more than one chain attribute specified to struct {}", x.ident
-                        ));
+                        // a table can declare composite secondary keys in additional attributes
+                        if chain_attrs[0].table_name().is_none() || chain_attrs[1..].iter().any(|attr| attr.composite_secondary().is_none()) {
+                            return Err(format_err_spanned!(
+                                x,
+                                "more than one chain attribute specified to struct {}", x.ident
+                            ));
+                        }
                     }
 
                     let attr = &chain_attrs[0];
@@ -237,8 +252,9 @@ impl Contract {
                             }
                             self.packers.push(x.clone());
                             for field in &x.fields {
-                                let (type_name, _) = Self::extract_type(&field.ty)?;
-                                arg_types.insert(type_name.clone(), type_name);
+                                for (type_name, _) in Self::extract_types(&field.ty)? {
+                                    arg_types.insert(type_name.clone(), type_name);
+                                }
                             };
                         }
                         _ => {
@@ -266,11 +282,34 @@ impl Contract {
                                         "dumplicated table name: {}", name.str()
                                     ));
                                 }
+                                if attr.table_version().is_some() && attr.is_singleton() {
+                                    return Err(format_err_spanned!(
+                                        attr.args().next().unwrap().ast,
+                                        "version is not supported by a singleton: {}", name.str()
+                                    ));
+                                }
+                                let migrate_action = attr.migrate_action();
+                                if let Some(action_name) = &migrate_action {
+                                    if attr.table_version().is_none() || attr.is_singleton() {
+                                        return Err(format_err_spanned!(
+                                            attr.args().next().unwrap().ast,
+                                            "migrate action requires a versioned table which is not a singleton: {}", name.str()
+                                        ));
+                                    }
+                                    if !is_name_valid(&action_name.str()) || action_name.length == 0 {
+                                        return Err(format_err_spanned!(
+                                            attr.args().next().unwrap().ast,
+                                            "migrate action name is empty or contains invalid character(s). valid characters are a-z and 1-5.: {}", action_name.str()
+                                        ));
+                                    }
+                                }
                                 self.tables.push(
                                     Table {
                                         item: x_backup,
                                         table_name: name,
                                         singleton: attr.is_singleton(),
+                                        version: attr.table_version(),
+                                        migrate_action,
                                     }
                                 )
                             }
@@ -301,8 +340,31 @@ impl Contract {
                                             "action name is empty or contains invalid character(s). valid characters are a-z and 1-5.: {}", name.str()
                                         ));
                                     }
+
+                                    let mut notify_contract = None;
+                                    if let Some((contract, action)) = attr.notify_source() {
+                                        if action != name.str() {
+                                            return Err(format_err_spanned!(
+                                                attr.args().next().unwrap().ast,
+                                                "notify action {} does not match action name {}", action, name.str()
+                                            ));
+                                        }
+                                        if contract != "*" {
+                                            if !is_name_valid(&contract) {
+                                                return Err(format_err_spanned!(
+                                                    attr.args().next().unwrap().ast,
+                                                    "invalid notify contract name: {}", contract
+                                                ));
+                                            }
+                                            notify_contract = Some(FixedString::new(&contract));
+                                        }
+                                    }
+
+                                    // an action and notify handlers of the same action from different contracts can coexist
                                     if self.actions.iter().any(|action| {
-                                        action.action_name == name
+                                        action.action_name == name &&
+                                        action.is_notify == attr.is_notify() &&
+                                        action.notify_contract == notify_contract
                                     }) {
                                         return Err(format_err_spanned!(
                                             attr.args().next().unwrap().ast,
@@ -323,16 +385,24 @@ impl Contract {
                                                         ));
                                                     }
                                                 }
-                                                let (type_name, _) = Self::extract_type(&x.ty)?;
-                                                arg_types.insert(type_name.clone(), type_name);
+                                                for (type_name, _) in Self::extract_types(&x.ty)? {
+                                                    arg_types.insert(type_name.clone(), type_name);
+                                                }
                                             }
                                         }
                                     };
 
+                                    if let syn::ReturnType::Type(_, ty) = &method_item.sig.output {
+                                        for (type_name, _) in Self::extract_types(ty)? {
+                                            arg_types.insert(type_name.clone(), type_name);
+                                        }
+                                    }
+
                                     self.actions.push(
                                         Action{
                                             item: method_item.clone(),
                                             is_notify: attr.is_notify(),
+                                            notify_contract: notify_contract,
                                             action_name: name,
                                         }
                                     )
@@ -385,16 +455,28 @@ impl Contract {
         for (ty, _) in arg_types {
             self.add_packer(&ty)?;
         }
+
+        for table in &self.tables {
+            if let Some(action_name) = &table.migrate_action {
+                if self.actions.iter().any(|action| action.action_name == *action_name) ||
+                    self.tables.iter().filter(|t| t.migrate_action == table.migrate_action).count() > 1 {
+                    return Err(format_err_spanned!(
+                        table.item,
+                        "dumplicated action name: {}", action_name.str()
+                    ));
+                }
+            }
+        }
         return Ok(())
         
     }
     
-    fn is_primitive_type(name: &str) -> bool {
+    pub(crate) fn is_primitive_type(name: &str) -> bool {
         match name {
             "bool" | "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" | "f32" | "f64" | "i128" | "u128" |
             "String" |
             "Varint32" | "VarUint32" | "Float128" | "TimePoint" | "TimePointSec" |
-            "BlockTimeStampType" | "Name" | "Checksum160" | "Checksum256" | "Uint256" |
+            "BlockTimeStampType" | "Microseconds" | "Name" | "Checksum160" | "Checksum256" | "Uint256" |
             "Checksum512" | "PublicKey" | "Signature" | "Symbol" | "SymbolCode" | "Asset" |
             "ExtendedAsset" => {
                 return true;
@@ -454,8 +536,9 @@ impl Contract {
                     }
 
                     for field in &x.fields {
-                        let name = Self::get_type_name(field)?;
-                        names.insert(name, true);
+                        for (name, _) in Self::extract_types(&field.ty)? {
+                            names.insert(name, true);
+                        }
                     }
                     break;
                 }
@@ -478,8 +561,9 @@ impl Contract {
                                     ));
                                 }
                                 let field = x.unnamed.last().unwrap();
-                                let name = Self::get_type_name(field)?;
-                                names.insert(name, true);
+                                for (name, _) in Self::extract_types(&field.ty)? {
+                                    names.insert(name, true);
+                                }
                             }
                             _ => {
                                 return Err(format_err_spanned!(
@@ -532,6 +616,54 @@ impl Contract {
                 )
             });
 
+            let version = self.tables.iter().find(|table| table.item.ident == *ident).and_then(|table| table.version);
+            if let Some(version) = version {
+                let error_lit = proc_macro2::Literal::string(&format!("{}: row version is newer than the struct", ident));
+                return quote_spanned!(span =>
+                    impl ::rust_chain::serializer::Packer for #ident {
+                        fn size(&self) -> usize {
+                            let mut _size: usize = 1;
+                            #( #get_size )*
+                            return _size;
+                        }
+
+                        fn pack(&self, enc: &mut Encoder) -> usize {
+                            let pos = enc.get_size();
+                            #version.pack(enc);
+                            #( #serialize )*
+                            enc.get_size() - pos
+                        }
+
+                        fn unpack(&mut self, data: &[u8]) -> usize {
+                            let mut version: u8 = 0;
+                            version.unpack(data);
+                            if version != #version {
+                                // rows of older versions are upgraded on read, `TableI64` passes the rows
+                                // written before the table was versioned as version 0
+                                rust_chain::check(version < #version, #error_lit);
+                                let (row, size) = <#ident as ::rust_chain::db::MigrateRow>::migrate(version, &data[1..]);
+                                *self = row;
+                                return 1 + size;
+                            }
+                            return 1 + self.unpack_fields(&data[1..]);
+                        }
+                    }
+
+                    impl #ident {
+                        /// Version of the rows packed by this struct.
+                        pub const TABLE_VERSION: u8 = #version;
+
+                        /// Unpacks the fields of a row without the version prefix,
+                        /// e.g. a row written by a plain `#[chain(table = "...")]` struct with the same fields.
+                        pub fn unpack_fields(&mut self, data: &[u8]) -> usize {
+                            let mut dec = ::rust_chain::serializer::Decoder::new(data);
+                            #( #deserialize )*
+                            return dec.get_pos();
+                        }
+                    }
+                );
+            }
+
             let packed = quote_spanned!(span =>
                 impl ::rust_chain::serializer::Packer for #ident {
                     fn size(&self) -> usize {
@@ -562,12 +694,12 @@ impl Contract {
         }
     }
 
-    fn generate_action_structs(&self) -> TokenStream2 {
+    fn generate_action_structs(&self) -> Result<TokenStream2, syn::Error> {
         let action_structs_code = self.actions.iter().map(|action|{
             let item = &action.item;
             let span = item.span();
             // let ident = &item.sig.ident;
-            let struct_name = action.action_name.str();
+            let struct_name = self.action_struct_name(action);
             let struct_name_ident = proc_macro2::Ident::new(&struct_name, proc_macro2::Span::call_site());
 
             let fields = item.sig.inputs.iter().filter(|arg| {
@@ -578,6 +710,13 @@ impl Contract {
                 }
             }).map(|item|{
                 if let syn::FnArg::Typed(tp) = item {
+                    // borrowed arguments are described by the owned types packed the same way
+                    if let Some(owned_ty) = Self::to_owned_type(&tp.ty) {
+                        let pat = &tp.pat;
+                        return quote!{
+                            pub #pat: #owned_ty
+                        };
+                    }
                     quote!{
                         pub #tp
                     }
@@ -605,7 +744,7 @@ impl Contract {
             let deserialize = item.sig.inputs.iter().map(|arg| {
                 if let syn::FnArg::Typed(pat_type) = arg {
                     let span = arg.span();
-                    let ty = &*pat_type.ty;
+                    let ty = Self::to_owned_type(&pat_type.ty).unwrap_or_else(|| (*pat_type.ty).clone());
                     if let syn::Pat::Ident(x) = &*pat_type.pat {
                         quote_spanned!(span=>
                             dec.unpack::<#ty>(&mut self.#x);
@@ -662,19 +801,73 @@ impl Contract {
                 }
             );
 
-            quote! {
-                #[cfg_attr(feature = "std", derive(::rust_chain::eosio_scale_info::TypeInfo))]
-                #[cfg_attr(feature = "std", scale_info(crate = ::rust_chain::eosio_scale_info))]
+            let struct_code = quote! {
                 #[derive(Default)]
                 pub struct #struct_name_ident {
                     # ( #fields ), *
                 }
+            };
+            let type_info = crate::derive::generate_struct_type_info(&syn::parse2(struct_code.clone())?)?;
+            Ok(quote! {
+                #struct_code
+                #type_info
                 #packed
+            })
+        }).collect::<Result<Vec<_>, syn::Error>>()?;
+
+        let migrate_structs_code = self.tables.iter().filter(|table| table.migrate_action.is_some()).map(|table| {
+            let action_name = table.migrate_action.unwrap().str();
+            let struct_name_ident = proc_macro2::Ident::new(&action_name, proc_macro2::Span::call_site());
+            // a module keeps `Name` as the field type for the ABI without clashing with the imports of the contract
+            let mod_ident = proc_macro2::Ident::new(&format!("__{}_action", action_name), proc_macro2::Span::call_site());
+            quote! {
+                #[allow(non_camel_case_types)]
+                mod #mod_ident {
+                    use rust_chain::Name;
+                    use rust_chain::serializer::{
+                        Packer as _,
+                        Encoder,
+                    };
+
+                    /// Upgrades the rows of a versioned table in `scope`, scanning at most `limit` rows from the primary key `start`.
+                    #[cfg_attr(feature = "std", derive(::rust_chain::eosio_scale_info::TypeInfo))]
+                    #[cfg_attr(feature = "std", scale_info(crate = ::rust_chain::eosio_scale_info))]
+                    #[derive(Default)]
+                    pub struct #struct_name_ident {
+                        pub scope: Name,
+                        pub start: u64,
+                        pub limit: u32,
+                    }
+
+                    impl ::rust_chain::serializer::Packer for #struct_name_ident {
+                        fn size(&self) -> usize {
+                            return self.scope.size() + self.start.size() + self.limit.size();
+                        }
+
+                        fn pack(&self, enc: &mut Encoder) -> usize {
+                            let pos = enc.get_size();
+                            self.scope.pack(enc);
+                            self.start.pack(enc);
+                            self.limit.pack(enc);
+                            enc.get_size() - pos
+                        }
+
+                        fn unpack<'a>(&mut self, data: &'a [u8]) -> usize {
+                            let mut dec = ::rust_chain::serializer::Decoder::new(data);
+                            dec.unpack(&mut self.scope);
+                            dec.unpack(&mut self.start);
+                            dec.unpack(&mut self.limit);
+                            return dec.get_pos();
+                        }
+                    }
+                }
+                pub use #mod_ident::#struct_name_ident;
             }
         });
-        quote!{
+        Ok(quote!{
             #( #action_structs_code ) *
-        }
+            #( #migrate_structs_code ) *
+        })
     }
 
     fn generate_tables_code(&self) -> Result<TokenStream2, syn::Error> {
@@ -684,6 +877,14 @@ impl Contract {
             let span = item.span();
             let table_ident = &item.ident;
             let mut primary_impl: Option<TokenStream2> = None;
+            let table_version = match table.version {
+                Some(version) => quote!{
+                    fn table_version() -> u8 {
+                        return #version;
+                    }
+                },
+                None => quote!{},
+            };
 
             for field in &item.fields {
                 let (chain_attrs, _) = attrs::partition_attributes(field.attrs.clone())?;
@@ -715,6 +916,7 @@ impl Contract {
                                 fn get_primary(&self) -> u64 {
                                     return self.#field_ident.get_primary();
                                 }
+                                #table_version
                             }
                         ))
                     }
@@ -738,63 +940,62 @@ impl Contract {
                 }
             }
 
-            let mut secondary_fields: Vec<(attrs::AttributeArg, syn::Field)> = Vec::new();
-
-            for field in &item.fields {
-                let (chain_attrs, _) = attrs::partition_attributes(field.attrs.clone())?;
-                if chain_attrs.len() == 0 {
-                    continue;
-                }
-
-                let attr = &chain_attrs[0];
-                if attr.args().len() == 0 {
-                    return Err(format_err_spanned!(
-                        field,
-                        "no chain attribute specified",
-                    ));
-                }
-
-                let first_attr = attr.args().next().unwrap();
-                match first_attr.arg {
-                    attrs::AttributeArg::Primary => {},
-                    attrs::AttributeArg::Secondary => {
-                        if !Self::is_secondary_type(&field.ty) {
-                            return Err(format_err_spanned!(
-                                first_attr.ast,
-                                "invalid secondary type, only  \"u64\", \"u128\", \"Uint256\", \"f64\" or \"Float128\" supported"
-                            ));
-                        }
-                        secondary_fields.push((first_attr.arg.clone(), field.clone()));
-                    }
-                    _ => {
-                        return Err(format_err_spanned!(
-                            first_attr.ast,
-                            "invalid attribute argument"
-                        ));
-                    }
-                }
-            };
+            let secondary_keys = Self::get_secondary_keys(table)?;
 
             let secondary_impls;
             if !self.has_secondary_value_interface_trait(&item.ident.to_string()) {
-                let secondary_getter_impls = secondary_fields.iter()
+                let secondary_getter_impls = secondary_keys.iter()
                 .enumerate()
-                .map(|(index, (_, field))|{
-                    let field_ident = field.ident.as_ref().unwrap();
+                .map(|(index, key)|{
+                    let key_ident = &key.ident;
+                    if key.fields.is_empty() {
+                        return quote! {
+                            if i == #index {
+                                return self.#key_ident.into();
+                            }
+                        }
+                    }
+                    let field_idents = key.fields.iter().map(|field| field.ident.as_ref().unwrap());
                     return quote! {
                         if i == #index {
-                            return self.#field_ident.into();
+                            return ::rust_chain::db::pack_composite_key(&[#( ::rust_chain::db::SecondaryKeyPart::to_key_part(&self.#field_idents) ),*]);
                         }
                     }
                 });
     
-                let secondary_setter_impls = secondary_fields.iter()
+                let secondary_setter_impls = secondary_keys.iter()
                 .enumerate()
-                .map(|(index, (_attr_arg, field))|{
-                    let field_ident = field.ident.as_ref().unwrap();
+                .map(|(index, key)|{
+                    let key_ident = &key.ident;
+                    if key.fields.is_empty() {
+                        return quote!{
+                            if i == #index {
+                                self.#key_ident = value.into();
+                            }
+                        }
+                    }
+                    let field_idents = key.fields.iter().map(|field| field.ident.as_ref().unwrap());
+                    let part_indices = 0..key.fields.len();
                     return quote!{
                         if i == #index {
-                            self.#field_ident = value.into();
+                            let parts = ::rust_chain::db::unpack_composite_key(value);
+                            #( self.#field_idents = ::rust_chain::db::SecondaryKeyPart::from_key_part(parts[#part_indices]); )*
+                        }
+                    }
+                });
+
+                let composite_key_fns = secondary_keys.iter()
+                .filter(|key| !key.fields.is_empty())
+                .map(|key| {
+                    let key_fn_ident = syn::Ident::new(&format!("{}_key", key.ident), key.ident.span());
+                    let key_ty = &key.ty;
+                    let field_idents: Vec<&syn::Ident> = key.fields.iter().map(|field| field.ident.as_ref().unwrap()).collect();
+                    let field_types = key.fields.iter().map(|field| &field.ty);
+                    return quote!{
+                        /// Builds the composite secondary key from its fields, for lookups on the index.
+                        #[allow(dead_code)]
+                        pub fn #key_fn_ident(#( #field_idents: #field_types ),*) -> #key_ty {
+                            return ::rust_chain::db::pack_composite_key(&[#( ::rust_chain::db::SecondaryKeyPart::to_key_part(&#field_idents) ),*]).into();
                         }
                     }
                 });
@@ -812,12 +1013,16 @@ impl Contract {
                             #( #secondary_setter_impls )*
                         }
                     }
+
+                    impl #table_ident {
+                        #( #composite_key_fns )*
+                    }
                 );
             } else {
                 secondary_impls = quote!{};
             }
 
-            let mi_impls = self.generate_mi_impls(table, &secondary_fields);
+            let mi_impls = self.generate_mi_impls(table, &secondary_keys);
 
             if !table.singleton {
                 if self.has_primary_value_interface_trait(&item.ident.to_string()) {
@@ -849,19 +1054,103 @@ impl Contract {
         });
     }
 
-    fn generate_mi_impls(&self, table: &Table, secondary_fields: &Vec<(attrs::AttributeArg, syn::Field)>) -> TokenStream2 {
+    fn get_secondary_keys(table: &Table) -> Result<Vec<SecondaryKey>, syn::Error> {
+        let mut secondary_keys: Vec<SecondaryKey> = Vec::new();
+
+        for field in &table.item.fields {
+            let (chain_attrs, _) = attrs::partition_attributes(field.attrs.clone())?;
+            if chain_attrs.len() == 0 {
+                continue;
+            }
+
+            let attr = &chain_attrs[0];
+            if attr.args().len() == 0 {
+                return Err(format_err_spanned!(
+                    field,
+                    "no chain attribute specified",
+                ));
+            }
+
+            let first_attr = attr.args().next().unwrap();
+            match first_attr.arg {
+                attrs::AttributeArg::Primary => {},
+                attrs::AttributeArg::Secondary => {
+                    if !Self::is_secondary_type(&field.ty) {
+                        return Err(format_err_spanned!(
+                            first_attr.ast,
+                            "invalid secondary type, only  \"u64\", \"u128\", \"Uint256\", \"f64\" or \"Float128\" supported"
+                        ));
+                    }
+                    secondary_keys.push(SecondaryKey {
+                        ident: field.ident.clone().unwrap(),
+                        ty: field.ty.clone(),
+                        secondary_type: Self::to_secondary_type(&field.ty).unwrap(),
+                        fields: Vec::new(),
+                    });
+                }
+                _ => {
+                    return Err(format_err_spanned!(
+                        first_attr.ast,
+                        "invalid attribute argument"
+                    ));
+                }
+            }
+        }
+
+        // composite keys are declared on the struct, after `#[chain(table = "...")]`
+        let (chain_attrs, _) = attrs::partition_attributes(table.item.attrs.clone())?;
+        for attr in chain_attrs.iter().skip(1) {
+            let (name, field_names) = attr.composite_secondary().unwrap();
+            let ast = &attr.args().next().unwrap().ast;
+            if syn::parse_str::<syn::Ident>(&name).is_err() {
+                return Err(format_err_spanned!(ast, "invalid secondary key name: {}", name));
+            }
+            let ident = syn::Ident::new(&name, ast.span());
+            if secondary_keys.iter().any(|key| key.ident == ident) {
+                return Err(format_err_spanned!(ast, "duplicated secondary key name: {}", name));
+            }
+            if field_names.len() == 0 || field_names.len() > 4 {
+                return Err(format_err_spanned!(ast, "a composite secondary key must have 1 to 4 fields"));
+            }
+
+            let mut fields: Vec<syn::Field> = Vec::new();
+            for field_name in &field_names {
+                match table.item.fields.iter().find(|field| field.ident.as_ref().unwrap() == field_name) {
+                    Some(field) => fields.push(field.clone()),
+                    None => {
+                        return Err(format_err_spanned!(ast, "field {} not found in struct {}", field_name, table.item.ident));
+                    }
+                }
+            }
+
+            let (ty, secondary_type): (syn::Type, &'static str) = match fields.len() {
+                1 => (syn::parse_quote!(u64), "Idx64"),
+                2 => (syn::parse_quote!(u128), "Idx128"),
+                _ => (syn::parse_quote!(::rust_chain::Uint256), "Idx256"),
+            };
+            secondary_keys.push(SecondaryKey {
+                ident,
+                ty,
+                secondary_type,
+                fields,
+            });
+        }
+        return Ok(secondary_keys);
+    }
+
+    fn generate_mi_impls(&self, table: &Table, secondary_keys: &Vec<SecondaryKey>) -> TokenStream2 {
         let table_name = table.table_name.str();
 
         let span = table.item.span();
         let table_ident = &table.item.ident;
 
 
-        let len_secondary = secondary_fields.len();
+        let len_secondary = secondary_keys.len();
 
-        let secondary_types = secondary_fields
+        let secondary_types = secondary_keys
             .iter()
-            .map(|(_, field)| {
-                let secondary_type_name = Self::to_secondary_type(&field.ty);
+            .map(|key| {
+                let secondary_type_name = Some(key.secondary_type);
                 match secondary_type_name {
                     Some("Idx64") => {
                         return quote! {
@@ -894,12 +1183,12 @@ impl Contract {
                 }
             });
 
-            let get_idx_db_funcs = secondary_fields
+            let get_idx_db_funcs = secondary_keys
                 .iter()
                 .enumerate()
-                .map(|(i, (_, field))| {
+                .map(|(i, key)| {
                     let idx_type: usize;
-                    let secondary_type = Self::to_secondary_type(&field.ty);
+                    let secondary_type = Some(key.secondary_type);
                     match secondary_type {
                         Some("Idx64") => { idx_type = 0; }
                         Some("Idx128") => { idx_type = 1; }
@@ -913,15 +1202,20 @@ impl Contract {
 
                     match secondary_type {
                         Some("Idx64") | Some("Idx128") | Some("Idx256") | Some("IdxF64") | Some("IdxF128") => {
-                            let span = field.span();
-                            let ty = &field.ty;
-                            let get_idx_method_name = String::from("get_idx_by_") + &field.ident.as_ref().unwrap().to_string();
+                            let span = key.ident.span();
+                            let ty = &key.ty;
+                            let key_name = key.ident.to_string();
+                            let get_idx_method_name = String::from("get_idx_by_") + &key_name;
                             let get_idx_method_ident = syn::Ident::new(&get_idx_method_name, span);
 
-                            let update_idx_method_name = String::from("update_") + &field.ident.as_ref().unwrap().to_string();
+                            let update_idx_method_name = String::from("update_") + &key_name;
                             let update_idx_method_ident = syn::Ident::new(&update_idx_method_name, span);
 
-                            let error_message = String::from("invalid db index on update: ") + &field.ident.as_ref().unwrap().to_string();
+                            let error_message = String::from("invalid db index on update: ") + &key_name;
+
+                            // a composite key is accessed by its own name, e.g. `by_owner_time()`
+                            let index_method_name = if key.fields.is_empty() { String::from("by_") + &key_name } else { key_name.clone() };
+                            let index_method_ident = syn::Ident::new(&index_method_name, span);
 
                             return quote_spanned!(span =>
                                 #[allow(dead_code)]
@@ -929,6 +1223,11 @@ impl Contract {
                                     return ::rust_chain::db::IdxTableProxy::<#ty, #idx_type>::new(self.mi.get_idx_db(#i));
                                 }
 
+                                #[allow(dead_code)]
+                                fn #index_method_ident(&self) -> ::rust_chain::mi::SecondaryIndex<'_, #ty, #table_ident, #idx_type> {
+                                    return ::rust_chain::mi::SecondaryIndex::new(&self.mi, #i);
+                                }
+
                                 #[allow(dead_code)]
                                 fn #update_idx_method_ident(&self, it: &::rust_chain::db::SecondaryIterator, value: #ty, payer: rust_chain::Name) {
                                     rust_chain::check(it.db_index == #i, #error_message);
@@ -940,12 +1239,24 @@ impl Contract {
                     };
                 });
 
+            let migrate_func = match table.version {
+                Some(version) => quote!{
+                    /// Rewrites with `payer` the rows packed by an older version of the table struct, `SAME_PAYER` keeps the payers,
+                    /// scanning at most `limit` rows from the primary key `start`.
+                    /// Returns the number of rewritten rows and the primary key to continue from.
+                    pub fn migrate(&self, payer: rust_chain::Name, start: u64, limit: u32) -> (u32, Option<u64>) {
+                        return self.mi.migrate(#version, payer, start, limit);
+                    }
+                },
+                None => quote!{},
+            };
+
             let mi_name = table_ident.to_string() + "MultiIndex";
             let mi_ident = syn::Ident::new(&mi_name, span);
             if table.singleton {
                 return quote_spanned!(span =>
                     pub struct #mi_ident {
-                        mi: ::rust_chain::mi::MultiIndex<#table_ident>
+                        db: ::rust_chain::db::Singleton<#table_ident>
                     }
                 
                     #[allow(dead_code)]
@@ -953,28 +1264,38 @@ impl Contract {
                         ///
                         pub fn new(code: rust_chain::Name, scope: rust_chain::Name, table: rust_chain::Name) -> Self {
                             Self {
-                                mi: ::rust_chain::mi::MultiIndex::<#table_ident>::new(code, scope, table, &[rust_chain::db::SecondaryType::Idx64; 0]),
+                                db: ::rust_chain::db::Singleton::<#table_ident>::new(code, scope, table),
                             }
                         }
 
                         pub fn new_table(code: rust_chain::Name, scope: rust_chain::Name, table: rust_chain::Name) -> Self {
                             Self {
-                                mi: ::rust_chain::mi::MultiIndex::<#table_ident>::new(code, scope, table, &[rust_chain::db::SecondaryType::Idx64; 0]),
+                                db: ::rust_chain::db::Singleton::<#table_ident>::new(code, scope, table),
                             }
                         }
 
-                        fn get(&self) -> Option<#table_ident> {
-                            let it = self.mi.find(rust_chain::Name::new(#table_name).value());
-                            return self.mi.get(&it);
+                        pub fn exists(&self) -> bool {
+                            return self.db.exists();
                         }
 
-                        fn set(&self, value: &#table_ident, payer: rust_chain::Name) {
-                            let it = self.mi.find(rust_chain::Name::new(#table_name).value());
-                            if it.is_ok() {
-                                self.mi.update(&it, value, payer);
-                            } else {
-                                self.mi.store(value, payer);
-                            }
+                        pub fn get(&self) -> Option<#table_ident> {
+                            return self.db.get();
+                        }
+
+                        pub fn get_or_default(&self) -> #table_ident {
+                            return self.db.get_or_default();
+                        }
+
+                        pub fn get_or_create(&self, payer: rust_chain::Name, default: #table_ident) -> #table_ident {
+                            return self.db.get_or_create(payer, default);
+                        }
+
+                        pub fn set(&self, value: &#table_ident, payer: rust_chain::Name) {
+                            self.db.set(value, payer);
+                        }
+
+                        pub fn remove(&self) {
+                            self.db.remove();
                         }
                     }
 
@@ -1014,6 +1335,14 @@ impl Contract {
                     pub fn update(&self, iterator: &::rust_chain::db::Iterator<#table_ident>, value: &#table_ident, payer: rust_chain::Name) {
                         return self.mi.update(iterator, value, payer);
                     }
+
+                    pub fn modify<F: FnOnce(&mut #table_ident)>(&self, iterator: &::rust_chain::db::Iterator<#table_ident>, payer: rust_chain::Name, f: F) {
+                        return self.mi.modify(iterator, payer, f);
+                    }
+
+                    pub fn estimate_store_cost(&self, value: &#table_ident) -> u64 {
+                        return self.mi.estimate_store_cost(value);
+                    }
                 
                     pub fn remove(&self, iterator: &::rust_chain::db::Iterator<#table_ident>) {
                         return self.mi.remove(iterator);
@@ -1050,6 +1379,18 @@ impl Contract {
                     pub fn end(&self) -> ::rust_chain::db::Iterator<#table_ident> {
                         return self.mi.end();
                     }
+
+                    pub fn iter(&self) -> ::rust_chain::db::TableIter<'_, #table_ident> {
+                        return self.mi.iter();
+                    }
+
+                    pub fn range<R: ::core::ops::RangeBounds<u64>>(&self, range: R) -> ::rust_chain::db::TableIter<'_, #table_ident> {
+                        return self.mi.range(range);
+                    }
+
+                    pub fn rev(&self) -> ::core::iter::Rev<::rust_chain::db::TableIter<'_, #table_ident>> {
+                        return self.mi.rev();
+                    }
                 
                     pub fn get_idx_db(&self, i: usize) -> &dyn ::rust_chain::db::IdxTable {
                         return self.mi.get_idx_db(i);
@@ -1059,6 +1400,8 @@ impl Contract {
                         self.mi.idx_update(it, value, payer);
                     }
 
+                    #migrate_func
+
                     #( #get_idx_db_funcs )*
                 }
 
@@ -1095,7 +1438,7 @@ impl Contract {
         let action_structs_code = actions.map(|action|{
             let item = &action.item;
             let ident = &item.sig.ident;
-            let struct_name = action.action_name.str();
+            let struct_name = self.action_struct_name(action);
             let struct_name_ident = proc_macro2::Ident::new(&struct_name, proc_macro2::Span::call_site());
             let action_name_n = proc_macro2::Literal::u64_suffixed(s2n(&action.action_name.str()));
 
@@ -1106,7 +1449,41 @@ impl Contract {
                 return false;
             });
 
+            // actions with `&[u8]` or `&str` arguments borrow them from the action data
+            let borrowed = Self::has_borrowed_args(item);
+            let unpack_code = if borrowed {
+                let unpack_args = item.sig.inputs.iter().filter_map(|arg| {
+                    if let syn::FnArg::Typed(pat_type) = arg {
+                        if let syn::Pat::Ident(x) = &*pat_type.pat {
+                            let ty = &pat_type.ty;
+                            return Some(quote!{
+                                let #x: #ty = dec.unpack_borrowed();
+                            });
+                        }
+                    }
+                    None
+                });
+                quote! {
+                    let data = ::rust_chain::vmapi::eosio::read_action_data();
+                    let mut dec = ::rust_chain::serializer::Decoder::new(&data);
+                    #( #unpack_args )*
+                }
+            } else {
+                quote! {
+                    let mut action: #struct_name_ident = Default::default();
+                    action.unpack(&::rust_chain::vmapi::eosio::read_action_data());
+                }
+            };
+
             let args = args.map(|arg| {
+                if borrowed {
+                    if let syn::FnArg::Typed(pat_type) = arg {
+                        if let syn::Pat::Ident(x) = &*pat_type.pat {
+                            return quote!{ #x };
+                        }
+                    }
+                    return quote!{};
+                }
                 if let syn::FnArg::Typed(pat_type) = arg {
                     let span = arg.span();
                     if let syn::Pat::Ident(x) = &*pat_type.pat {
@@ -1121,16 +1498,76 @@ impl Contract {
                 }
             });
 
+            let call_code = match &item.sig.output {
+                syn::ReturnType::Default => quote! {
+                    contract.#ident(#( #args ),*);
+                },
+                syn::ReturnType::Type(_, _) => quote! {
+                    let ret = contract.#ident(#( #args ),*);
+                    ::rust_chain::vmapi::eosio_ex::set_action_return_value(::rust_chain::serializer::Encoder::pack(&ret));
+                },
+            };
+
+            let body = quote! {
+                #unpack_code
+                #call_code
+            };
+            (action, action_name_n, body)
+        }).collect::<Vec<_>>();
+
+        // notify handlers of the same action are tried by source contract, then the `*` handler
+        let mut handled: Vec<FixedString> = Vec::new();
+        let action_structs_code = action_structs_code.iter().filter_map(|(action, action_name_n, body)| {
+            if !notify {
+                return Some(quote! {
+                    #action_name_n => {
+                        #body
+                    }
+                });
+            }
+            if handled.contains(&action.action_name) {
+                return None;
+            }
+            handled.push(action.action_name);
+            let handlers = action_structs_code.iter().filter(|(x, _, _)| x.action_name == action.action_name);
+            let source_checks = handlers.clone().filter_map(|(x, _, body)| {
+                let notify_contract_n = proc_macro2::Literal::u64_suffixed(s2n(&x.notify_contract?.str()));
+                Some(quote! {
+                    if first_receiver == #notify_contract_n {
+                        #body
+                    } else
+                })
+            });
+            let fallback = handlers.clone().find(|(x, _, _)| x.notify_contract.is_none()).map(|(_, _, body)| body);
+            Some(quote! {
+                #action_name_n => {
+                    #( #source_checks )*
+                    {
+                        #fallback
+                    }
+                }
+            })
+        }).collect::<Vec<_>>();
+        let migrate_actions_code = self.tables.iter().filter(|table| !notify && table.migrate_action.is_some()).map(|table| {
+            let table_ident = &table.item.ident;
+            let action_name = table.migrate_action.unwrap().str();
+            let struct_name_ident = proc_macro2::Ident::new(&action_name, proc_macro2::Span::call_site());
+            let action_name_n = proc_macro2::Literal::u64_suffixed(s2n(&action_name));
             quote! {
                 #action_name_n => {
                     let mut action: #struct_name_ident = Default::default();
                     action.unpack(&::rust_chain::vmapi::eosio::read_action_data());
-                    contract.#ident(#( #args ),*);
+                    ::rust_chain::require_auth(_receiver);
+                    // the upgraded rows keep their payers
+                    let progress = #table_ident::new_table_with_scope(_receiver, action.scope).migrate(::rust_chain::SAME_PAYER, action.start, action.limit);
+                    ::rust_chain::vmapi::eosio_ex::set_action_return_value(::rust_chain::serializer::Encoder::pack(&progress));
                 }
             }
         });
+
         quote! {
             #( #action_structs_code ) *
+            #( #migrate_actions_code ) *
         }
     }
 
@@ -1152,43 +1589,76 @@ impl Contract {
         return false;
     }
 
-    fn extract_type(ty: &syn::Type) -> Result<(String, &syn::Type), syn::Error> {
-        if let syn::Type::Path(type_path) = ty {
-            if type_path.path.segments.len() != 1 {
+    /// Returns the names and types of the structs and intrinsic types used by `ty`, looking into
+    /// `Option`, `Vec`, `BinaryExtension`, `Box`, `BTreeSet`, `BTreeMap`, arrays and tuples.
+    pub(crate) fn extract_types(ty: &syn::Type) -> Result<Vec<(String, &syn::Type)>, syn::Error> {
+        match ty {
+            syn::Type::Reference(_) => {
+                if let Some(owned) = Self::to_owned_type(ty) {
+                    if let syn::Type::Path(type_path) = owned {
+                        if type_path.path.is_ident("String") {
+                            return Ok(vec![(String::from("String"), ty)]);
+                        }
+                    }
+                    return Ok(vec![(String::from("u8"), ty)]);
+                }
                 return Err(format_err_spanned!(
                     ty,
-                    "can not parse type with multiple segments",
+                    "only &[u8] and &str are supported as borrowed types",
                 ))
             }
+            syn::Type::Array(x) => {
+                return Self::extract_types(&x.elem);
+            }
+            syn::Type::Tuple(x) => {
+                let mut types = Vec::new();
+                for elem in &x.elems {
+                    types.extend(Self::extract_types(elem)?);
+                }
+                return Ok(types);
+            }
+            syn::Type::Path(type_path) => {
+                // types are matched by name, e.g. `rust_chain::Name` as `Name`
+                let path_seg = match type_path.path.segments.last() {
+                    Some(path_seg) if type_path.qself.is_none() => path_seg,
+                    _ => {
+                        return Err(format_err_spanned!(
+                            ty,
+                            "unsupported type",
+                        ))
+                    }
+                };
+                let name = path_seg.ident.to_string();
+                let args_count = match name.as_str() {
+                    "Option" | "Vec" | "BinaryExtension" | "Box" | "BTreeSet" => 1,
+                    "BTreeMap" => 2,
+                    _ => {
+                        return Ok(vec![(name, ty)]);
+                    }
+                };
 
-            let path_seg = &type_path.path.segments[0];
-            let name = path_seg.ident.to_string();
-            if name == "Option" || name == "Vec" || name == "BinaryExtension" {
                 if let syn::PathArguments::AngleBracketed(x) = &path_seg.arguments {
-                    if x.args.len() != 1 {
+                    if x.args.len() != args_count {
                         return Err(format_err_spanned!(
                             x,
-                            "can not parse option type with multiple arguments",
-                        ))      
+                            "{} type requires {} argument(s)", name, args_count
+                        ))
                     }
-
-                    let arg = &x.args[0];
-                    if let syn::GenericArgument::Type(ty) = arg {
-                        if let syn::Type::Path(type_path) = ty {
-                            if type_path.path.segments.len() != 1 {
-                                return Err(format_err_spanned!(
-                                    type_path,
-                                    "can not parse type in option with multiple segments",
-                                ))
-                            }
-                            let name = type_path.path.segments.last().unwrap().ident.to_string();
-                            return Ok((name, ty));
+                    let mut types = Vec::new();
+                    for arg in &x.args {
+                        if let syn::GenericArgument::Type(ty) = arg {
+                            types.extend(Self::extract_types(ty)?);
+                        } else {
+                            return Err(format_err_spanned!(
+                                arg,
+                                "unsupported type argument",
+                            ))
                         }
                     }
+                    return Ok(types);
                 }
-            } else {
-                return Ok((name, ty));
             }
+            _ => {}
         }
 
         Err(format_err_spanned!(
@@ -1197,7 +1667,71 @@ impl Contract {
         ))
     }
 
-    fn is_binary_extension_type(ty: &syn::Type) -> bool {
+    /// Calls `f` on every path type in `ty`, after the type arguments of the path.
+    pub(crate) fn visit_type_paths(ty: &mut syn::Type, f: &mut dyn FnMut(&mut syn::TypePath)) {
+        match ty {
+            syn::Type::Path(type_path) => {
+                for seg in type_path.path.segments.iter_mut() {
+                    if let syn::PathArguments::AngleBracketed(args) = &mut seg.arguments {
+                        for arg in args.args.iter_mut() {
+                            if let syn::GenericArgument::Type(ty) = arg {
+                                Self::visit_type_paths(ty, f);
+                            }
+                        }
+                    }
+                }
+                f(type_path);
+            }
+            syn::Type::Array(x) => Self::visit_type_paths(&mut x.elem, f),
+            syn::Type::Slice(x) => Self::visit_type_paths(&mut x.elem, f),
+            syn::Type::Reference(x) => Self::visit_type_paths(&mut x.elem, f),
+            syn::Type::Paren(x) => Self::visit_type_paths(&mut x.elem, f),
+            syn::Type::Group(x) => Self::visit_type_paths(&mut x.elem, f),
+            syn::Type::Tuple(x) => {
+                for elem in x.elems.iter_mut() {
+                    Self::visit_type_paths(elem, f);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the owned type which is packed like the borrowed type `ty`:
+    /// `Vec<u8>` for `&[u8]` and `String` for `&str`, or `None` if `ty` is not one of them.
+    fn to_owned_type(ty: &syn::Type) -> Option<syn::Type> {
+        if let syn::Type::Reference(x) = ty {
+            if x.mutability.is_some() {
+                return None;
+            }
+            match &*x.elem {
+                syn::Type::Slice(slice) => {
+                    if let syn::Type::Path(type_path) = &*slice.elem {
+                        if type_path.path.is_ident("u8") {
+                            return Some(syn::parse_quote!(Vec<u8>));
+                        }
+                    }
+                }
+                syn::Type::Path(type_path) => {
+                    if type_path.path.is_ident("str") {
+                        return Some(syn::parse_quote!(String));
+                    }
+                }
+                _ => {}
+            }
+        }
+        return None;
+    }
+
+    fn has_borrowed_args(item: &syn::ImplItemMethod) -> bool {
+        return item.sig.inputs.iter().any(|arg| {
+            if let syn::FnArg::Typed(pat_type) = arg {
+                return Self::to_owned_type(&pat_type.ty).is_some();
+            }
+            return false;
+        });
+    }
+
+    pub(crate) fn is_binary_extension_type(ty: &syn::Type) -> bool {
         if let syn::Type::Path(type_path) = ty {
             if type_path.path.segments.len() != 1 {
                 false;
@@ -1256,6 +1790,17 @@ impl Contract {
         return None;
     }
 
+    fn to_abi_index_type(secondary_type: &str) -> Option<&'static str> {
+        match Some(secondary_type) {
+            Some("Idx64") => Some("i64"),
+            Some("Idx128") => Some("i128"),
+            Some("Idx256") => Some("i256"),
+            Some("IdxF64") => Some("float64"),
+            Some("IdxF128") => Some("float128"),
+            _ => None,
+        }
+    }
+
     fn add_abi_type<'a>(&'a self, tp_name: &str, abi_types: &mut HashMap<String, &'a syn::Type>) -> Result<(), syn::Error> {
         if Self::is_primitive_type(tp_name) {
             return Ok(());
@@ -1269,12 +1814,13 @@ impl Contract {
                         continue;
                     }
                     for field in &x.fields {
-                        let (type_name, ty) = Self::extract_type(&field.ty)?;
-                        if Self::is_primitive_type(&type_name) {
-                            continue;
-                        }
-                        if abi_types.insert(type_name.clone(), ty).is_none() {
-                            ty_names.push(type_name);
+                        for (type_name, ty) in Self::extract_types(&field.ty)? {
+                            if Self::is_primitive_type(&type_name) {
+                                continue;
+                            }
+                            if abi_types.insert(type_name.clone(), ty).is_none() {
+                                ty_names.push(type_name);
+                            }
                         }
                     }
                     break;
@@ -1288,12 +1834,13 @@ impl Contract {
                         // let field_ident = &field.ident;
                         if let syn::Fields::Unnamed(unnamed_fields) = &field.fields {
                             let unnamed_field = unnamed_fields.unnamed.last().unwrap();
-                            let (type_name, _) = Self::extract_type(&unnamed_field.ty)?;
-                            if Self::is_primitive_type(&type_name) {
-                                continue;
-                            }
-                            if abi_types.insert(type_name.clone(), &unnamed_field.ty).is_none() {
-                                ty_names.push(type_name);
+                            for (type_name, ty) in Self::extract_types(&unnamed_field.ty)? {
+                                if Self::is_primitive_type(&type_name) {
+                                    continue;
+                                }
+                                if abi_types.insert(type_name.clone(), ty).is_none() {
+                                    ty_names.push(type_name);
+                                }
                             }
                         }
                         //DODO: return error
@@ -1318,7 +1865,17 @@ impl Contract {
             // let span = item.span();
             for arg in item.sig.inputs.iter() {
                 if let syn::FnArg::Typed(pat_type) = arg {
-                    let (type_name, ty) = Self::extract_type(&pat_type.ty)?;
+                    for (type_name, ty) in Self::extract_types(&pat_type.ty)? {
+                        if Self::is_primitive_type(&type_name) {
+                            continue;
+                        }
+                        abi_types.insert(type_name.clone(), ty);
+                        self.add_abi_type(&type_name, &mut abi_types)?;
+                    }
+                }
+            }
+            if let syn::ReturnType::Type(_, ty) = &item.sig.output {
+                for (type_name, ty) in Self::extract_types(ty)? {
                     if Self::is_primitive_type(&type_name) {
                         continue;
                     }
@@ -1330,12 +1887,13 @@ impl Contract {
 
         for table in &self.tables {
             for field in &table.item.fields {
-                let (type_name, tp) = Self::extract_type(&field.ty)?;
-                if Self::is_primitive_type(&type_name) {
-                    continue;
+                for (type_name, tp) in Self::extract_types(&field.ty)? {
+                    if Self::is_primitive_type(&type_name) {
+                        continue;
+                    }
+                    abi_types.insert(type_name.clone(), tp);
+                    self.add_abi_type(&type_name, &mut abi_types)?;
                 }
-                abi_types.insert(type_name.clone(), tp);
-                self.add_abi_type(&type_name, &mut abi_types)?;
             }
         }
 
@@ -1343,7 +1901,7 @@ impl Contract {
         for (_, tp) in abi_types {
             structs_code.push(
                 quote!{
-                    let struct_info = #tp::type_info();
+                    let struct_info = <#tp as ::rust_chain::eosio_scale_info::TypeInfo>::type_info();
                     if !info.structs.contains(&struct_info) {
                         info.structs.push(struct_info);
                     }
@@ -1351,8 +1909,10 @@ impl Contract {
             );
         }
 
+        // only one struct per action name is described in the ABI
         self.actions
             .iter()
+            .filter(|action| self.action_struct_name(action) == action.action_name.str())
             .for_each(|action| {
                 let struct_name_ident = proc_macro2::Ident::new(&action.action_name.str(), proc_macro2::Span::call_site());
                 structs_code.push(
@@ -1379,33 +1939,79 @@ impl Contract {
                 );
             });
 
-        let table_scale_info_code = self.tables
+        self.tables
             .iter()
-            .map(|table| {
-                let ident = &table.item.ident;
-                let table_name_lit = proc_macro2::Literal::string(&table.table_name.str());
-                quote!{
-                    info.tables.push(
-                        ::rust_chain::abi::TableInfo {
-                            name: String::from(#table_name_lit),
-                            info: #ident::type_info(),
-                        });
-                }
+            .filter(|table| table.migrate_action.is_some())
+            .for_each(|table| {
+                let action_name = table.migrate_action.unwrap().str();
+                let struct_name_ident = proc_macro2::Ident::new(&action_name, proc_macro2::Span::call_site());
+                let action_name_lit = proc_macro2::Literal::string(&action_name);
+                structs_code.push(
+                    quote!{
+                        let struct_info = #struct_name_ident::type_info();
+                        if !info.structs.contains(&struct_info) {
+                            info.structs.push(struct_info);
+                        }
+                        info.actions.push(
+                            ::rust_chain::abi::ActionInfo {
+                                name: String::from(#action_name_lit),
+                                info: #struct_name_ident::type_info(),
+                                result_type: Some(String::from("(u32,Option<u64>)")),
+                            });
+                    }
+                );
             });
 
+        let mut table_scale_info_code: Vec<TokenStream2> = Vec::new();
+        for table in &self.tables {
+            let ident = &table.item.ident;
+            let table_name_lit = proc_macro2::Literal::string(&table.table_name.str());
+            let secondary_keys = Self::get_secondary_keys(table)?;
+            let key_names = secondary_keys
+                .iter()
+                .map(|key| key.ident.to_string());
+            let key_types = secondary_keys
+                .iter()
+                .map(|key| Self::to_abi_index_type(key.secondary_type).unwrap());
+            let version = match table.version {
+                Some(version) => quote!{ Some(#version) },
+                None => quote!{ None },
+            };
+            table_scale_info_code.push(quote!{
+                info.tables.push(
+                    ::rust_chain::abi::TableInfo {
+                        name: String::from(#table_name_lit),
+                        info: #ident::type_info(),
+                        key_names: vec![#( String::from(#key_names) ),*],
+                        key_types: vec![#( String::from(#key_types) ),*],
+                        version: #version,
+                    });
+            });
+        }
+
         let action_scale_info_code = self.actions
             .iter()
+            .filter(|action| self.action_struct_name(action) == action.action_name.str())
             .map(|action| {
                 // let ident = &action.item.sig.ident;
                 let struct_name = action.action_name.str();
                 let action_name_lit = proc_macro2::Literal::string(&action.action_name.str());
 
                 let struct_name_ident = proc_macro2::Ident::new(&struct_name, proc_macro2::Span::call_site());
+                let result_type = match &action.item.sig.output {
+                    syn::ReturnType::Default => quote!{ None },
+                    syn::ReturnType::Type(_, ty) => {
+                        let rust_type: String = crate::derive::type_name_string(ty).split_whitespace().collect();
+                        let rust_type_lit = proc_macro2::Literal::string(&rust_type);
+                        quote!{ Some(String::from(#rust_type_lit)) }
+                    }
+                };
                 quote!{
                     info.actions.push(
                         ::rust_chain::abi::ActionInfo {
                             name: String::from(#action_name_lit),
                             info: #struct_name_ident::type_info(),
+                            result_type: #result_type,
                         });
                 }
             });
@@ -1428,6 +2034,173 @@ impl Contract {
         });
     }
 
+    fn generate_client_code(&self) -> TokenStream2 {
+        let action_calls_code = self.actions.iter().filter(|action| !action.is_notify).map(|action| {
+            let item = &action.item;
+            let struct_name_ident = proc_macro2::Ident::new(&action.action_name.str(), proc_macro2::Span::call_site());
+            let action_name_lit = proc_macro2::Literal::string(&action.action_name.str());
+
+            let args: Vec<(&syn::Ident, &syn::Type)> = item.sig.inputs.iter().filter_map(|arg| {
+                if let syn::FnArg::Typed(pat_type) = arg {
+                    if let syn::Pat::Ident(x) = &*pat_type.pat {
+                        return Some((&x.ident, &*pat_type.ty));
+                    }
+                }
+                None
+            }).collect();
+            let arg_names = args.iter().map(|(name, _)| name);
+            let arg_values = args.iter().map(|(name, ty)| {
+                if Self::to_owned_type(ty).is_some() {
+                    return quote!{ #name: #name.into() };
+                }
+                quote!{ #name }
+            });
+            let arg_types = args.iter().map(|(_, ty)| ty);
+
+            let result_type = match &item.sig.output {
+                syn::ReturnType::Default => quote!{ () },
+                syn::ReturnType::Type(_, ty) => quote!{ #ty },
+            };
+
+            quote! {
+                pub fn #struct_name_ident<'a>(tester: &'a mut dyn ::rust_chain::chaintester::Tester, contract: &str, #( #arg_names: #arg_types ),*) -> ::rust_chain::tester::ActionCall<'a, #result_type> {
+                    let action = super::#struct_name_ident { #( #arg_values ),* };
+                    ::rust_chain::tester::ActionCall::new(tester, contract, #action_name_lit, ::rust_chain::serializer::Encoder::pack(&action))
+                }
+            }
+        });
+
+        let table_reads_code = self.tables.iter().map(|table| {
+            let table_ident = &table.item.ident;
+            let table_name_lit = proc_macro2::Literal::string(&table.table_name.str());
+            let fn_ident = proc_macro2::Ident::new(&table.table_name.str().replace(".", "_"), proc_macro2::Span::call_site());
+            quote! {
+                pub fn #fn_ident(tester: &mut dyn ::rust_chain::chaintester::Tester, contract: &str, scope: &str) -> ::rust_chain::chaintester::client::Result<Vec<super::super::#table_ident>> {
+                    ::rust_chain::tester::get_table_rows(tester, contract, scope, #table_name_lit)
+                }
+            }
+        });
+
+        quote! {
+            /// Typed client of the contract, usable with `ChainTester` and `MockTester`.
+            #[cfg(feature = "std")]
+            #[allow(non_snake_case)]
+            pub mod client {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #( #action_calls_code )*
+
+                /// Reads the rows of the contract tables.
+                pub mod tables {
+                    #[allow(unused_imports)]
+                    use super::super::*;
+
+                    #( #table_reads_code )*
+                }
+            }
+        }
+    }
+
+    /// Returns the name of the struct that holds the arguments of `action`.
+    /// Actions use the action name, as do notify handlers unless an action or an earlier
+    /// notify handler already has that name, in which case the method name is appended.
+    fn action_struct_name(&self, action: &Action) -> String {
+        let name = action.action_name.str();
+        let owner = self.actions.iter()
+            .find(|a| a.action_name == action.action_name && !a.is_notify)
+            .or_else(|| self.actions.iter().find(|a| a.action_name == action.action_name));
+        if let Some(owner) = owner {
+            if !core::ptr::eq(owner, action) {
+                return format!("{}_{}", name, action.item.sig.ident);
+            }
+        }
+        return name;
+    }
+
+    /// Converts an action name like `transfer` to the name of its proxy struct, `Transfer`.
+    fn to_proxy_name(action_name: &str) -> String {
+        let mut chars = action_name.chars();
+        match chars.next() {
+            Some(c) => c.to_ascii_uppercase().to_string() + chars.as_str(),
+            None => String::new(),
+        }
+    }
+
+    fn generate_action_proxies_code(&self) -> TokenStream2 {
+        let proxy_names: Vec<String> = self.actions.iter().filter(|action| !action.is_notify).map(|action| {
+            Self::to_proxy_name(&action.action_name.str())
+        }).collect();
+        let proxies_code = self.actions.iter().filter(|action| !action.is_notify).map(|action| {
+            let item = &action.item;
+            let struct_name_ident = proc_macro2::Ident::new(&action.action_name.str(), proc_macro2::Span::call_site());
+            let proxy_ident = proc_macro2::Ident::new(&Self::to_proxy_name(&action.action_name.str()), proc_macro2::Span::call_site());
+            let action_name_lit = proc_macro2::Literal::string(&action.action_name.str());
+            let doc = format!("Sends the `{}` action of the contract as an inline action.", action.action_name.str());
+
+            let args: Vec<(&syn::Ident, &syn::Type)> = item.sig.inputs.iter().filter_map(|arg| {
+                if let syn::FnArg::Typed(pat_type) = arg {
+                    if let syn::Pat::Ident(x) = &*pat_type.pat {
+                        return Some((&x.ident, &*pat_type.ty));
+                    }
+                }
+                None
+            }).collect();
+            let arg_names = args.iter().map(|(name, _)| name).collect::<Vec<_>>();
+            let arg_values = args.iter().map(|(name, ty)| {
+                if Self::to_owned_type(ty).is_some() {
+                    return quote!{ #name: #name.into() };
+                }
+                quote!{ #name }
+            });
+            // a type named like a proxy, e.g. `Transfer`, refers to the type of the contract module
+            let arg_types = args.iter().map(|(_, ty)| {
+                let mut ty = (*ty).clone();
+                Self::visit_type_paths(&mut ty, &mut |type_path| {
+                    if type_path.qself.is_none() && type_path.path.leading_colon.is_none() && type_path.path.segments.len() == 1 &&
+                        proxy_names.contains(&type_path.path.segments[0].ident.to_string()) {
+                        type_path.path = syn::parse_quote!(super::#type_path);
+                    }
+                });
+                ty
+            }).collect::<Vec<_>>();
+
+            quote! {
+                #[doc = #doc]
+                pub struct #proxy_ident {
+                    pub contract: ::rust_chain::Name,
+                }
+
+                impl #proxy_ident {
+                    pub const NAME: ::rust_chain::Name = ::rust_chain::Name{n: ::rust_chain::name::static_str_to_name(#action_name_lit)};
+
+                    pub fn new(contract: ::rust_chain::Name) -> Self {
+                        Self { contract }
+                    }
+
+                    pub fn to_action(&self, auth: ::rust_chain::action::PermissionLevel, #( #arg_names: #arg_types ),*) -> ::rust_chain::action::Action {
+                        let data = super::#struct_name_ident { #( #arg_values ),* };
+                        ::rust_chain::action::Action::new(self.contract, Self::NAME, auth, &data)
+                    }
+
+                    pub fn send(&self, auth: ::rust_chain::action::PermissionLevel, #( #arg_names: #arg_types ),*) {
+                        self.to_action(auth, #( #arg_names ),*).send();
+                    }
+                }
+            }
+        });
+
+        quote! {
+            /// Typed proxies for sending the actions of the contract as inline actions.
+            pub mod actions {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #( #proxies_code )*
+            }
+        }
+    }
+
     fn generate_apply_code(&self) -> TokenStream2 {
         if self.main_struct.is_none() && self.sub_struct.is_none() {
             return quote!{};
@@ -1444,8 +2217,10 @@ impl Contract {
 
         let entry_code = match self.main_struct {
             Some(_) => {
+                // crates which depend on the contract, e.g. to send its actions, enable `no-entry`
+                // so that only one `apply` is linked into the wasm file
                 quote!{
-                    #[cfg(not(feature = "std"))]
+                    #[cfg(not(any(feature = "std", feature = "no-entry")))]
                     #[no_mangle]
                     pub fn apply(receiver: u64, first_receiver: u64, action: u64) {
                         contract_apply(receiver, first_receiver, action);
@@ -1484,9 +2259,6 @@ impl Contract {
 
             #entry_code
 
-            #[cfg(feature = "std")]
-            use rust_chain::chaintester::interfaces::TApplySyncClient;
-
             #[cfg(feature = "std")]
             pub fn native_apply(receiver: u64, first_receiver: u64, action: u64) {
                 contract_apply(receiver, first_receiver, action);
@@ -1604,7 +2376,7 @@ impl Contract {
     }
 
     pub fn generate_code(&self) -> Result<TokenStream2, syn::Error> {
-        let action_structs_code = self.generate_action_structs();
+        let action_structs_code = self.generate_action_structs()?;
         let tables_code = self.generate_tables_code()?;
         let apply_code;
         if !self.has_apply_func() {
@@ -1629,43 +2401,57 @@ impl Contract {
                     if self.packers.iter().any(|packer|{
                         packer.ident == x.ident
                     }) {
-                        quote!{
-                            #[cfg_attr(feature = "std", derive(rust_chain::eosio_scale_info::TypeInfo))]
-                            #[cfg_attr(feature = "std", scale_info(crate = ::rust_chain::eosio_scale_info))]
+                        let type_info = crate::derive::generate_struct_type_info(&x.clone().into())?;
+                        Ok(quote!{
                             #[derive(Default)]
                             #item
-                        }
+                            #type_info
+                        })
                     } else {
-                        quote!{
+                        Ok(quote!{
                             #item
-                        }
+                        })
                     }
                 }
                 syn::Item::Enum(x) => {
                     if self.variants.iter().any(|variant|{
                         variant.ident == x.ident
                     }) {
-                        quote!{
+                        Ok(quote!{
                             #[cfg_attr(feature = "std", derive(rust_chain::eosio_scale_info::TypeInfo))]
                             #[cfg_attr(feature = "std", scale_info(crate = ::rust_chain::eosio_scale_info))]
                             #item
-                        }
+                        })
                     } else {
-                        quote!{
+                        Ok(quote!{
                             #item
-                        }
+                        })
                     }
                 }
                 _ => {
-                    quote!{
+                    Ok(quote!{
                         #item
-                    }
+                    })
                 }
             }
-        });
+        }).collect::<Result<Vec<_>, syn::Error>>()?;
         let ident = &self.ident;
         let attrs = self.attrs();
         let vis = self.vis();
+        let client_code = self.generate_client_code();
+        let action_proxies_code = self.generate_action_proxies_code();
+
+        let native_apply_code = if self.main_struct.is_some() || self.sub_struct.is_some() {
+            quote!{
+                #[cfg(feature = "std")]
+                pub fn contract_apply(receiver: u64, first_receiver: u64, action: u64) {
+                    #ident::contract_apply(receiver, first_receiver, action);
+                }
+            }
+        } else {
+            quote!{}
+        };
+
         Ok(quote! {
             #( #attrs )*
             #vis mod #ident {
@@ -1698,12 +2484,21 @@ impl Contract {
                 #tables_code
                 #apply_code
                 #scale_info
+                #client_code
+                #action_proxies_code
             }
 
             #[cfg(feature = "std")]
             pub fn generate_abi() -> String {
                 #ident::generate_abi()
             }
+
+            #[cfg(feature = "std")]
+            pub use #ident::client;
+
+            pub use #ident::actions;
+        
+            #native_apply_code
         })
     }
 
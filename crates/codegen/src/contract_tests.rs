@@ -297,5 +297,262 @@ mod tests {
         assert!(ret.as_ref().unwrap().has_primary_value_interface_trait("MyData"));
         assert!(ret.as_ref().unwrap().has_secondary_value_interface_trait("MyData"));
     }
-}
 
+    #[test]
+    fn test_notify_source() {
+        let ret = Contract::new(
+            syn::parse_quote! {},
+            syn::parse_quote! {
+                mod hello {
+                    #[chain(main)]
+                    pub struct Hello {
+                        receiver: Name,
+                        first_receiver: Name,
+                        action: Name,
+                    }
+
+                    impl Hello {
+                        pub fn new(receiver: Name, first_receiver: Name, action: Name) -> Self {
+                            Self {
+                                receiver: receiver,
+                                first_receiver: first_receiver,
+                                action: action,
+                            }
+                        }
+
+                        #[chain(notify="eosio.token::transfer")]
+                        pub fn on_transfer(&self, from: Name, to: Name, quantity: Asset, memo: String) {
+                        }
+                    }
+                }
+            }
+        );
+
+        assert!(ret.is_ok(), "bad return");
+        let code = ret.unwrap().generate_code().unwrap().to_string();
+        let check = format!("if first_receiver == {}u64", crate::name::s2n("eosio.token"));
+        assert!(code.contains(&check));
+    }
+
+    #[test]
+    fn test_notify_same_action_name() {
+        let ret = Contract::new(
+            syn::parse_quote! {},
+            syn::parse_quote! {
+                mod hello {
+                    #[chain(main)]
+                    pub struct Hello {
+                        receiver: Name,
+                        first_receiver: Name,
+                        action: Name,
+                    }
+
+                    impl Hello {
+                        pub fn new(receiver: Name, first_receiver: Name, action: Name) -> Self {
+                            Self {
+                                receiver: receiver,
+                                first_receiver: first_receiver,
+                                action: action,
+                            }
+                        }
+
+                        #[chain(action="transfer")]
+                        pub fn transfer(&self, from: Name, to: Name, quantity: Asset, memo: String) {
+                        }
+
+                        #[chain(notify="eosio.token::transfer")]
+                        pub fn on_transfer(&self, from: Name, to: Name, quantity: Asset, memo: String) {
+                        }
+
+                        #[chain(notify="*::transfer")]
+                        pub fn on_any_transfer(&self, from: Name, to: Name, quantity: Asset, memo: String) {
+                        }
+                    }
+                }
+            }
+        );
+
+        assert!(ret.is_ok(), "bad return");
+        let code = ret.unwrap().generate_code().unwrap().to_string();
+        assert!(code.contains("pub struct transfer_on_transfer"));
+        assert!(code.contains("pub struct transfer_on_any_transfer"));
+        let check = format!("if first_receiver == {}u64", crate::name::s2n("eosio.token"));
+        assert!(code.contains(&check));
+
+        let ret = Contract::new(
+            syn::parse_quote! {},
+            syn::parse_quote! {
+                mod hello {
+                    #[chain(main)]
+                    pub struct Hello {
+                        receiver: Name,
+                        first_receiver: Name,
+                        action: Name,
+                    }
+
+                    impl Hello {
+                        pub fn new(receiver: Name, first_receiver: Name, action: Name) -> Self {
+                            Self {
+                                receiver: receiver,
+                                first_receiver: first_receiver,
+                                action: action,
+                            }
+                        }
+
+                        #[chain(notify="eosio.token::transfer")]
+                        pub fn on_transfer(&self, from: Name, to: Name, quantity: Asset, memo: String) {
+                        }
+
+                        #[chain(notify="eosio.token::transfer")]
+                        pub fn on_transfer2(&self, from: Name, to: Name, quantity: Asset, memo: String) {
+                        }
+                    }
+                }
+            }
+        );
+        assert!(ret.is_err(), "bad return");
+        assert!(ret.err().unwrap().to_compile_error().to_string().contains("dumplicated action name: transfer"));
+    }
+
+    #[test]
+    fn test_notify_action_mismatch() {
+        let ret = Contract::new(
+            syn::parse_quote! {},
+            syn::parse_quote! {
+                mod hello {
+                    #[chain(main)]
+                    pub struct Hello {
+                        receiver: Name,
+                        first_receiver: Name,
+                        action: Name,
+                    }
+
+                    impl Hello {
+                        pub fn new(receiver: Name, first_receiver: Name, action: Name) -> Self {
+                            Self {
+                                receiver: receiver,
+                                first_receiver: first_receiver,
+                                action: action,
+                            }
+                        }
+
+                        #[chain(action="transfer", notify="*::issue")]
+                        pub fn on_transfer(&self) {
+                        }
+                    }
+                }
+            }
+        );
+        assert!(ret.is_err(), "bad return");
+        assert!(ret.err().unwrap().to_compile_error().to_string().contains("notify action issue does not match action name transfer"));
+    }
+
+    #[test]
+    fn test_table_version() {
+        let contract = Contract::new(
+            syn::parse_quote! {},
+            syn::parse_quote! {
+                mod hello {
+                    #[chain(table="mydata", version=0)]
+                    pub struct MyData {
+                        #[chain(primary)]
+                        a1: u64,
+                    }
+                }
+            }
+        );
+        assert!(contract.is_err(), "bad return");
+        assert!(contract.err().unwrap().to_compile_error().to_string().contains("table version must be in range 1..=255"));
+
+        let contract = Contract::new(
+            syn::parse_quote! {},
+            syn::parse_quote! {
+                mod hello {
+                    #[chain(table="mydata", migrate="migrate")]
+                    pub struct MyData {
+                        #[chain(primary)]
+                        a1: u64,
+                    }
+                }
+            }
+        );
+        assert!(contract.is_err(), "bad return");
+        assert!(contract.err().unwrap().to_compile_error().to_string().contains("migrate action requires a versioned table which is not a singleton: mydata"));
+
+        let contract = Contract::new(
+            syn::parse_quote! {},
+            syn::parse_quote! {
+                mod hello {
+                    #[chain(table="mydata", version=2)]
+                    pub struct MyData {
+                        #[chain(primary)]
+                        a1: u64,
+                    }
+                }
+            }
+        );
+        assert!(contract.is_ok(), "bad return");
+        let code = contract.unwrap().generate_code().unwrap().to_string();
+        assert!(code.contains("pub const TABLE_VERSION : u8 = 2u8"));
+        // `TableI64` knows the version to tell the rows written before the table was versioned
+        assert!(code.contains("fn table_version () -> u8 { return 2u8 ; }"));
+        // the migrated rows report the bytes they were unpacked from, including the version prefix
+        assert!(code.contains("let (row , size) = < MyData as :: rust_chain :: db :: MigrateRow > :: migrate (version , & data [1 ..]) ; * self = row ; return 1 + size ;"));
+
+        let contract = Contract::new(
+            syn::parse_quote! {},
+            syn::parse_quote! {
+                mod hello {
+                    #[chain(table="mydata", singleton, version=2)]
+                    pub struct MyData {
+                        a1: u64,
+                    }
+                }
+            }
+        );
+        assert!(contract.is_err(), "bad return");
+        assert!(contract.err().unwrap().to_compile_error().to_string().contains("version is not supported by a singleton: mydata"));
+    }
+
+    #[test]
+    fn test_action_proxies() {
+        let contract = Contract::new(
+            syn::parse_quote! {},
+            syn::parse_quote! {
+                mod hello {
+                    #[chain(main)]
+                    pub struct Hello {
+                        receiver: Name,
+                        first_receiver: Name,
+                        action: Name,
+                    }
+
+                    impl Hello {
+                        pub fn new(receiver: Name, first_receiver: Name, action: Name) -> Self {
+                            Self {
+                                receiver: receiver,
+                                first_receiver: first_receiver,
+                                action: action,
+                            }
+                        }
+
+                        #[chain(action="setconfig")]
+                        pub fn set_config(&self, value: u64, memo: &str) {
+                        }
+
+                        #[chain(action="roster")]
+                        pub fn roster(&self, roster: Roster, history: Vec<Roster>) {
+                        }
+                    }
+                }
+            }
+        );
+        let code = contract.unwrap().generate_code().unwrap().to_string();
+        assert!(code.contains("# [cfg (not (any (feature = \"std\" , feature = \"no-entry\")))] # [no_mangle] pub fn apply"));
+        assert!(code.contains("pub struct Setconfig"));
+        assert!(code.contains("static_str_to_name (\"setconfig\")"));
+        assert!(code.contains("pub fn send (& self , auth : :: rust_chain :: action :: PermissionLevel , value : u64 , memo : & str)"));
+        // the argument types are not shadowed by the proxy structs
+        assert!(code.contains("pub fn send (& self , auth : :: rust_chain :: action :: PermissionLevel , roster : super :: Roster , history : Vec < super :: Roster >)"));
+    }
+}
@@ -0,0 +1,392 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{
+    quote,
+    quote_spanned,
+};
+
+use syn::spanned::Spanned;
+
+use crate::contract::Contract;
+
+/// Drops the module paths of the types in `ty`, e.g. `Vec<core::primitive::u64>` becomes `Vec<u64>`,
+/// as the ABI maps types by their name.
+fn strip_type_paths(ty: &mut syn::Type) {
+    Contract::visit_type_paths(ty, &mut |type_path| {
+        if type_path.qself.is_some() {
+            return;
+        }
+        if let Some(last) = type_path.path.segments.pop() {
+            type_path.path.leading_colon = None;
+            type_path.path.segments.clear();
+            type_path.path.segments.push(last.into_value());
+        }
+    });
+}
+
+/// Returns the type name of `ty` in the form used by scale-info, e.g. `Vec<u8>`.
+pub(crate) fn type_name_string(ty: &syn::Type) -> String {
+    let mut ty = ty.clone();
+    strip_type_paths(&mut ty);
+    return quote!(#ty).to_string()
+        .replace(" ::", "::")
+        .replace(":: ", "::")
+        .replace(" ,", ",")
+        .replace(" ;", ";")
+        .replace(" [", "[")
+        .replace("[ ", "[")
+        .replace(" ]", "]")
+        .replace(" (", "(")
+        .replace(",(", ", (")
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">");
+}
+
+/// Returns true if the field has a `#[packer(skip)]` attribute.
+fn is_skipped(field: &syn::Field) -> Result<bool, syn::Error> {
+    for attr in &field.attrs {
+        if !attr.path.is_ident("packer") {
+            continue;
+        }
+        let ident: syn::Ident = attr.parse_args()?;
+        if ident != "skip" {
+            return Err(format_err_spanned!(
+                attr,
+                "unknown packer attribute: {}", ident
+            ));
+        }
+        return Ok(true);
+    }
+    return Ok(false);
+}
+
+/// Adds `bound` to every type parameter of `generics`.
+fn add_bounds(generics: &syn::Generics, bound: TokenStream2) -> syn::Generics {
+    let bounds: syn::punctuated::Punctuated<syn::TypeParamBound, syn::Token![+]> = syn::parse_quote!(#bound);
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.extend(bounds.clone());
+    }
+    return generics;
+}
+
+/// Generates the `TypeInfo` implementation of a type, which also registers the structs it uses
+/// and, if `register_self` is true, the type itself in the scale-info type map for ABI generation.
+fn generate_type_info(input: &syn::DeriveInput, type_def: TokenStream2, used_types: Vec<&syn::Type>, register_self: bool) -> TokenStream2 {
+    let ident = &input.ident;
+    let name_lit = proc_macro2::Literal::string(&ident.to_string());
+    let generics = add_bounds(&input.generics, quote!(::rust_chain::eosio_scale_info::TypeInfo + 'static));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let type_params = input.generics.type_params().map(|param| {
+        let param_ident = &param.ident;
+        let param_lit = proc_macro2::Literal::string(&param_ident.to_string());
+        quote!{
+            TypeParameter::new(#param_lit, Some(meta_type::<#param_ident>()))
+        }
+    });
+
+    let register = if register_self {
+        quote!{
+            let full_name = ::rust_chain::string::String::from(module_path!()) + "::" + #name_lit;
+            let registered = ::rust_chain::eosio_scale_info::get_scale_type_map().lock().unwrap().insert(full_name, ty.clone()).is_some();
+        }
+    } else {
+        quote!{
+            let registered = false;
+        }
+    };
+
+    quote!{
+        #[cfg(feature = "std")]
+        impl #impl_generics ::rust_chain::eosio_scale_info::TypeInfo for #ident #ty_generics #where_clause {
+            type Identity = Self;
+
+            fn type_info() -> ::rust_chain::eosio_scale_info::Type {
+                #[allow(unused_imports)]
+                use ::rust_chain::eosio_scale_info::{
+                    build::{
+                        Fields,
+                        Variants,
+                    },
+                    meta_type,
+                    Path,
+                    Type,
+                    TypeInfo,
+                    TypeParameter,
+                };
+
+                let ty = Type::builder()
+                    .path(Path::new(#name_lit, module_path!()))
+                    .type_params(::rust_chain::vec![#( #type_params ),*])
+                    #type_def;
+                #register
+                if !registered {
+                    #( ::rust_chain::eosio_scale_info::add_scale_type(<#used_types as TypeInfo>::type_info()); )*
+                }
+                ty
+            }
+        }
+    }
+}
+
+/// Returns the struct types used by `ty` which have to be registered for ABI generation.
+fn get_used_types<'a>(ty: &'a syn::Type, used_types: &mut Vec<&'a syn::Type>) -> Result<(), syn::Error> {
+    for (name, ty) in Contract::extract_types(ty)? {
+        if !Contract::is_primitive_type(&name) && !used_types.contains(&ty) {
+            used_types.push(ty);
+        }
+    }
+    return Ok(());
+}
+
+/// Generates the `TypeInfo` implementation of a struct described by its named `fields`.
+fn generate_composite_type_info(input: &syn::DeriveInput, fields: &[&syn::Field], register_self: bool) -> Result<TokenStream2, syn::Error> {
+    let mut used_types = Vec::new();
+    for field in fields {
+        get_used_types(&field.ty, &mut used_types)?;
+    }
+    // a struct which contains itself, e.g. in a `Vec`, is registered by the caller of `type_info`
+    used_types.retain(|ty| !matches!(ty, syn::Type::Path(type_path) if type_path.path.is_ident(&input.ident)));
+
+    let type_def_fields = fields.iter().map(|field| {
+        let ty = &field.ty;
+        let name_lit = proc_macro2::Literal::string(&field.ident.as_ref().unwrap().to_string());
+        let type_name_lit = proc_macro2::Literal::string(&type_name_string(ty));
+        quote!{
+            .field(|f| f.ty::<#ty>().name(#name_lit).type_name(#type_name_lit))
+        }
+    });
+    return Ok(generate_type_info(input, quote!{
+        .composite(Fields::named()#( #type_def_fields )*)
+    }, used_types, register_self));
+}
+
+/// Generates the `TypeInfo` implementation of a struct with named fields defined in a contract.
+/// Unlike the `TypeInfo` derive of eosio-scale-info, generic, array and tuple field types are supported.
+/// Like the derive, the structs used by the fields are registered in the scale-info type map
+/// but the struct itself is not, the contract adds it to the ABI directly.
+pub(crate) fn generate_struct_type_info(input: &syn::DeriveInput) -> Result<TokenStream2, syn::Error> {
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(fields), .. }) => {
+            fields.named.iter().collect::<Vec<_>>()
+        }
+        syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Unit, .. }) => {
+            Vec::new()
+        }
+        _ => {
+            return Err(format_err_spanned!(
+                input,
+                "only structs with named fields are supported"
+            ));
+        }
+    };
+    return generate_composite_type_info(input, &fields, false);
+}
+
+/// Generates `Packer` and `TypeInfo` for a struct with named fields,
+/// fields with `#[packer(skip)]` are neither packed nor described in the ABI.
+pub fn generate_packer(input: TokenStream2) -> Result<TokenStream2, syn::Error> {
+    let input: syn::DeriveInput = syn::parse2(input)?;
+    let ident = &input.ident;
+    let span = input.span();
+
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(fields), .. }) => {
+            fields.named.iter().collect::<Vec<_>>()
+        }
+        syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Unit, .. }) => {
+            Vec::new()
+        }
+        _ => {
+            return Err(format_err_spanned!(
+                input,
+                "Packer can only be derived for structs with named fields, use ChainVariant for enums"
+            ));
+        }
+    };
+
+    let mut packed_fields: Vec<&syn::Field> = Vec::new();
+    for field in fields {
+        if !is_skipped(field)? {
+            packed_fields.push(field);
+        }
+    }
+
+    for (i, field) in packed_fields.iter().enumerate() {
+        if Contract::is_binary_extension_type(&field.ty) && i + 1 != packed_fields.len() {
+            return Err(format_err_spanned!(
+                field,
+                "BinaryExtension type can only appear at the last field of a struct",
+            ));
+        }
+    }
+
+    let get_size = packed_fields.iter().map(|field| {
+        let field_ident = &field.ident;
+        quote_spanned!(field.span()=>
+            _size += ::rust_chain::serializer::Packer::size(&self.#field_ident);
+        )
+    });
+
+    let serialize = packed_fields.iter().map(|field| {
+        let field_ident = &field.ident;
+        quote_spanned!(field.span()=>
+            ::rust_chain::serializer::Packer::pack(&self.#field_ident, enc);
+        )
+    });
+
+    let deserialize = packed_fields.iter().map(|field| {
+        let field_ident = &field.ident;
+        let ty = &field.ty;
+        quote_spanned!(field.span()=>
+            dec.unpack::<#ty>(&mut self.#field_ident);
+        )
+    });
+
+    let type_info = generate_composite_type_info(&input, &packed_fields, true)?;
+
+    let generics = add_bounds(&input.generics, quote!(::rust_chain::serializer::Packer));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote_spanned!(span =>
+        impl #impl_generics ::rust_chain::serializer::Packer for #ident #ty_generics #where_clause {
+            fn size(&self) -> usize {
+                #[allow(unused_mut)]
+                let mut _size: usize = 0;
+                #( #get_size )*
+                return _size;
+            }
+
+            fn pack(&self, enc: &mut ::rust_chain::serializer::Encoder) -> usize {
+                let pos = enc.get_size();
+                #( #serialize )*
+                enc.get_size() - pos
+            }
+
+            fn unpack(&mut self, data: &[u8]) -> usize {
+                #[allow(unused_mut)]
+                let mut dec = ::rust_chain::serializer::Decoder::new(data);
+                #( #deserialize )*
+                return dec.get_pos();
+            }
+        }
+
+        #type_info
+    ))
+}
+
+/// Generates `Packer`, `Default` and `TypeInfo` for an enum whose variants all have a single unnamed field,
+/// which is an ABI variant. `Default` returns the first variant with a default value.
+pub fn generate_chain_variant(input: TokenStream2) -> Result<TokenStream2, syn::Error> {
+    let input: syn::DeriveInput = syn::parse2(input)?;
+    let ident = &input.ident;
+    let span = input.span();
+
+    let variants = match &input.data {
+        syn::Data::Enum(x) => &x.variants,
+        _ => {
+            return Err(format_err_spanned!(
+                input,
+                "ChainVariant can only be derived for enums"
+            ));
+        }
+    };
+
+    if variants.is_empty() {
+        return Err(format_err_spanned!(
+            input,
+            "ChainVariant requires at least one variant"
+        ));
+    }
+
+    let mut types: Vec<&syn::Type> = Vec::new();
+    for v in variants {
+        match &v.fields {
+            syn::Fields::Unnamed(x) if x.unnamed.len() == 1 => {
+                types.push(&x.unnamed[0].ty);
+            }
+            _ => {
+                return Err(format_err_spanned!(
+                    v,
+                    "variant of ChainVariant must have exactly one unnamed field"
+                ));
+            }
+        }
+    }
+
+    let variant_idents: Vec<&syn::Ident> = variants.iter().map(|v| &v.ident).collect();
+    let indexes: Vec<u8> = (0..variants.len()).map(|i| i as u8).collect();
+    let first_variant = variant_idents[0];
+
+    let mut used_types = Vec::new();
+    for ty in &types {
+        get_used_types(ty, &mut used_types)?;
+    }
+
+    let type_def_variants = variant_idents.iter().zip(types.iter()).zip(indexes.iter()).map(|((variant_ident, ty), index)| {
+        let name_lit = proc_macro2::Literal::string(&variant_ident.to_string());
+        let type_name_lit = proc_macro2::Literal::string(&type_name_string(ty));
+        quote!{
+            .variant(#name_lit, |v| v.index(#index).fields(Fields::unnamed().field(|f| f.ty::<#ty>().type_name(#type_name_lit))))
+        }
+    });
+    let type_info = generate_type_info(&input, quote!{
+        .variant(Variants::new()#( #type_def_variants )*)
+    }, used_types, true);
+
+    let generics = add_bounds(&input.generics, quote!(::rust_chain::serializer::Packer + Default));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote_spanned!(span =>
+        impl #impl_generics Default for #ident #ty_generics #where_clause {
+            #[inline]
+            fn default() -> Self {
+                #ident::#first_variant(Default::default())
+            }
+        }
+
+        impl #impl_generics ::rust_chain::serializer::Packer for #ident #ty_generics #where_clause {
+            fn size(&self) -> usize {
+                match self {
+                    #( #ident::#variant_idents(x) => 1 + ::rust_chain::serializer::Packer::size(x), )*
+                }
+            }
+
+            fn pack(&self, enc: &mut ::rust_chain::serializer::Encoder) -> usize {
+                let pos = enc.get_size();
+                match self {
+                    #(
+                        #ident::#variant_idents(x) => {
+                            ::rust_chain::serializer::Packer::pack(&#indexes, enc);
+                            ::rust_chain::serializer::Packer::pack(x, enc);
+                        }
+                    )*
+                }
+                enc.get_size() - pos
+            }
+
+            fn unpack(&mut self, data: &[u8]) -> usize {
+                let mut dec = ::rust_chain::serializer::Decoder::new(data);
+                let mut index: u8 = 0;
+                dec.unpack(&mut index);
+                match index {
+                    #(
+                        #indexes => {
+                            let mut v: #types = Default::default();
+                            dec.unpack(&mut v);
+                            *self = #ident::#variant_idents(v);
+                        }
+                    )*
+                    _ => {
+                        ::rust_chain::check(false, "bad variant index!");
+                    }
+                }
+                return dec.get_pos();
+            }
+        }
+
+        #type_info
+    ))
+}
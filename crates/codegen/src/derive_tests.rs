@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use crate::derive::{
+        generate_chain_variant,
+        generate_packer,
+    };
+
+    #[test]
+    fn test_derive_packer() {
+        let code = generate_packer(quote::quote! {
+            pub struct MyData<T> {
+                a: u64,
+                #[packer(skip)]
+                cache: Vec<u8>,
+                b: BinaryExtension<T>,
+            }
+        }).unwrap().to_string();
+        assert!(code.contains("impl < T : :: rust_chain :: serializer :: Packer >"));
+        assert!(code.contains("self . a"));
+        assert!(!code.contains("self . cache"));
+        assert!(code.contains(r#"name ("b") . type_name ("BinaryExtension<T>")"#));
+
+        let ret = generate_packer(quote::quote! {
+            pub struct MyData {
+                b: BinaryExtension<u64>,
+                a: u64,
+            }
+        });
+        assert!(ret.err().unwrap().to_compile_error().to_string().contains("BinaryExtension type can only appear at the last field of a struct"));
+
+        let ret = generate_packer(quote::quote! {
+            pub struct MyData {
+                #[packer(ignore)]
+                a: u64,
+            }
+        });
+        assert!(ret.err().unwrap().to_compile_error().to_string().contains("unknown packer attribute: ignore"));
+
+        let code = generate_packer(quote::quote! {
+            pub struct MyData {
+                owner: rust_chain::Name,
+                ids: Vec<core::primitive::u64>,
+                pair: (::rust_chain::Name, [rust_chain::Checksum256; 2]),
+            }
+        }).unwrap().to_string();
+        assert!(code.contains(r#"name ("owner") . type_name ("Name")"#));
+        assert!(code.contains(r#"name ("ids") . type_name ("Vec<u64>")"#));
+        assert!(code.contains(r#"name ("pair") . type_name ("(Name,[Checksum256; 2])")"#));
+
+        let ret = generate_packer(quote::quote! {
+            pub struct MyData(u64);
+        });
+        assert!(ret.err().unwrap().to_compile_error().to_string().contains("Packer can only be derived for structs with named fields"));
+    }
+
+    #[test]
+    fn test_derive_chain_variant() {
+        let code = generate_chain_variant(quote::quote! {
+            pub enum MyVariant {
+                A(u64),
+                B(String),
+            }
+        }).unwrap().to_string();
+        assert!(code.contains("MyVariant :: A (Default :: default ())"));
+
+        let ret = generate_chain_variant(quote::quote! {
+            pub enum MyVariant {
+                A(u64, u64),
+            }
+        });
+        assert!(ret.err().unwrap().to_compile_error().to_string().contains("variant of ChainVariant must have exactly one unnamed field"));
+    }
+}
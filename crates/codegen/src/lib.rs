@@ -9,8 +9,14 @@ mod table;
 mod attrs;
 mod contract;
 mod contract_tests;
+mod derive;
+mod derive_tests;
 
 pub use self::{
     contract::Contract,
+    derive::{
+        generate_chain_variant,
+        generate_packer,
+    },
     fixedstring::FixedString,
 };
@@ -7,4 +7,8 @@ pub struct Table {
     pub item: syn::ItemStruct,
     pub table_name: FixedString,
     pub singleton: bool,
+    /// `version = N`, rows are prefixed with the version of the struct
+    pub version: Option<u8>,
+    /// `migrate = "action"`, name of the generated action that upgrades old rows
+    pub migrate_action: Option<FixedString>,
 }
@@ -0,0 +1,19 @@
+use proc_macro2::TokenStream as TokenStream2;
+
+pub fn generate_packer(input: TokenStream2) -> TokenStream2 {
+    match eosio_codegen::generate_packer(input) {
+        Ok(tokens) => tokens,
+        Err(err) => {
+            err.to_compile_error()
+        },
+    }
+}
+
+pub fn generate_chain_variant(input: TokenStream2) -> TokenStream2 {
+    match eosio_codegen::generate_chain_variant(input) {
+        Ok(tokens) => tokens,
+        Err(err) => {
+            err.to_compile_error()
+        },
+    }
+}
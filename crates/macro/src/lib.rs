@@ -3,16 +3,41 @@
 extern crate proc_macro;
 
 mod contract;
+mod derive;
 
 use proc_macro::{
     TokenStream,
 };
 
+/// Generates the code of a contract from the module it is applied to.
+///
+/// In wasm builds the contract exports the `apply` entry point. A crate which depends on the contract,
+/// e.g. to send its actions through `actions` or to reuse its structs, enables the `no-entry` feature
+/// of the contract so that it does not link a second `apply`:
+///
+/// ```toml
+/// [dependencies]
+/// hello = { path = "../hello", default-features = false, features = ["no-entry"] }
+/// ```
+///
+/// The contract declares the feature in its `Cargo.toml` with `no-entry = []`.
 #[proc_macro_attribute]
 pub fn contract(attr: TokenStream, item: TokenStream) -> TokenStream {
     contract::generate(attr.into(), item.into()).into()
 }
 
+/// Derives `Packer` for a struct with named fields, `#[packer(skip)]` excludes a field.
+#[proc_macro_derive(Packer, attributes(packer))]
+pub fn derive_packer(item: TokenStream) -> TokenStream {
+    derive::generate_packer(item.into()).into()
+}
+
+/// Derives `Packer` and `Default` for an enum of single field variants, which is an ABI variant.
+#[proc_macro_derive(ChainVariant)]
+pub fn derive_chain_variant(item: TokenStream) -> TokenStream {
+    derive::generate_chain_variant(item.into()).into()
+}
+
 // #[proc_macro_attribute]
 // pub fn chain(_attr: TokenStream, item: TokenStream) -> TokenStream {
 //     item
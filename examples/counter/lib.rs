@@ -1,7 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 #[rust_chain::contract]
-mod token {
+pub mod token {
     use rust_chain::{
         Name,
         chain_println,
@@ -14,6 +14,12 @@ mod token {
         count: u64
     }
 
+    #[chain(packer)]
+    pub struct CounterState {
+        pub count: u64,
+        pub owner: Name,
+    }
+
     #[chain(main)]
     pub struct Contract {
         receiver: Name,
@@ -31,17 +37,26 @@ mod token {
         }
 
         #[chain(action = "inc")]
-        pub fn inc_count(&self) {
+        pub fn inc_count(&self) -> u64 {
             let db = Counter::new_table(self.receiver);
             let it = db.find(1u64);
             if let Some(mut value) = db.get(&it) {
                 value.count += 1;
                 db.update(&it, &value, self.receiver);
                 chain_println!("count is", value.count);
+                return value.count;
             } else {
                 db.store(&Counter{key: 1, count: 1}, self.receiver);
                 chain_println!("count is", 1);
+                return 1;
             }
         }
+
+        #[chain(action = "state")]
+        pub fn state(&self) -> CounterState {
+            let db = Counter::new_table(self.receiver);
+            let count = db.get(&db.find(1u64)).map(|value| value.count).unwrap_or(0);
+            return CounterState{count: count, owner: self.receiver};
+        }
     }
 }
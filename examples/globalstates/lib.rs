@@ -4,7 +4,10 @@
 mod test {
     use rust_chain::{
         Name,
+        SAME_PAYER,
+        name,
         chain_println,
+        db::Singleton,
     };
 
     #[chain(table="states", singleton)]
@@ -12,6 +15,12 @@ mod test {
         count: u64
     }
 
+    #[chain(table="config", singleton)]
+    pub struct Config {
+        fee: u64,
+        updates: u64,
+    }
+
     #[chain(main)]
     #[allow(dead_code)]
     pub struct Contract {
@@ -40,6 +49,27 @@ mod test {
             self.states.count += 1;
             chain_println!("++++count:", self.states.count);
         }
+
+        #[chain(action = "setfee")]
+        pub fn set_fee(&self, scope: Name, fee: u64) {
+            let db = Config::new_table_with_scope(self.receiver, scope);
+            let mut config = db.get_or_create(self.receiver, Config{fee: 0, updates: 0});
+            config.fee = fee;
+            config.updates += 1;
+            db.set(&config, SAME_PAYER);
+        }
+
+        #[chain(action = "rmfee")]
+        pub fn remove_fee(&self, scope: Name) {
+            Config::new_table_with_scope(self.receiver, scope).remove();
+        }
+
+        #[chain(action = "showfee")]
+        pub fn show_fee(&self, scope: Name) {
+            let db = Singleton::<Config>::new(self.receiver, scope, name!("config"));
+            let config = db.get_or_default();
+            chain_println!("exists:", db.exists(), "fee:", config.fee, "updates:", config.updates);
+        }
     }
 
     impl Drop for Contract {
@@ -1,11 +1,50 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use rust_chain::{
+    Name,
+    ChainVariant,
+    Packer,
+    string::String,
+};
+
+/// Packed outside of the contract module, as a type shared by several contracts would be.
+#[derive(Packer, Default)]
+pub struct Greeting {
+    pub from: Name,
+    pub text: String,
+    #[packer(skip)]
+    pub replies: u32,
+}
+
+/// Field types may be written with their module paths.
+#[derive(Packer, Default)]
+pub struct Roster {
+    pub owner: rust_chain::Name,
+    pub ids: rust_chain::vec::Vec<core::primitive::u64>,
+}
+
+///
+#[derive(ChainVariant)]
+pub enum Mood {
+    Level(u8),
+    Text(String),
+}
+
 #[rust_chain::contract]
 mod helloworld {
     use rust_chain::{
         Name,
         chain_println,
     };
+    use super::{
+        Greeting,
+        Mood,
+        Roster,
+    };
+    use rust_chain::collections::{
+        BTreeMap,
+        BTreeSet,
+    };
 
     #[chain(main)]
     #[allow(dead_code)]
@@ -28,5 +67,40 @@ mod helloworld {
         pub fn say_hello(&self) {
             chain_println!("hello,world!");
         }
+
+        #[chain(action = "greet")]
+        pub fn greet(&self, scores: BTreeMap<Name, u64>, tags: BTreeSet<Name>, code: [u8; 4], pair: (Name, u64), boxed: Box<u32>) {
+            for (name, score) in &scores {
+                chain_println!("score:", *name, *score);
+            }
+            for tag in &tags {
+                chain_println!("tag:", *tag);
+            }
+            chain_println!("code:", code[0], code[3], "pair:", pair.0, pair.1, "boxed:", *boxed);
+        }
+
+        /// `feed` and `payload` borrow from the action data instead of being copied.
+        #[chain(action = "report")]
+        pub fn report(&self, feed: &str, payload: &[u8], round: u32) {
+            let sum: u64 = payload.iter().map(|b| *b as u64).sum();
+            chain_println!("report:", feed, payload.len(), sum, round);
+        }
+
+        #[chain(action = "reply")]
+        pub fn reply(&self, greeting: Greeting, mood: Mood) {
+            match mood {
+                Mood::Level(level) => {
+                    chain_println!(greeting.from, greeting.text, "level:", level, greeting.replies);
+                }
+                Mood::Text(text) => {
+                    chain_println!(greeting.from, greeting.text, "mood:", text, greeting.replies);
+                }
+            }
+        }
+
+        #[chain(action = "roster")]
+        pub fn roster(&self, roster: Roster, editor: rust_chain::Name) {
+            chain_println!(roster.owner, roster.ids.len(), editor);
+        }
     }
 }
@@ -4,20 +4,12 @@
 mod inline_action_example {
     use rust_chain::{
         Name,
-        action::{
-            Action,
-            PermissionLevel,    
-        },
+        action::PermissionLevel,
         name,
         ACTIVE,
         chain_println,
     };
 
-    #[chain(packer)]
-    struct SayGoodbye {
-        name: String
-    }
-
     #[chain(main)]
     pub struct Contract {
         receiver: Name,
@@ -38,9 +30,7 @@ mod inline_action_example {
         pub fn say_hello(&self, name: String) {
             chain_println!("hello", name);
             let perm = PermissionLevel{actor: name!("hello"), permission: ACTIVE};
-            let say_goodbye = SayGoodbye{name: name};
-            let action = Action::new(name!("hello"), name!("saygoodbye"), perm, &say_goodbye);
-            action.send();
+            actions::Saygoodbye::new(self.receiver).send(perm, name);
         }
 
         #[chain(action = "saygoodbye")]
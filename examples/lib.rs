@@ -31,7 +31,14 @@ fn native_apply(receiver: u64, first_receiver: u64, action: u64) {
 #[cfg(test)]
 mod tests {
 
-    use rust_chain::ChainTester;
+    use rust_chain::{
+        ChainTester,
+        MockTester,
+        Encoder,
+        Name,
+        Asset,
+        PermissionLevel,
+    };
     use rust_chain::serializer::Packer as _;
     use rust_chain::chaintester::{
         GetTableRowsPrams,
@@ -262,6 +269,139 @@ mod tests {
         tester.produce_block();
     }
 
+    #[test]
+    fn test_globalstates_mock() {
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(globalstates::contract_apply)).unwrap();
+
+        let permissions = r#"{"hello": "active"}"#;
+        let show_fee = |tester: &mut MockTester, scope: &'static str| -> String {
+            let ret = tester.push_action("hello", "showfee", Encoder::pack(&Name::new(scope)).into(), permissions).unwrap();
+            ret["action_traces"][0]["console"].as_str().unwrap().to_string()
+        };
+        assert_eq!(show_fee(&mut tester, "alice"), "exists: false fee: 0 updates: 0\n");
+
+        for fee in [10u64, 20] {
+            let mut args = Encoder::pack(&Name::new("alice"));
+            args.extend(Encoder::pack(&fee));
+            tester.push_action("hello", "setfee", args.into(), permissions).unwrap();
+        }
+        assert_eq!(show_fee(&mut tester, "alice"), "exists: true fee: 20 updates: 2\n");
+        assert_eq!(show_fee(&mut tester, "bob"), "exists: false fee: 0 updates: 0\n");
+
+        tester.push_action("hello", "rmfee", Encoder::pack(&Name::new("alice")).into(), permissions).unwrap();
+        assert_eq!(show_fee(&mut tester, "alice"), "exists: false fee: 0 updates: 0\n");
+    }
+
+    fn ram_delta(deltas: &[(String, i64)], account: &str) -> i64 {
+        return deltas.iter().find(|(name, _)| name == account).map_or(0, |(_, delta)| *delta);
+    }
+
+    /// Pushes the migrate action of `contract`, returns its return value and the RAM deltas of the accounts.
+    fn push_migrate(tester: &mut MockTester, contract: &str, start: u64, limit: u32) -> ((u32, Option<u64>), Vec<(String, i64)>) {
+        let mut args = Encoder::pack(&Name::new(""));
+        args.extend(Encoder::pack(&start));
+        args.extend(Encoder::pack(&limit));
+        let permissions = format!(r#"{{"{}": "active"}}"#, contract);
+        let ret = tester.push_action(contract, "migrate", args.into(), &permissions).unwrap();
+        let deltas = ret["account_ram_deltas"].as_array().unwrap().iter()
+            .map(|delta| (delta["account"].as_str().unwrap().to_string(), delta["delta"].as_i64().unwrap()))
+            .collect();
+        return (rust_chain::decode_action_return_value::<(u32, Option<u64>)>(&ret).unwrap(), deltas);
+    }
+
+    #[test]
+    fn test_table_migration_mock() {
+        let mut tester = MockTester::new();
+        tester.create_account("eosio", "alice", "", "", 0, 0, 0).unwrap();
+        let permissions = r#"{"hello": "active"}"#;
+        let alice_permissions = r#"{"alice": "active"}"#;
+
+        // rows written by a plain table struct have no version prefix, the first bytes of their primary keys
+        // look like a version prefix, e.g. 2 is the version of the current struct
+        let marker_id = u64::from_le_bytes([0xff, b'V', b'E', b'R', 2, 0, 0, 0]);
+        tester.set_native_apply("hello", Some(migrationv0::contract_apply)).unwrap();
+        for id in [1u64, 2, marker_id] {
+            let mut args = Encoder::pack(&Name::new("alice"));
+            args.extend([id.to_le_bytes(), (id % 1000 * 100).to_le_bytes()].concat());
+            tester.push_action("hello", "open", args.into(), alice_permissions).unwrap();
+        }
+
+        // upgrade the contract, old rows are migrated on read
+        tester.set_native_apply("hello", Some(migrationv2::contract_apply)).unwrap();
+        for (id, balance) in [(1u64, 100u64), (2, 200), (marker_id, marker_id % 1000 * 100)] {
+            let ret = tester.push_action("hello", "show", id.to_le_bytes().to_vec().into(), permissions).unwrap();
+            assert_eq!(ret["action_traces"][0]["console"].as_str().unwrap(), format!("balance: {} memo: v0\n", balance));
+        }
+
+        let mut args = Encoder::pack(&2u64);
+        args.extend(Encoder::pack(&String::from("hello")));
+        tester.push_action("hello", "setmemo", args.clone().into(), permissions).unwrap_err()
+            .check_err("rows written before the table was versioned must be migrated first");
+
+        // the migrated rows grow and stay billed to alice
+        let mut start = 0u64;
+        let mut progress = Vec::new();
+        loop {
+            let ((count, next), ret) = push_migrate(&mut tester, "hello", start, 1);
+            assert_eq!(ram_delta(&ret, "alice"), 4);
+            progress.push((count, next));
+            match next {
+                Some(next) => start = next,
+                None => break,
+            }
+        }
+        assert_eq!(progress, vec![(1, Some(2)), (1, Some(marker_id)), (1, None)]);
+        assert_eq!(push_migrate(&mut tester, "hello", 0, 10).0, (0, None));
+
+        tester.push_action("hello", "setmemo", args.into(), permissions).unwrap();
+        let ret = tester.push_action("hello", "show", 2u64.to_le_bytes().to_vec().into(), permissions).unwrap();
+        assert_eq!(ret["action_traces"][0]["console"].as_str().unwrap(), "balance: 200 memo: hello\n");
+        let ret = tester.push_action("hello", "show", 1u64.to_le_bytes().to_vec().into(), permissions).unwrap();
+        assert_eq!(ret["action_traces"][0]["console"].as_str().unwrap(), "balance: 100 memo: v0\n");
+
+        let rows = tester.get_table_rows(false, "hello", "", "accounts", "", "", 10).unwrap();
+        assert_eq!(rows["rows"].as_array().unwrap().len(), 3);
+        for row in rows["rows"].as_array().unwrap() {
+            assert!(row["data"].as_str().unwrap().starts_with("02"));
+        }
+
+        // rows of version 1 of a table which was versioned from the start
+        tester.set_native_apply("bob", Some(migrationv1::contract_apply)).unwrap();
+        for id in 1u64..=2 {
+            let mut args = Encoder::pack(&Name::new("alice"));
+            args.extend([id.to_le_bytes(), (id * 100).to_le_bytes()].concat());
+            tester.push_action("bob", "open", args.into(), alice_permissions).unwrap();
+        }
+        tester.set_native_apply("bob", Some(migrationv2::contract_apply)).unwrap();
+        let ret = tester.push_action("bob", "show", 2u64.to_le_bytes().to_vec().into(), r#"{"bob": "active"}"#).unwrap();
+        assert_eq!(ret["action_traces"][0]["console"].as_str().unwrap(), "balance: 200 memo: v1\n");
+        let ((count, next), ret) = push_migrate(&mut tester, "bob", 0, 10);
+        assert_eq!((count, next), (2, None));
+        assert_eq!(ram_delta(&ret, "alice"), 6);
+        assert_eq!(ram_delta(&ret, "bob"), 0);
+
+        let abi: String = migrationv2::generate_abi().split_whitespace().collect();
+        assert!(abi.contains(r#"{"name":"Account_row","base":"","fields":[{"name":"version","type":"uint8"},{"name":"row","type":"Account"}]}"#));
+        assert!(abi.contains(r#"{"name":"accounts","type":"Account_row""#));
+        assert!(abi.contains(r#"{"name":"migrate","base":"","fields":[{"name":"scope","type":"name"},{"name":"start","type":"uint64"},{"name":"limit","type":"uint32"}]}"#));
+        assert!(abi.contains(r#"{"name":"migrate","result_type":"pair_uint32_uint64_opt"}"#));
+    }
+
+    #[test]
+    fn test_migrated_row_size() {
+        let old = Encoder::pack(&migrationv2::migrationv2::AccountV1{id: 7, balance: 700});
+        for version in [0u8, 1] {
+            // a row of an older version followed by other data
+            let mut data = vec![version];
+            data.extend(&old);
+            data.extend([0xaa, 0xbb]);
+            let mut account = migrationv2::migrationv2::Account::default();
+            assert_eq!(account.unpack(&data), 1 + old.len());
+            assert_eq!(account.balance, 700);
+        }
+    }
+
     #[test]
     fn test_inlineaction() {
         let abi = &inlineaction::generate_abi();
@@ -466,4 +606,401 @@ mod tests {
         let r = tester.get_table_rows(true, "hello", "helloworld11", "accounts", "", "", 1).unwrap();
         assert!(ret["rows"][0].is_null());
     }
+
+    #[test]
+    fn test_counter_mock() {
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(counter::contract_apply)).unwrap();
+
+        let permissions = r#"
+        {
+            "hello": "active"
+        }
+        "#;
+        let ret = tester.push_action("hello", "inc", vec![].into(), permissions).unwrap();
+        assert!(ret["action_traces"][0]["console"] == "count is 1\n");
+        tester.produce_block();
+
+        let ret = tester.push_action("hello", "inc", vec![].into(), permissions).unwrap();
+        assert!(rust_chain::decode_action_return_value::<u64>(&ret) == Some(2));
+        let ret = tester.get_table_rows(false, "hello", "", "counter", "", "", 10).unwrap();
+        assert!(ret["rows"][0]["data"] == "01000000000000000200000000000000");
+
+        let ret = tester.push_action("hello", "state", vec![].into(), permissions).unwrap();
+        let state = rust_chain::decode_action_return_value::<counter::token::CounterState>(&ret).unwrap();
+        assert!(state.count == 2 && state.owner == Name::new("hello"));
+
+        let abi: String = counter::generate_abi().split_whitespace().collect();
+        assert!(abi.contains(r#""action_results":[{"name":"inc","result_type":"uint64"},{"name":"state","result_type":"CounterState"}]"#));
+        assert!(abi.contains(r#"{"name":"CounterState","base":"","fields":[{"name":"count","type":"uint64"},{"name":"owner","type":"name"}]}"#));
+    }
+
+    #[test]
+    fn test_container_params_mock() {
+        use std::collections::{BTreeMap, BTreeSet};
+
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(helloworld::contract_apply)).unwrap();
+        let scores = BTreeMap::from([(Name::new("bob"), 2u64), (Name::new("alice"), 1)]);
+        let tags = BTreeSet::from([Name::new("b"), Name::new("a")]);
+        let mut args = Encoder::pack(&scores);
+        args.extend(Encoder::pack(&tags));
+        args.extend(Encoder::pack(&[1u8, 2, 3, 4]));
+        args.extend(Encoder::pack(&(Name::new("carol"), 3u64)));
+        args.extend(Encoder::pack(&Box::new(5u32)));
+        let ret = tester.push_action("hello", "greet", args.into(), r#"{"hello": "active"}"#).unwrap();
+        let console = ret["action_traces"][0]["console"].as_str().unwrap();
+        assert_eq!(console, "score: alice 1\nscore: bob 2\ntag: a\ntag: b\ncode: 1 4 pair: carol 3 boxed: 5\n");
+
+        let abi: String = helloworld::generate_abi().split_whitespace().collect();
+        assert!(abi.contains(r#"{"name":"pair_name_uint64","base":"","fields":[{"name":"first","type":"name"},{"name":"second","type":"uint64"}]}"#));
+        assert!(abi.contains(r#"{"name":"scores","type":"pair_name_uint64[]"},{"name":"tags","type":"name[]"},{"name":"code","type":"uint8[4]"},{"name":"pair","type":"pair_name_uint64"},{"name":"boxed","type":"uint32"}"#));
+    }
+
+    #[test]
+    fn test_derive_packer_mock() {
+        use helloworld::{Greeting, Mood};
+
+        let greeting = Greeting{from: Name::new("alice"), text: "hi".into(), replies: 7};
+        let data = Encoder::pack(&greeting);
+        assert_eq!(data.len(), 8 + 3);
+        let mut decoded = Greeting::default();
+        decoded.unpack(&data);
+        assert!(decoded.from == greeting.from && decoded.text == greeting.text && decoded.replies == 0);
+
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(helloworld::contract_apply)).unwrap();
+        let mut args = data.clone();
+        args.extend(Encoder::pack(&Mood::Text("glad".into())));
+        let ret = tester.push_action("hello", "reply", args.into(), r#"{"hello": "active"}"#).unwrap();
+        assert_eq!(ret["action_traces"][0]["console"], "alice hi mood: glad 0\n");
+        let mut args = data;
+        args.extend(Encoder::pack(&Mood::Level(3)));
+        let ret = tester.push_action("hello", "reply", args.into(), r#"{"hello": "active"}"#).unwrap();
+        assert_eq!(ret["action_traces"][0]["console"], "alice hi level: 3 0\n");
+
+        #[derive(rust_chain::Packer, Default)]
+        struct Wrapper<T> {
+            value: T,
+        }
+        let data = Encoder::pack(&Wrapper{value: Mood::Level(5)});
+        assert_eq!(data, [0, 5]);
+        let mut decoded = Wrapper::<Mood>::default();
+        decoded.unpack(&data);
+        assert!(matches!(decoded.value, Mood::Level(5)));
+
+        let abi: String = helloworld::generate_abi().split_whitespace().collect();
+        assert!(abi.contains(r#"{"name":"Greeting","base":"","fields":[{"name":"from","type":"name"},{"name":"text","type":"string"}]}"#));
+        assert!(abi.contains(r#"{"name":"Mood","types":["uint8","string"]}"#));
+
+        let roster = helloworld::Roster{owner: Name::new("alice"), ids: vec![1, 2, 3]};
+        let mut args = Encoder::pack(&roster);
+        args.extend(Encoder::pack(&Name::new("bob")));
+        let ret = tester.push_action("hello", "roster", args.into(), r#"{"hello": "active"}"#).unwrap();
+        assert_eq!(ret["action_traces"][0]["console"], "alice 3 bob\n");
+        assert!(abi.contains(r#"{"name":"Roster","base":"","fields":[{"name":"owner","type":"name"},{"name":"ids","type":"uint64[]"}]}"#));
+        assert!(abi.contains(r#"{"name":"roster","base":"","fields":[{"name":"roster","type":"Roster"},{"name":"editor","type":"name"}]}"#));
+    }
+
+    #[test]
+    fn test_borrowed_args_mock() {
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(helloworld::contract_apply)).unwrap();
+        let payload = vec![7u8; 300];
+        let ret = helloworld::client::report(&mut tester, "hello", "btc", &payload, 3).auth("hello").send().unwrap();
+        assert_eq!(ret["action_traces"][0]["console"], "report: btc 300 2100 3\n");
+
+        let abi: String = helloworld::generate_abi().split_whitespace().collect();
+        assert!(abi.contains(r#"{"name":"report","base":"","fields":[{"name":"feed","type":"string"},{"name":"payload","type":"bytes"},{"name":"round","type":"uint32"}]}"#));
+    }
+
+    #[test]
+    fn test_secondary_index_abi() {
+        let abi: String = secondaryindex::generate_abi().split_whitespace().collect();
+        assert!(abi.contains(r#""key_names":["value"],"key_types":["i64"]"#));
+        assert!(abi.contains(r#""key_names":["by_owner_time","by_owner_price"],"key_types":["i128","i256"]"#));
+    }
+
+    #[test]
+    fn test_composite_secondary_key_mock() {
+        use rust_chain::db::SecondaryValueInterface as _;
+        use rust_chain::tester::TableReader as _;
+        use secondaryindex::secondaryindex::Order;
+
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(secondaryindex::contract_apply)).unwrap();
+        let orders = [
+            (1u64, "alice", 5i64, 150u64),
+            (2, "bob", -7, 50),
+            (3, "alice", -3, 20),
+            (4, "alice", 0, 100),
+            (5, "bob", 1, 10),
+        ];
+        for (id, owner, created, price) in orders {
+            let mut args = Encoder::pack(&id);
+            args.extend(Encoder::pack(&Name::new(owner)));
+            args.extend(Encoder::pack(&created));
+            args.extend(Encoder::pack(&price));
+            tester.push_action("hello", "addorder", args.into(), r#"{"hello": "active"}"#).unwrap();
+        }
+
+        let args = Encoder::pack(&Name::new("alice"));
+        let ret = tester.push_action("hello", "listorders", args.into(), r#"{"hello": "active"}"#).unwrap();
+        let console = ret["action_traces"][0]["console"].as_str().unwrap();
+        assert_eq!(console, "time: 3 -3\ntime: 4 0\ntime: 1 5\nprice: 3\nprice: 4\nprice: 1\nprice: 5\nprice: 2\n");
+
+        let args = [1u64.to_le_bytes(), 5u64.to_le_bytes()].concat();
+        tester.push_action("hello", "setprice", args.into(), r#"{"hello": "active"}"#).unwrap();
+        let args = Encoder::pack(&Name::new("alice"));
+        let ret = tester.push_action("hello", "listorders", args.into(), r#"{"hello": "active"}"#).unwrap();
+        let console = ret["action_traces"][0]["console"].as_str().unwrap();
+        assert!(console.ends_with("price: 1\nprice: 3\nprice: 4\nprice: 5\nprice: 2\n"));
+        let rows = tester.get_table::<Order>("hello", "", "orders").lower_bound("1").upper_bound("1").iter();
+        assert!(rows.map(|row| row.unwrap()).map(|(_, payer, row)| (payer, row.price)).eq([(Name::new("hello"), 5)]));
+
+        let key = Order::by_owner_time_key(Name::new("alice"), -3);
+        let order = Order{id: 3, owner: Name::new("alice"), created: -3, price: 20};
+        assert!(order.get_secondary_value(0) == key.into());
+        let mut decoded = Order::default();
+        decoded.set_secondary_value(1, order.get_secondary_value(1));
+        assert!(decoded.owner == order.owner && decoded.price == order.price && decoded.id == order.id);
+    }
+
+    #[test]
+    fn test_ram_usage_mock() {
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(secondaryindex::contract_apply)).unwrap();
+        let mut estimates = Vec::new();
+        for id in [1u64, 2] {
+            let mut args = Encoder::pack(&id);
+            args.extend(Encoder::pack(&Name::new("alice")));
+            args.extend(Encoder::pack(&0i64));
+            args.extend(Encoder::pack(&100u64));
+            let ret = tester.push_action("hello", "addorder", args.into(), r#"{"hello": "active"}"#).unwrap();
+            let console = ret["action_traces"][0]["console"].as_str().unwrap();
+            let estimate: i64 = console.trim().strip_prefix("ram: ").unwrap().parse().unwrap();
+            let deltas = ret["account_ram_deltas"].as_array().unwrap();
+            assert_eq!(deltas.len(), 1);
+            assert_eq!(deltas[0]["account"], "hello");
+            assert_eq!(deltas[0]["delta"].as_i64(), Some(estimate));
+            estimates.push(estimate);
+        }
+        // the first row also creates the primary table and both secondary tables
+        assert_eq!(estimates, [32 + 108 + 136 + 152 + 108 * 3, 32 + 108 + 136 + 152]);
+
+        let args = [1u64.to_le_bytes(), 5u64.to_le_bytes()].concat();
+        let ret = tester.push_action("hello", "setprice", args.into(), r#"{"hello": "active"}"#).unwrap();
+        assert!(ret["account_ram_deltas"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_secondary_index_mock() {
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(secondaryindex::contract_apply)).unwrap();
+
+        let permissions = r#"
+        {
+            "hello": "active"
+        }
+        "#;
+        for (key, value) in [(1u64, 11u64), (2, 22)] {
+            let args = [key.to_le_bytes(), value.to_le_bytes()].concat();
+            tester.push_action("hello", "test1", args.into(), permissions).unwrap();
+        }
+
+        let ret = tester.push_action("hello", "test2", 20u64.to_le_bytes().to_vec().into(), permissions).unwrap();
+        assert!(ret["action_traces"][0]["console"].as_str().unwrap().contains("++++primary value 2 secondary value: 22"));
+        let ret = tester.push_action("hello", "test2", 23u64.to_le_bytes().to_vec().into(), permissions).unwrap();
+        assert!(ret["action_traces"][0]["console"].as_str().unwrap().contains("++++primary value 2 secondary value: 23"));
+    }
+
+    #[test]
+    fn test_table_iterator_mock() {
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(secondaryindex::contract_apply)).unwrap();
+        for (key, value) in [(1u64, 11u64), (2, 3), (3, 5), (4, 1)] {
+            let args = [key.to_le_bytes(), value.to_le_bytes()].concat();
+            tester.push_action("hello", "test1", args.into(), r#"{"hello": "active"}"#).unwrap();
+        }
+
+        let args = [2u64.to_le_bytes(), 4u64.to_le_bytes()].concat();
+        let ret = tester.push_action("hello", "test3", args.into(), r#"{"hello": "active"}"#).unwrap();
+        let console = ret["action_traces"][0]["console"].as_str().unwrap();
+        assert_eq!(console, "range: 2 3\nrange: 3 5\nrev: 4\nrev: 3\nrev: 2\nrev: 1\nidx: 2 3\n");
+
+        let args = [5u64.to_le_bytes(), 1u64.to_le_bytes()].concat();
+        let ret = tester.push_action("hello", "test3", args.into(), r#"{"hello": "active"}"#).unwrap();
+        let console = ret["action_traces"][0]["console"].as_str().unwrap();
+        assert_eq!(console, "rev: 4\nrev: 3\nrev: 2\nrev: 1\n");
+    }
+
+    #[test]
+    fn test_secondary_index_rows_mock() {
+        use rust_chain::tester::TableReader as _;
+        use secondaryindex::secondaryindex::MyData;
+
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(secondaryindex::contract_apply)).unwrap();
+        for (key, value) in [(1u64, 11u64), (2, 3), (3, 5), (4, 1)] {
+            let args = [key.to_le_bytes(), value.to_le_bytes()].concat();
+            tester.push_action("hello", "test1", args.into(), r#"{"hello": "active"}"#).unwrap();
+        }
+
+        let ret = tester.push_action("hello", "test4", 5u64.to_le_bytes().to_vec().into(), r#"{"hello": "active"}"#).unwrap();
+        let console = ret["action_traces"][0]["console"].as_str().unwrap();
+        assert_eq!(console, "find: 5 3\nerase: 1 4\nrange: 3 2\nrange: 11 1\nrange: 105 3\n");
+
+        let rows: Vec<(u64, u64)> = tester.get_table::<MyData>("hello", "", "counter").iter()
+            .map(|row| row.unwrap())
+            .map(|(_, _, row)| (row.key, row.value))
+            .collect();
+        assert_eq!(rows, [(1, 11), (2, 3), (3, 105)]);
+        let keys: Vec<u64> = tester.get_table::<MyData>("hello", "", "counter").index(2, "i64").iter()
+            .map(|row| row.unwrap().0)
+            .collect();
+        assert_eq!(keys, [2, 1, 3]);
+    }
+
+    #[test]
+    fn test_get_table_mock() {
+        use rust_chain::tester::TableReader as _;
+        use secondaryindex::secondaryindex::MyData;
+
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(secondaryindex::contract_apply)).unwrap();
+        for (key, value) in [(1u64, 11u64), (2, 22), (3, 5)] {
+            let args = [key.to_le_bytes(), value.to_le_bytes()].concat();
+            tester.push_action("hello", "test1", args.into(), r#"{"hello": "active"}"#).unwrap();
+        }
+
+        fn keys(rows: rust_chain::tester::TableRows<MyData>) -> Vec<u64> {
+            rows.map(|row| {
+                let (key, payer, row) = row.unwrap();
+                assert!(payer == Name::new("hello") && row.key == key);
+                key
+            }).collect()
+        }
+        assert!(keys(tester.get_table::<MyData>("hello", "", "counter").iter()) == [1, 2, 3]);
+        assert!(keys(tester.get_table::<MyData>("hello", "", "counter").reverse().iter()) == [3, 2, 1]);
+        assert!(keys(tester.get_table::<MyData>("hello", "", "counter").lower_bound("2").upper_bound("3").iter()) == [2, 3]);
+        assert!(keys(tester.get_table::<MyData>("hello", "", "counter").limit(1).iter()) == [1, 2, 3]);
+
+        let rows = tester.get_table::<MyData>("hello", "", "counter").index(2, "i64").iter();
+        assert!(keys(rows) == [3, 1, 2]);
+        let rows = tester.get_table::<MyData>("hello", "", "counter")
+            .index(2, "i64")
+            .lower_bound("6")
+            .upper_bound("22")
+            .reverse()
+            .iter();
+        assert!(keys(rows) == [2, 1]);
+
+        // rows with equal secondary keys are split across pages without being repeated
+        for (key, value) in [(4u64, 11u64), (5, 11), (6, 11)] {
+            let args = [key.to_le_bytes(), value.to_le_bytes()].concat();
+            tester.push_action("hello", "test1", args.into(), r#"{"hello": "active"}"#).unwrap();
+        }
+        for limit in [1, 2, 3, 100] {
+            let rows = tester.get_table::<MyData>("hello", "", "counter").index(2, "i64").limit(limit).iter();
+            assert_eq!(keys(rows), [3, 1, 4, 5, 6, 2]);
+            let rows = tester.get_table::<MyData>("hello", "", "counter").index(2, "i64").limit(limit).reverse().iter();
+            assert_eq!(keys(rows), [2, 6, 5, 4, 1, 3]);
+        }
+        assert_eq!(rust_chain::tester::get_table_rows::<MyData>(&mut tester, "hello", "", "counter").unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_mock_check_failure() {
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(token::contract_apply)).unwrap();
+
+        let permissions = r#"
+        {
+            "alice": "active"
+        }
+        "#;
+        let mut args = Encoder::pack(&Name::new("hello"));
+        args.extend(Encoder::pack(&Asset::from_string("100.0000 EOS")));
+        let err = tester.push_action("hello", "create", args.into(), permissions).unwrap_err();
+        err.check_err("missing authority of hello");
+        let ret = tester.get_table_rows(false, "hello", "EOS", "stat", "", "", 1).unwrap();
+        assert!(ret["rows"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_token_client_mock() {
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(token::contract_apply)).unwrap();
+        tester.create_account("eosio", "alice", "", "", 0, 0, 0).unwrap();
+
+        let hello = Name::new("hello");
+        let alice = Name::new("alice");
+        token::client::create(&mut tester, "hello", hello, Asset::from_string("100.0000 EOS"))
+            .auth("hello")
+            .send()
+            .unwrap();
+        token::client::issue(&mut tester, "hello", hello, Asset::from_string("10.0000 EOS"), "issue".into())
+            .auth("hello@active")
+            .send()
+            .unwrap();
+        token::client::transfer(&mut tester, "hello", hello, alice, Asset::from_string("1.0000 EOS"), "".into())
+            .auth("alice")
+            .send()
+            .unwrap_err()
+            .check_err("missing authority of hello");
+        token::client::transfer(&mut tester, "hello", hello, alice, Asset::from_string("1.0000 EOS"), "".into())
+            .auth("hello")
+            .send()
+            .unwrap();
+
+        let rows = token::client::tables::accounts(&mut tester, "hello", "alice").unwrap();
+        assert!(rows.len() == 1 && rows[0].balance == Asset::from_string("1.0000 EOS"));
+        let rows = token::client::tables::stat(&mut tester, "hello", "EOS").unwrap();
+        assert!(rows[0].supply == Asset::from_string("10.0000 EOS") && rows[0].issuer == hello);
+    }
+
+    #[test]
+    fn test_inlineaction_mock() {
+        let mut tester = MockTester::new();
+        tester.set_native_apply("hello", Some(inlineaction::contract_apply)).unwrap();
+
+        let ret = inlineaction::client::sayhello(&mut tester, "hello", "bob".into())
+            .auth("hello")
+            .send()
+            .unwrap();
+        let traces = ret["action_traces"].as_array().unwrap();
+        assert!(traces.len() == 2);
+        assert!(traces[1]["act"]["name"] == "saygoodbye");
+        assert!(traces[1]["console"].as_str().unwrap().contains("goodbye bob"));
+
+        let action = inlineaction::actions::Saygoodbye::new(Name::new("hello"))
+            .to_action(PermissionLevel::new(Name::new("hello"), Name::new("active")), "bob".into());
+        assert!(action.name == Name::new("saygoodbye") && action.data == Encoder::pack(&String::from("bob")));
+    }
+
+    #[test]
+    fn test_notify_mock() {
+        let mut tester = MockTester::new();
+        tester.set_native_apply("alice", Some(sender::contract_apply)).unwrap();
+        tester.set_native_apply("bob", Some(sender::contract_apply)).unwrap();
+        tester.set_native_apply("carol", Some(sender::contract_apply)).unwrap();
+        tester.set_native_apply("hello", Some(receiver::contract_apply)).unwrap();
+
+        let args = Encoder::pack(&String::from("bob"));
+        let ret = tester.push_action("alice", "test", args.clone().into(), r#"{"alice": "active"}"#).unwrap();
+        assert!(ret["action_traces"][1]["receiver"] == "hello");
+        assert!(ret["action_traces"][1]["console"] == "++++++++receiver: bob\n");
+
+        // notifications of the same action from other contracts are ignored
+        let ret = tester.push_action("bob", "test", args.clone().into(), r#"{"bob": "active"}"#).unwrap();
+        assert!(ret["action_traces"][1]["receiver"] == "hello");
+        assert!(ret["action_traces"][1]["console"] == "");
+
+        let ret = tester.push_action("carol", "test", args.clone().into(), r#"{"carol": "active"}"#).unwrap();
+        assert!(ret["action_traces"][1]["console"] == "++++++++carol: bob\n");
+
+        // the receiver's own action of the same name is dispatched as an action
+        let ret = tester.push_action("hello", "test", args.into(), r#"{"hello": "active"}"#).unwrap();
+        assert!(ret["action_traces"][0]["console"] == "++++++++test: bob\n");
+    }
 }
@@ -0,0 +1,41 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[rust_chain::contract]
+pub mod migrationv0 {
+    use rust_chain::{
+        Name,
+        require_auth,
+    };
+
+    #[chain(table="accounts")]
+    pub struct Account {
+        #[chain(primary)]
+        pub id: u64,
+        pub balance: u64,
+    }
+
+    #[chain(main)]
+    #[allow(dead_code)]
+    pub struct Contract {
+        receiver: Name,
+        first_receiver: Name,
+        action: Name,
+    }
+
+    impl Contract {
+        pub fn new(receiver: Name, first_receiver: Name, action: Name) -> Self {
+            Self {
+                receiver: receiver,
+                first_receiver: first_receiver,
+                action: action,
+            }
+        }
+
+        #[chain(action = "open")]
+        pub fn open(&self, owner: Name, id: u64, balance: u64) {
+            require_auth(owner);
+            let db = Account::new_table(self.receiver);
+            db.store(&Account{id, balance}, owner);
+        }
+    }
+}
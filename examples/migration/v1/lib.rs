@@ -0,0 +1,52 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[rust_chain::contract]
+pub mod migrationv1 {
+    use rust_chain::{
+        Name,
+        require_auth,
+        db::MigrateRow,
+    };
+
+    #[chain(table="accounts", version=1, migrate="migrate")]
+    pub struct Account {
+        #[chain(primary)]
+        pub id: u64,
+        pub balance: u64,
+    }
+
+    impl MigrateRow for Account {
+        fn migrate(version: u8, data: &[u8]) -> (Self, usize) {
+            // rows written before the table was versioned have the same fields
+            rust_chain::check(version == 0, "unknown version of Account");
+            let mut account = Account::default();
+            let size = account.unpack_fields(data);
+            return (account, size);
+        }
+    }
+
+    #[chain(main)]
+    #[allow(dead_code)]
+    pub struct Contract {
+        receiver: Name,
+        first_receiver: Name,
+        action: Name,
+    }
+
+    impl Contract {
+        pub fn new(receiver: Name, first_receiver: Name, action: Name) -> Self {
+            Self {
+                receiver: receiver,
+                first_receiver: first_receiver,
+                action: action,
+            }
+        }
+
+        #[chain(action = "open")]
+        pub fn open(&self, owner: Name, id: u64, balance: u64) {
+            require_auth(owner);
+            let db = Account::new_table(self.receiver);
+            db.store(&Account{id, balance}, owner);
+        }
+    }
+}
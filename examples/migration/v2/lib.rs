@@ -0,0 +1,68 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[rust_chain::contract]
+pub mod migrationv2 {
+    use rust_chain::{
+        Name,
+        SAME_PAYER,
+        chain_println,
+        db::MigrateRow,
+    };
+
+    /// Row layout of version 1 of `Account`, which is also the layout of the rows written before the table was versioned.
+    #[chain(packer)]
+    pub struct AccountV1 {
+        pub id: u64,
+        pub balance: u64,
+    }
+
+    #[chain(table="accounts", version=2, migrate="migrate")]
+    pub struct Account {
+        #[chain(primary)]
+        pub id: u64,
+        pub balance: u64,
+        pub memo: String,
+    }
+
+    impl MigrateRow for Account {
+        fn migrate(version: u8, data: &[u8]) -> (Self, usize) {
+            let mut old = AccountV1::default();
+            let size = old.unpack(data);
+            let memo = if version == 0 { "v0" } else { "v1" };
+            (Account{id: old.id, balance: old.balance, memo: String::from(memo)}, size)
+        }
+    }
+
+    #[chain(main)]
+    #[allow(dead_code)]
+    pub struct Contract {
+        receiver: Name,
+        first_receiver: Name,
+        action: Name,
+    }
+
+    impl Contract {
+        pub fn new(receiver: Name, first_receiver: Name, action: Name) -> Self {
+            Self {
+                receiver: receiver,
+                first_receiver: first_receiver,
+                action: action,
+            }
+        }
+
+        #[chain(action = "show")]
+        pub fn show(&self, id: u64) {
+            let db = Account::new_table(self.receiver);
+            if let Some(account) = db.find(id).get_value() {
+                chain_println!("balance:", account.balance, "memo:", account.memo);
+            }
+        }
+
+        #[chain(action = "setmemo")]
+        pub fn set_memo(&self, id: u64, memo: String) {
+            let db = Account::new_table(self.receiver);
+            let it = db.find(id);
+            db.modify(&it, SAME_PAYER, |account| account.memo = memo);
+        }
+    }
+}
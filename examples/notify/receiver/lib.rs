@@ -32,9 +32,19 @@ mod hello {
             }
         }
 
-        #[chain(action="test", notify)]
+        #[chain(action="test")]
         pub fn test(&self, name: String) {
+            chain_println!("++++++++test:", name);
+        }
+
+        #[chain(notify="alice::test")]
+        pub fn on_alice_test(&self, name: String) {
             chain_println!("++++++++receiver:", name);
         }
+
+        #[chain(notify="carol::test")]
+        pub fn on_carol_test(&self, name: String) {
+            chain_println!("++++++++carol:", name);
+        }
     }
 }
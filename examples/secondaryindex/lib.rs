@@ -1,18 +1,30 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 #[rust_chain::contract]
-mod secondaryindex {
+pub mod secondaryindex {
     use rust_chain::{
         Name,
+        SAME_PAYER,
         chain_println,
     };
 
     #[chain(table="counter")]
     pub struct MyData {
         #[chain(primary)]
-        key: u64,
+        pub key: u64,
         #[chain(secondary)]
-        value: u64,
+        pub value: u64,
+    }
+
+    #[chain(table="orders")]
+    #[chain(secondary = "by_owner_time", fields = "owner,created")]
+    #[chain(secondary = "by_owner_price", fields = "owner,price,id")]
+    pub struct Order {
+        #[chain(primary)]
+        pub id: u64,
+        pub owner: Name,
+        pub created: i64,
+        pub price: u64,
     }
 
     #[chain(main)]
@@ -61,5 +73,64 @@ mod secondaryindex {
                 db.idx_update(&it_secondary, secondary_value.into(), payer);
             }
         }
+
+        #[chain(action = "test3")]
+        pub fn test3(&self, lower: u64, upper: u64) {
+            let db = MyData::new_table(self.receiver);
+            for (key, data) in db.range(lower..upper) {
+                chain_println!("range:", key, data.value);
+            }
+            for (key, _) in db.rev() {
+                chain_println!("rev:", key);
+            }
+            for (key, value) in db.get_idx_by_value().range(lower..=upper) {
+                chain_println!("idx:", key, value);
+            }
+        }
+
+        #[chain(action = "test4")]
+        pub fn test4(&self, value: u64) {
+            let db = MyData::new_table(self.receiver);
+            let idx = db.by_value();
+            if let Some((it, key, data)) = idx.find(value) {
+                chain_println!("find:", key, data.key);
+                idx.modify(&it, self.receiver, |data| data.value += 100);
+            }
+            if let Some((it, key, data)) = idx.lower_bound(0) {
+                chain_println!("erase:", key, data.key);
+                idx.erase(&it);
+            }
+            for (key, data) in idx.range(..) {
+                chain_println!("range:", key, data.key);
+            }
+        }
+
+        #[chain(action = "addorder")]
+        pub fn addorder(&self, id: u64, owner: Name, created: i64, price: u64) {
+            let db = Order::new_table(self.receiver);
+            let order = Order{id, owner, created, price};
+            chain_println!("ram:", db.estimate_store_cost(&order));
+            db.store(&order, self.receiver);
+        }
+
+        #[chain(action = "setprice")]
+        pub fn setprice(&self, id: u64, price: u64) {
+            let db = Order::new_table(self.receiver);
+            let it = db.find(id);
+            db.modify(&it, SAME_PAYER, |order| order.price = price);
+        }
+
+        #[chain(action = "listorders")]
+        pub fn listorders(&self, owner: Name) {
+            let db = Order::new_table(self.receiver);
+            let lower = Order::by_owner_time_key(owner, i64::MIN);
+            let upper = Order::by_owner_time_key(owner, i64::MAX);
+            for (_, order) in db.by_owner_time().range(lower..=upper) {
+                chain_println!("time:", order.id, order.created);
+            }
+            for (_, order) in db.by_owner_price().range(..) {
+                chain_println!("price:", order.id);
+            }
+        }
     }
 }
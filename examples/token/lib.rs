@@ -2,7 +2,7 @@
 
 
 #[rust_chain::contract]
-mod token {
+pub mod token {
     use rust_chain::{
         require_auth,
         check,
@@ -18,15 +18,15 @@ mod token {
     #[chain(table="accounts")]
     pub struct Account {
         #[chain(primary)]
-        balance: Asset 
+        pub balance: Asset,
     }
     
     #[chain(table="stat")]
     pub struct CurrencyStats {
         #[chain(primary)]
-        supply: Asset,
-        max_supply: Asset,
-        issuer: Name,
+        pub supply: Asset,
+        pub max_supply: Asset,
+        pub issuer: Name,
     }
 
     #[chain(main)]